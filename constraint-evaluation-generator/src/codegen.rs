@@ -1,14 +1,22 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 use proc_macro2::TokenStream;
 use quote::quote;
+use triton_vm::air::memory_layout::CoefficientOrder;
 use twenty_first::prelude::BFieldElement;
 use twenty_first::prelude::XFieldElement;
 
 use crate::constraints::Constraints;
 
+#[cfg(test)]
+mod generic_mini_constraints;
 mod rust;
 mod tasm;
+#[cfg(test)]
+mod tasm_diff;
+#[cfg(test)]
+mod unchecked_test_constraints;
 
 pub(crate) trait Codegen {
     fn constraint_evaluation_code(constraints: &Constraints) -> TokenStream;
@@ -30,6 +38,28 @@ pub(crate) struct RustBackend {
     ///
     /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
     scope: HashSet<usize>,
+
+    /// Whether the code that is to be generated reads its input rows without bounds checks.
+    /// If so, every evaluating function starts by asserting that all input rows are long
+    /// enough, which is what makes the subsequent unchecked accesses sound.
+    elide_bounds_checks: bool,
+
+    /// Whether the code that is to be generated is generic over the field it evaluates the
+    /// constraints in. If so, all inputs, challenges, and constants are elements of one and the
+    /// same field `FF`, instead of being [`BFieldElement`]s and [`XFieldElement`]s.
+    generic_field: bool,
+}
+
+/// Constraint evaluation code that is split into one module per table, plus a dispatcher.
+/// See [`RustBackend::constraint_evaluation_code_split_by_table`].
+#[derive(Debug, Clone)]
+pub(crate) struct SplitConstraintEvaluationCode {
+    /// The code of the module `constraints`, which declares the per-table modules and
+    /// evaluates the constraints by dispatching to them.
+    pub dispatcher: TokenStream,
+
+    /// The name and code of each per-table module, in the order of their constraints.
+    pub tables: Vec<(&'static str, TokenStream)>,
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -39,16 +69,88 @@ pub(crate) struct TasmBackend {
     /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
     scope: HashSet<usize>,
 
+    /// The [circuit] IDs of shared nodes that are never stored to memory. Both uses of such a
+    /// node are the operands of one and the same binary operation, which is why duplicating the
+    /// node's value on the stack right after computing it suffices.
+    ///
+    /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
+    duplicated_on_stack: HashSet<usize>,
+
+    /// The [circuit] IDs of shared nodes that are kept on the stack instead of being stored to
+    /// memory, each mapped to the index of the last [stored node](Self::stored_nodes) using it.
+    /// The value of such a node is dropped from the stack right after storing that last user.
+    ///
+    /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
+    kept_on_stack: HashMap<usize, usize>,
+
+    /// Whether shared nodes are kept on the stack instead of being stored to memory where
+    /// possible. See [`duplicated_on_stack`](Self::duplicated_on_stack) and
+    /// [`kept_on_stack`](Self::kept_on_stack).
+    keep_shared_nodes_on_stack: bool,
+
+    /// The [circuit] IDs of all shared nodes processed so far, in the order of their
+    /// evaluation, including those kept on the stack.
+    ///
+    /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
+    stored_nodes: Vec<usize>,
+
+    /// All loads of stored nodes emitted so far.
+    node_loads: Vec<NodeLoad>,
+
+    /// Whether adding a [base field][bfe] constant uses a single `addi` instead of loading the
+    /// constant as an extension field element and adding it with `xx_add`.
+    ///
+    /// [bfe]: twenty_first::prelude::BFieldElement
+    add_base_field_constants_with_addi: bool,
+
     /// The number of elements written to the output list.
     elements_written: usize,
 
     /// Whether the code that is to be generated can assume statically provided
     /// addresses for the various input arrays.
     input_location_is_static: bool,
+
+    /// The order in which the coefficients of the evaluated constraints are written to the
+    /// output array. Intermediate values are always stored in little-endian order, since they
+    /// are read back only by the emitted code itself.
+    coefficient_order: CoefficientOrder,
+
+    /// Whether the evaluated constraints are left on the op stack instead of being written to
+    /// the output array in memory.
+    results_on_stack: bool,
+}
+
+/// A load of a shared node's value, emitted by the [`TasmBackend`] while evaluating another node.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct NodeLoad {
+    /// The [circuit] ID of the loaded node.
+    ///
+    /// [circuit]: triton_vm::table::constraint_circuit::ConstraintCircuit
+    node_id: usize,
+
+    /// The index of the [stored node](TasmBackend::stored_nodes) whose evaluation requires the
+    /// load, or `None` if a constraint's evaluation requires it.
+    stored_node_index: Option<usize>,
+
+    /// The number of words on the stack on top of those present before the evaluation requiring
+    /// the load started.
+    stack_height: usize,
 }
 
 #[cfg(test)]
 pub mod tests {
+    use std::collections::BTreeMap;
+
+    use syn::Expr;
+    use syn::Pat;
+    use syn::Stmt;
+    use triton_vm::instruction::Instruction;
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::op_stack::NumberOfWords;
+    use triton_vm::prelude::triton_asm;
+    use triton_vm::table::constraint_circuit::ConstraintCircuit;
+    use triton_vm::table::constraint_circuit::InputIndicator;
+
     use super::*;
 
     pub fn print_constraints<B: Codegen>(constraints: &Constraints) {
@@ -57,4 +159,101 @@ pub mod tests {
         let code = prettyplease::unparse(&syntax_tree);
         println!("{code}");
     }
+
+    /// How often each opcode occurs in the given instructions. Arguments are ignored: all
+    /// instructions sharing an opcode are counted towards the same key, which is the instruction
+    /// with its default argument. Anything but [instructions](LabelledInstruction::Instruction),
+    /// like labels, is ignored, too.
+    pub fn opcode_histogram(instructions: &[LabelledInstruction]) -> BTreeMap<Instruction, usize> {
+        let mut histogram = BTreeMap::new();
+        for instruction in instructions {
+            let LabelledInstruction::Instruction(instruction) = instruction else {
+                continue;
+            };
+            let opcode = Instruction::try_from(instruction.opcode()).unwrap();
+            *histogram.entry(opcode).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// The number of constraints the [`RustBackend`] emits code for, determined by counting the
+    /// elements of the arrays of evaluated base and extension constraints.
+    fn num_constraints_emitted_by_rust_backend<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+    ) -> usize {
+        let (_, _, evaluation_code) = RustBackend::default().tokenize_circuits(constraints);
+        let evaluation_code: syn::Block = syn::parse2(quote!({ #evaluation_code })).unwrap();
+
+        let num_array_elements = |statement: &Stmt| {
+            let Stmt::Local(local) = statement else {
+                return 0;
+            };
+            let Pat::Ident(pattern) = &local.pat else {
+                return 0;
+            };
+            if !["base_constraints", "ext_constraints"].contains(&&*pattern.ident.to_string()) {
+                return 0;
+            }
+            let Some(Expr::Array(array)) = local.init.as_ref().map(|init| &*init.expr) else {
+                panic!("constraints must be evaluated into an array");
+            };
+            array.elems.len()
+        };
+        evaluation_code.stmts.iter().map(num_array_elements).sum()
+    }
+
+    /// The number of constraints the [`TasmBackend`] emits code for, determined by counting the
+    /// elements written to the output list.
+    fn num_constraints_emitted_by_tasm_backend<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+    ) -> usize {
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let _ = backend.tokenize_circuits(constraints);
+        backend.elements_written
+    }
+
+    fn assert_backends_emit_all_constraints<II: InputIndicator>(
+        category: &str,
+        constraints: &[ConstraintCircuit<II>],
+    ) {
+        let num_constraints = constraints.len();
+        let num_rust_constraints = num_constraints_emitted_by_rust_backend(constraints);
+        let num_tasm_constraints = num_constraints_emitted_by_tasm_backend(constraints);
+        assert_eq!(
+            (num_constraints, num_constraints),
+            (num_rust_constraints, num_tasm_constraints),
+            "number of {category} constraints must match; (expected, expected) vs (Rust, TASM)",
+        );
+    }
+
+    #[test]
+    fn opcode_histogram_ignores_labels_and_arguments() {
+        let instructions = triton_asm!(
+            start:
+                push 1 push 2 add
+            end:
+                pop 1 push 3
+        );
+        let histogram = opcode_histogram(&instructions);
+
+        let push = Instruction::Push(BFieldElement::new(0));
+        let pop = Instruction::Pop(NumberOfWords::N1);
+        let expected = BTreeMap::from([(push, 3), (Instruction::Add, 1), (pop, 1)]);
+        assert_eq!(expected, histogram);
+    }
+
+    #[test]
+    fn rust_and_tasm_backends_emit_the_same_number_of_constraints() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        // The number of constraints per category is what the `RustBackend` emits as, for
+        // example, `MasterExtTable::NUM_INITIAL_CONSTRAINTS`.
+        assert_backends_emit_all_constraints("initial", &constraints.init());
+        assert_backends_emit_all_constraints("consistency", &constraints.cons());
+        assert_backends_emit_all_constraints("transition", &constraints.tran());
+        assert_backends_emit_all_constraints("terminal", &constraints.term());
+    }
 }
@@ -0,0 +1,64 @@
+//! The [Rust backend's](super::RustBackend) code evaluating the
+//! [mini constraints](crate::constraints::Constraints::mini_constraints) generically over the
+//! field. Checked in so that the generated code is type-checked by the compiler.
+//!
+//! Test `generic_mini_constraints_fixture_is_up_to_date` makes sure this file stays in sync with
+//! the code generator. To update, replace everything below with the output of test
+//! `print_mini_constraints_generic_over_field`.
+
+use ndarray::ArrayView1;
+use twenty_first::math::traits::FiniteField;
+use twenty_first::prelude::BFieldElement;
+#[allow(unused_variables)]
+pub fn evaluate_initial_constraints<FF>(
+    base_row: ArrayView1<FF>,
+    ext_row: ArrayView1<FF>,
+    challenges: &[FF],
+) -> Vec<FF>
+where
+    FF: FiniteField + From<BFieldElement>,
+{
+    let base_constraints: [FF; 0usize] = [];
+    let ext_constraints: [FF; 1usize] = [((base_row[0usize]) * (challenges[37usize]))
+        + ((FF::from(BFieldElement::from_raw_u64(18446744065119617026u64)))
+            * ((ext_row[1usize]) * (FF::from(BFieldElement::from_raw_u64(180388626390u64)))))];
+    base_constraints
+        .into_iter()
+        .chain(ext_constraints)
+        .collect()
+}
+#[allow(unused_variables)]
+pub fn evaluate_consistency_constraints<FF>(
+    base_row: ArrayView1<FF>,
+    ext_row: ArrayView1<FF>,
+    challenges: &[FF],
+) -> Vec<FF>
+where
+    FF: FiniteField + From<BFieldElement>,
+{
+    vec![]
+}
+#[allow(unused_variables)]
+pub fn evaluate_transition_constraints<FF>(
+    current_base_row: ArrayView1<FF>,
+    current_ext_row: ArrayView1<FF>,
+    next_base_row: ArrayView1<FF>,
+    next_ext_row: ArrayView1<FF>,
+    challenges: &[FF],
+) -> Vec<FF>
+where
+    FF: FiniteField + From<BFieldElement>,
+{
+    vec![]
+}
+#[allow(unused_variables)]
+pub fn evaluate_terminal_constraints<FF>(
+    base_row: ArrayView1<FF>,
+    ext_row: ArrayView1<FF>,
+    challenges: &[FF],
+) -> Vec<FF>
+where
+    FF: FiniteField + From<BFieldElement>,
+{
+    vec![]
+}
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
 use itertools::Itertools;
 use proc_macro2::TokenStream;
+use proc_macro2::TokenTree;
 use quote::format_ident;
 use quote::quote;
 
@@ -9,23 +13,442 @@ use triton_vm::table::constraint_circuit::InputIndicator;
 
 use crate::codegen::Codegen;
 use crate::codegen::RustBackend;
+use crate::codegen::SplitConstraintEvaluationCode;
 use crate::Constraints;
 
 impl Codegen for RustBackend {
     fn constraint_evaluation_code(constraints: &Constraints) -> TokenStream {
-        let num_init_constraints = constraints.init.len();
-        let num_cons_constraints = constraints.cons.len();
-        let num_tran_constraints = constraints.tran.len();
-        let num_term_constraints = constraints.term.len();
+        Self::with_bounds_checks().tokenize_constraint_evaluation_code(constraints)
+    }
+}
+
+impl RustBackend {
+    fn with_bounds_checks() -> Self {
+        Self {
+            scope: HashSet::new(),
+            elide_bounds_checks: false,
+            generic_field: false,
+        }
+    }
+
+    fn with_generic_field() -> Self {
+        Self {
+            generic_field: true,
+            ..Self::with_bounds_checks()
+        }
+    }
+
+    fn with_elided_bounds_checks() -> Self {
+        Self {
+            elide_bounds_checks: true,
+            ..Self::with_bounds_checks()
+        }
+    }
+
+    /// Like [`constraint_evaluation_code`][code], except that the generated code accesses the
+    /// input rows without bounds checks. Instead, every evaluating function asserts up front
+    /// that all its input rows are long enough for every access it performs.
+    ///
+    /// [code]: Codegen::constraint_evaluation_code
+    pub(crate) fn constraint_evaluation_code_with_elided_bounds_checks(
+        constraints: &Constraints,
+    ) -> TokenStream {
+        Self::with_elided_bounds_checks().tokenize_constraint_evaluation_code(constraints)
+    }
+
+    /// Code evaluating the constraints [without bounds checks][elided] on the input rows, like
+    /// the production code generated with `--elide-bounds-checks`, but implementing
+    /// [`Evaluable`][evaluable] for the dedicated type `UncheckedConstraints` instead of for
+    /// the master extension table. Since the code refers to Triton VM as an external crate, it
+    /// can be compiled as part of the generator's tests, which compare its evaluations with those
+    /// of the constraint circuits.
+    ///
+    /// [elided]: Self::constraint_evaluation_code_with_elided_bounds_checks
+    /// [evaluable]: triton_vm::table::extension_table::Evaluable
+    #[cfg(test)]
+    pub(crate) fn unchecked_constraint_evaluation_code(constraints: &Constraints) -> TokenStream {
+        let mut backend = Self::with_elided_bounds_checks();
+        let (_, init_constraints_bfe, init_constraints_xfe) =
+            backend.tokenize_circuits(&constraints.init());
+        let (_, cons_constraints_bfe, cons_constraints_xfe) =
+            backend.tokenize_circuits(&constraints.cons());
+        let (_, tran_constraints_bfe, tran_constraints_xfe) =
+            backend.tokenize_circuits(&constraints.tran());
+        let (_, term_constraints_bfe, term_constraints_xfe) =
+            backend.tokenize_circuits(&constraints.term());
+
+        let evaluable_over_base_field = Self::generate_evaluable_implementation_over_field(
+            &init_constraints_bfe,
+            &cons_constraints_bfe,
+            &tran_constraints_bfe,
+            &term_constraints_bfe,
+            quote!(BFieldElement),
+            quote!(UncheckedConstraints),
+        );
+        let evaluable_over_ext_field = Self::generate_evaluable_implementation_over_field(
+            &init_constraints_xfe,
+            &cons_constraints_xfe,
+            &tran_constraints_xfe,
+            &term_constraints_xfe,
+            quote!(XFieldElement),
+            quote!(UncheckedConstraints),
+        );
+
+        quote!(
+            use ndarray::ArrayView1;
+            use triton_vm::table::challenges::Challenges;
+            use triton_vm::table::extension_table::Evaluable;
+            use twenty_first::prelude::BFieldElement;
+            use twenty_first::prelude::XFieldElement;
+
+            /// Evaluates the AIR constraints like [`MasterExtTable`][table], but accesses the
+            /// input rows without bounds checks.
+            ///
+            /// [table]: triton_vm::table::master_table::MasterExtTable
+            #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+            pub struct UncheckedConstraints;
+
+            #evaluable_over_base_field
+            #evaluable_over_ext_field
+        )
+    }
+
+    /// Like [`constraint_evaluation_code`][code], except that the generated functions are generic
+    /// over the field `FF` in which the constraints are evaluated. This allows monomorphizing
+    /// the same constraint logic for different field types, for example alternative extension
+    /// fields. Since all inputs, challenges, and values are elements of `FF`, no
+    /// [`Evaluable`][evaluable] implementation is generated; the production code remains the
+    /// concrete one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the constraints contains an [extension field][xfe] constant that is not
+    /// also a [base field][bfe] element, since `FF` only needs to be constructible from the
+    /// latter.
+    ///
+    /// [code]: Codegen::constraint_evaluation_code
+    /// [evaluable]: triton_vm::table::extension_table::Evaluable
+    /// [xfe]: twenty_first::prelude::XFieldElement
+    /// [bfe]: twenty_first::prelude::BFieldElement
+    pub(crate) fn constraint_evaluation_code_generic_over_field(
+        constraints: &Constraints,
+    ) -> TokenStream {
+        let mut backend = Self::with_generic_field();
+        let single_row_args = Self::generic_single_row_args();
+        let dual_row_args = Self::generic_dual_row_args();
+        let init =
+            backend.tokenize_generic_function(&constraints.init(), "initial", &single_row_args);
+        let cons =
+            backend.tokenize_generic_function(&constraints.cons(), "consistency", &single_row_args);
+        let tran =
+            backend.tokenize_generic_function(&constraints.tran(), "transition", &dual_row_args);
+        let term =
+            backend.tokenize_generic_function(&constraints.term(), "terminal", &single_row_args);
+
+        quote!(
+            use ndarray::ArrayView1;
+            use twenty_first::math::traits::FiniteField;
+            use twenty_first::prelude::BFieldElement;
+
+            #init
+            #cons
+            #tran
+            #term
+        )
+    }
+
+    /// A function evaluating the given constraints over the generic field `FF`, returning the
+    /// values of all base constraints followed by those of all extension constraints.
+    fn tokenize_generic_function<II: InputIndicator>(
+        &mut self,
+        constraints: &[ConstraintCircuit<II>],
+        constraint_type: &str,
+        args: &TokenStream,
+    ) -> TokenStream {
+        let name = format_ident!("evaluate_{constraint_type}_constraints");
+        let body = match constraints.is_empty() {
+            true => quote!(vec![]),
+            false => {
+                let (shared_declarations, base_constraints, ext_constraints) =
+                    self.tokenize_partitioned_circuits(constraints);
+                let num_base_constraints = base_constraints.len();
+                let num_ext_constraints = ext_constraints.len();
+                quote!(
+                    #(#shared_declarations)*
+                    let base_constraints: [FF; #num_base_constraints] = [#(#base_constraints),*];
+                    let ext_constraints: [FF; #num_ext_constraints] = [#(#ext_constraints),*];
+                    base_constraints
+                        .into_iter()
+                        .chain(ext_constraints)
+                        .collect()
+                )
+            }
+        };
+
+        quote!(
+            #[allow(unused_variables)]
+            pub fn #name<FF>(#args) -> Vec<FF>
+            where
+                FF: FiniteField + From<BFieldElement>,
+            {
+                #body
+            }
+        )
+    }
+
+    fn generic_single_row_args() -> TokenStream {
+        quote!(base_row: ArrayView1<FF>, ext_row: ArrayView1<FF>, challenges: &[FF])
+    }
+
+    fn generic_dual_row_args() -> TokenStream {
+        quote!(
+            current_base_row: ArrayView1<FF>,
+            current_ext_row: ArrayView1<FF>,
+            next_base_row: ArrayView1<FF>,
+            next_ext_row: ArrayView1<FF>,
+            challenges: &[FF],
+        )
+    }
+
+    /// Like [`constraint_evaluation_code`][code], except that the code is split into one module
+    /// per table and a dispatcher, allowing the tables' code to be compiled in parallel. The
+    /// dispatcher evaluates the constraints of all tables and returns their values in the same
+    /// order as the monolithic code: the values of all base constraints, then those of all
+    /// extension constraints.
+    ///
+    /// [code]: Codegen::constraint_evaluation_code
+    pub(crate) fn constraint_evaluation_code_split_by_table(
+        constraints: &Constraints,
+        elide_bounds_checks: bool,
+    ) -> SplitConstraintEvaluationCode {
+        let mut backend = match elide_bounds_checks {
+            true => Self::with_elided_bounds_checks(),
+            false => Self::with_bounds_checks(),
+        };
+
+        let tables = constraints
+            .split_by_table()
+            .into_iter()
+            .map(|(table, constraints)| (table, backend.tokenize_table_module(&constraints)))
+            .collect_vec();
+        let dispatcher = Self::tokenize_dispatcher(constraints);
+
+        SplitConstraintEvaluationCode { dispatcher, tables }
+    }
+
+    fn tokenize_table_module(&mut self, constraints: &Constraints) -> TokenStream {
+        let single_row_args = Self::single_row_args;
+        let dual_row_args = Self::dual_row_args;
+        let init = self.tokenize_table_functions(&constraints.init(), "initial", single_row_args);
+        let cons =
+            self.tokenize_table_functions(&constraints.cons(), "consistency", single_row_args);
+        let tran = self.tokenize_table_functions(&constraints.tran(), "transition", dual_row_args);
+        let term = self.tokenize_table_functions(&constraints.term(), "terminal", single_row_args);
+
+        quote!(
+            use ndarray::ArrayView1;
+            use twenty_first::prelude::BFieldElement;
+            use twenty_first::prelude::XFieldElement;
+
+            use crate::table::challenges::Challenges;
+
+            #init
+            #cons
+            #tran
+            #term
+        )
+    }
 
+    /// The functions evaluating the given constraints of one table, one for each field the base
+    /// row can be over. Each function returns the values of the base constraints separately from
+    /// the values of the extension constraints, allowing the dispatcher to order them like the
+    /// monolithic code does. If there are no constraints, no functions are generated.
+    fn tokenize_table_functions<II: InputIndicator>(
+        &mut self,
+        constraints: &[ConstraintCircuit<II>],
+        constraint_type: &str,
+        args: fn(TokenStream) -> TokenStream,
+    ) -> TokenStream {
+        if constraints.is_empty() {
+            return quote!();
+        }
+
+        let (shared_declarations, tokenized_base_constraints, tokenized_ext_constraints) =
+            self.tokenize_partitioned_circuits(constraints);
+        let declare_constraints = |name, field, tokenized_constraints: &[TokenStream]| {
+            let name = format_ident!("{name}");
+            match tokenized_constraints.is_empty() {
+                true => quote!(let #name: [#field; 0] = [];),
+                false => quote!(let #name = [#(#tokenized_constraints),*];),
+            }
+        };
+
+        let bfe_base_constraints = declare_constraints(
+            "base_constraints",
+            quote!(BFieldElement),
+            &tokenized_base_constraints,
+        );
+        let xfe_base_constraints = declare_constraints(
+            "base_constraints",
+            quote!(XFieldElement),
+            &tokenized_base_constraints,
+        );
+        let ext_constraints = declare_constraints(
+            "ext_constraints",
+            quote!(XFieldElement),
+            &tokenized_ext_constraints,
+        );
+
+        let tokenized_bfe_constraints = quote!(
+            #(#shared_declarations)*
+            #bfe_base_constraints
+            #ext_constraints
+            let base_constraints = base_constraints.into_iter().map(|bfe| bfe.lift()).collect();
+            (base_constraints, ext_constraints.to_vec())
+        );
+        let tokenized_xfe_constraints = quote!(
+            #(#shared_declarations)*
+            #xfe_base_constraints
+            #ext_constraints
+            (base_constraints.to_vec(), ext_constraints.to_vec())
+        );
+
+        let (tokenized_bfe_constraints, tokenized_xfe_constraints) = match self.elide_bounds_checks
+        {
+            true => (
+                Self::guard_unchecked_accesses(constraints, tokenized_bfe_constraints),
+                Self::guard_unchecked_accesses(constraints, tokenized_xfe_constraints),
+            ),
+            false => (tokenized_bfe_constraints, tokenized_xfe_constraints),
+        };
+
+        let function = |field, tokenized_constraints| {
+            let name = format_ident!("evaluate_{constraint_type}_constraints_{field}");
+            let field = match field {
+                "bfe" => quote!(BFieldElement),
+                _ => quote!(XFieldElement),
+            };
+            let args = args(field);
+            quote!(
+                #[allow(unused_variables)]
+                pub(super) fn #name(#args) -> (Vec<XFieldElement>, Vec<XFieldElement>) {
+                    #tokenized_constraints
+                }
+            )
+        };
+        let bfe_function = function("bfe", tokenized_bfe_constraints);
+        let xfe_function = function("xfe", tokenized_xfe_constraints);
+
+        quote!(
+            #bfe_function
+            #xfe_function
+        )
+    }
+
+    fn single_row_args(field: TokenStream) -> TokenStream {
+        quote!(
+            base_row: ArrayView1<#field>,
+            ext_row: ArrayView1<XFieldElement>,
+            challenges: &Challenges,
+        )
+    }
+
+    fn dual_row_args(field: TokenStream) -> TokenStream {
+        quote!(
+            current_base_row: ArrayView1<#field>,
+            current_ext_row: ArrayView1<XFieldElement>,
+            next_base_row: ArrayView1<#field>,
+            next_ext_row: ArrayView1<XFieldElement>,
+            challenges: &Challenges,
+        )
+    }
+
+    /// The module `constraints`, which declares the per-table modules and implements
+    /// [`Evaluable`][evaluable] as well as [`Quotientable`][quotientable] by dispatching to them.
+    ///
+    /// [evaluable]: triton_vm::table::extension_table::Evaluable
+    /// [quotientable]: triton_vm::table::extension_table::Quotientable
+    fn tokenize_dispatcher(constraints: &Constraints) -> TokenStream {
+        let tables = constraints.split_by_table();
+        let modules = tables.iter().map(|(table, _)| format_ident!("{table}"));
+
+        let dispatch = |constraint_type, field, num_constraints: fn(&Constraints) -> usize| {
+            let name = format_ident!("evaluate_{constraint_type}_constraints_{field}");
+            let args = match constraint_type {
+                "transition" => quote!(
+                    current_base_row,
+                    current_ext_row,
+                    next_base_row,
+                    next_ext_row,
+                    challenges
+                ),
+                _ => quote!(base_row, ext_row, challenges),
+            };
+            let calls = tables
+                .iter()
+                .filter(|(_, constraints)| num_constraints(constraints) > 0)
+                .map(|(table, _)| format_ident!("{table}"))
+                .map(|table| quote!(#table::#name(#args)))
+                .collect_vec();
+            if calls.is_empty() {
+                return quote!(vec![]);
+            }
+            quote!(
+                let (base_constraints, ext_constraints): (Vec<_>, Vec<_>) =
+                    [#(#calls),*].into_iter().unzip();
+                base_constraints
+                    .concat()
+                    .into_iter()
+                    .chain(ext_constraints.concat())
+                    .collect()
+            )
+        };
+
+        let evaluable_over_base_field = Self::generate_evaluable_implementation_over_field(
+            &dispatch("initial", "bfe", Constraints::num_init),
+            &dispatch("consistency", "bfe", Constraints::num_cons),
+            &dispatch("transition", "bfe", Constraints::num_tran),
+            &dispatch("terminal", "bfe", Constraints::num_term),
+            quote!(BFieldElement),
+            quote!(MasterExtTable),
+        );
+        let evaluable_over_ext_field = Self::generate_evaluable_implementation_over_field(
+            &dispatch("initial", "xfe", Constraints::num_init),
+            &dispatch("consistency", "xfe", Constraints::num_cons),
+            &dispatch("transition", "xfe", Constraints::num_tran),
+            &dispatch("terminal", "xfe", Constraints::num_term),
+            quote!(XFieldElement),
+            quote!(MasterExtTable),
+        );
+
+        let quotient_trait_impl = Self::generate_quotientable_implementation(
+            constraints,
+            &Self::tokenize_degree_bounds(&constraints.init()),
+            &Self::tokenize_degree_bounds(&constraints.cons()),
+            &Self::tokenize_degree_bounds(&constraints.tran()),
+            &Self::tokenize_degree_bounds(&constraints.term()),
+        );
+
+        let uses = Self::uses();
+        quote!(
+            #(mod #modules;)*
+
+            #uses
+            #evaluable_over_base_field
+            #evaluable_over_ext_field
+            #quotient_trait_impl
+        )
+    }
+
+    fn tokenize_constraint_evaluation_code(&mut self, constraints: &Constraints) -> TokenStream {
         let (init_constraint_degrees, init_constraints_bfe, init_constraints_xfe) =
-            Self::tokenize_circuits(&constraints.init());
+            self.tokenize_circuits(&constraints.init());
         let (cons_constraint_degrees, cons_constraints_bfe, cons_constraints_xfe) =
-            Self::tokenize_circuits(&constraints.cons());
+            self.tokenize_circuits(&constraints.cons());
         let (tran_constraint_degrees, tran_constraints_bfe, tran_constraints_xfe) =
-            Self::tokenize_circuits(&constraints.tran());
+            self.tokenize_circuits(&constraints.tran());
         let (term_constraint_degrees, term_constraints_bfe, term_constraints_xfe) =
-            Self::tokenize_circuits(&constraints.term());
+            self.tokenize_circuits(&constraints.term());
 
         let uses = Self::uses();
         let evaluable_over_base_field = Self::generate_evaluable_implementation_over_field(
@@ -34,6 +457,7 @@ impl Codegen for RustBackend {
             &tran_constraints_bfe,
             &term_constraints_bfe,
             quote!(BFieldElement),
+            quote!(MasterExtTable),
         );
         let evaluable_over_ext_field = Self::generate_evaluable_implementation_over_field(
             &init_constraints_xfe,
@@ -41,9 +465,38 @@ impl Codegen for RustBackend {
             &tran_constraints_xfe,
             &term_constraints_xfe,
             quote!(XFieldElement),
+            quote!(MasterExtTable),
         );
 
-        let quotient_trait_impl = quote!(
+        let quotient_trait_impl = Self::generate_quotientable_implementation(
+            constraints,
+            &init_constraint_degrees,
+            &cons_constraint_degrees,
+            &tran_constraint_degrees,
+            &term_constraint_degrees,
+        );
+
+        quote!(
+            #uses
+            #evaluable_over_base_field
+            #evaluable_over_ext_field
+            #quotient_trait_impl
+        )
+    }
+
+    fn generate_quotientable_implementation(
+        constraints: &Constraints,
+        init_constraint_degrees: &TokenStream,
+        cons_constraint_degrees: &TokenStream,
+        tran_constraint_degrees: &TokenStream,
+        term_constraint_degrees: &TokenStream,
+    ) -> TokenStream {
+        let num_init_constraints = constraints.num_init();
+        let num_cons_constraints = constraints.num_cons();
+        let num_tran_constraints = constraints.num_tran();
+        let num_term_constraints = constraints.num_term();
+
+        quote!(
         impl Quotientable for MasterExtTable {
             const NUM_INITIAL_CONSTRAINTS: usize = #num_init_constraints;
             const NUM_CONSISTENCY_CONSTRAINTS: usize = #num_cons_constraints;
@@ -80,18 +533,9 @@ impl Codegen for RustBackend {
                 [#term_constraint_degrees].to_vec()
             }
         }
-        );
-
-        quote!(
-            #uses
-            #evaluable_over_base_field
-            #evaluable_over_ext_field
-            #quotient_trait_impl
         )
     }
-}
 
-impl RustBackend {
     fn uses() -> TokenStream {
         quote!(
             use ndarray::ArrayView1;
@@ -111,9 +555,10 @@ impl RustBackend {
         tran_constraints: &TokenStream,
         term_constraints: &TokenStream,
         field: TokenStream,
+        implementor: TokenStream,
     ) -> TokenStream {
         quote!(
-        impl Evaluable<#field> for MasterExtTable {
+        impl Evaluable<#field> for #implementor {
             #[allow(unused_variables)]
             fn evaluate_initial_constraints(
                 base_row: ArrayView1<#field>,
@@ -162,44 +607,20 @@ impl RustBackend {
     ///    with the input type for the base row being `BFieldElement`, and
     /// 1. the third stream is like the second, except that the input type for the base row is
     ///    `XFieldElement`.
-    fn tokenize_circuits<II: InputIndicator>(
+    pub(super) fn tokenize_circuits<II: InputIndicator>(
+        &mut self,
         constraints: &[ConstraintCircuit<II>],
     ) -> (TokenStream, TokenStream, TokenStream) {
         if constraints.is_empty() {
             return (quote!(), quote!(vec![]), quote!(vec![]));
         }
 
-        let mut backend = Self::default();
-        let shared_declarations = backend.declare_shared_nodes(constraints);
-        let (base_constraints, ext_constraints): (Vec<_>, Vec<_>) = constraints
-            .iter()
-            .partition(|constraint| constraint.evaluates_to_base_element());
-
-        // The order of the constraints' degrees must match the order of the constraints.
-        // Hence, listing the degrees is only possible after the partition into base and extension
-        // constraints is known.
-        let tokenized_degree_bounds = base_constraints
-            .iter()
-            .chain(&ext_constraints)
-            .map(|circuit| match circuit.degree() {
-                d if d > 1 => quote!(interpolant_degree * #d - zerofier_degree),
-                1 => quote!(interpolant_degree - zerofier_degree),
-                _ => panic!("Constraint degree must be positive"),
-            })
-            .collect_vec();
-        let tokenized_degree_bounds = quote!(#(#tokenized_degree_bounds),*);
-
-        let tokenize_constraint_evaluation = |constraints: &[&ConstraintCircuit<II>]| {
-            constraints
-                .iter()
-                .map(|constraint| backend.evaluate_single_node(constraint))
-                .collect_vec()
-        };
-        let tokenized_base_constraints = tokenize_constraint_evaluation(&base_constraints);
-        let tokenized_ext_constraints = tokenize_constraint_evaluation(&ext_constraints);
+        let tokenized_degree_bounds = Self::tokenize_degree_bounds(constraints);
+        let (shared_declarations, tokenized_base_constraints, tokenized_ext_constraints) =
+            self.tokenize_partitioned_circuits(constraints);
 
         // If there are no base constraints, the type needs to be explicitly declared.
-        let tokenized_bfe_base_constraints = match base_constraints.is_empty() {
+        let tokenized_bfe_base_constraints = match tokenized_base_constraints.is_empty() {
             true => quote!(let base_constraints: [BFieldElement; 0] = []),
             false => quote!(let base_constraints = [#(#tokenized_base_constraints),*]),
         };
@@ -224,6 +645,15 @@ impl RustBackend {
                 .collect()
         );
 
+        let (tokenized_bfe_constraints, tokenized_xfe_constraints) = match self.elide_bounds_checks
+        {
+            true => (
+                Self::guard_unchecked_accesses(constraints, tokenized_bfe_constraints),
+                Self::guard_unchecked_accesses(constraints, tokenized_xfe_constraints),
+            ),
+            false => (tokenized_bfe_constraints, tokenized_xfe_constraints),
+        };
+
         (
             tokenized_degree_bounds,
             tokenized_bfe_constraints,
@@ -231,6 +661,56 @@ impl RustBackend {
         )
     }
 
+    /// Code that, when evaluated, produces the degree bounds of the given constraints' quotients.
+    /// The order of the degree bounds matches the order of the constraints' values in the code
+    /// produced by [`tokenize_circuits`](Self::tokenize_circuits): first those of all base
+    /// constraints, then those of all extension constraints.
+    fn tokenize_degree_bounds<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+    ) -> TokenStream {
+        let (base_constraints, ext_constraints): (Vec<_>, Vec<_>) = constraints
+            .iter()
+            .partition(|constraint| constraint.evaluates_to_base_element());
+        let tokenized_degree_bounds = base_constraints
+            .iter()
+            .chain(&ext_constraints)
+            .map(|circuit| match circuit.degree() {
+                d if d > 1 => quote!(interpolant_degree * #d - zerofier_degree),
+                1 => quote!(interpolant_degree - zerofier_degree),
+                _ => panic!("Constraint degree must be positive"),
+            })
+            .collect_vec();
+        quote!(#(#tokenized_degree_bounds),*)
+    }
+
+    /// The declarations of all shared nodes, followed by the code evaluating each of the base
+    /// constraints and the code evaluating each of the extension constraints.
+    fn tokenize_partitioned_circuits<II: InputIndicator>(
+        &mut self,
+        constraints: &[ConstraintCircuit<II>],
+    ) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>) {
+        self.scope = HashSet::new();
+        let shared_declarations = self.declare_shared_nodes(constraints);
+        let (base_constraints, ext_constraints): (Vec<_>, Vec<_>) = constraints
+            .iter()
+            .partition(|constraint| constraint.evaluates_to_base_element());
+
+        let tokenized_base_constraints = base_constraints
+            .iter()
+            .map(|constraint| self.evaluate_single_node(constraint))
+            .collect();
+        let tokenized_ext_constraints = ext_constraints
+            .iter()
+            .map(|constraint| self.evaluate_single_node(constraint))
+            .collect();
+
+        (
+            shared_declarations,
+            tokenized_base_constraints,
+            tokenized_ext_constraints,
+        )
+    }
+
     /// Declare all shared variables, i.e., those with a ref count greater than 1.
     /// These declarations must be made starting from the highest ref count.
     /// Otherwise, the resulting code will refer to bindings that have not yet been made.
@@ -286,7 +766,7 @@ impl RustBackend {
         }
 
         assert_eq!(circuit.ref_count, ref_count);
-        let binding_name = Self::binding_name(circuit);
+        let binding_name = self.binding_name(circuit);
         let evaluation = self.evaluate_single_node(circuit);
         let new_binding = quote!(let #binding_name = #evaluation;);
 
@@ -302,11 +782,11 @@ impl RustBackend {
         circuit: &ConstraintCircuit<II>,
     ) -> TokenStream {
         if self.scope.contains(&circuit.id) {
-            return Self::binding_name(circuit);
+            return self.binding_name(circuit);
         }
 
         let CircuitExpression::BinaryOperation(binop, lhs, rhs) = &circuit.expression else {
-            return Self::binding_name(circuit);
+            return self.binding_name(circuit);
         };
 
         let lhs = self.evaluate_single_node(&lhs.borrow());
@@ -314,11 +794,100 @@ impl RustBackend {
         quote!((#lhs) #binop (#rhs))
     }
 
-    fn binding_name<II: InputIndicator>(circuit: &ConstraintCircuit<II>) -> TokenStream {
+    /// Prefix the given function body with an assertion that all input rows are long enough
+    /// for every input accessed by the given constraints, then evaluate the body in an `unsafe`
+    /// block. The assertion upholds the safety precondition of all unchecked accesses.
+    fn guard_unchecked_accesses<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+        body: TokenStream,
+    ) -> TokenStream {
+        let min_row_lengths = Self::min_row_lengths(constraints);
+        if min_row_lengths.is_empty() {
+            return body;
+        }
+
+        let length_requirements = min_row_lengths.into_iter().map(|(row, min_len)| {
+            let row = format_ident!("{row}");
+            quote!(#row.len() >= #min_len)
+        });
+        quote!(
+            assert!(#(#length_requirements)&&*, "input row is too short; index out of bounds");
+            unsafe { #body }
+        )
+    }
+
+    /// For every input row accessed by any of the given constraints, the minimal length the row
+    /// must have in order for all accesses to be in bounds.
+    fn min_row_lengths<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+    ) -> BTreeMap<String, usize> {
+        let mut visited_nodes = HashSet::new();
+        let mut min_row_lengths = BTreeMap::new();
+        for constraint in constraints {
+            Self::record_min_row_lengths(constraint, &mut visited_nodes, &mut min_row_lengths);
+        }
+        min_row_lengths
+    }
+
+    fn record_min_row_lengths<II: InputIndicator>(
+        circuit: &ConstraintCircuit<II>,
+        visited_nodes: &mut HashSet<usize>,
+        min_row_lengths: &mut BTreeMap<String, usize>,
+    ) {
+        if !visited_nodes.insert(circuit.id) {
+            return;
+        }
+
         match &circuit.expression {
+            CircuitExpression::Input(input) => {
+                let row = Self::row_name(*input).to_string();
+                let min_len = min_row_lengths.entry(row).or_default();
+                *min_len = (*min_len).max(input.column() + 1);
+            }
+            CircuitExpression::BinaryOperation(_, lhs, rhs) => {
+                Self::record_min_row_lengths(&lhs.borrow(), visited_nodes, min_row_lengths);
+                Self::record_min_row_lengths(&rhs.borrow(), visited_nodes, min_row_lengths);
+            }
+            _ => (),
+        }
+    }
+
+    /// The identifier of the row the input refers to. Relies on the input being tokenized as
+    /// `row[column]`.
+    fn row_name<II: InputIndicator>(input: II) -> TokenTree {
+        let tokenized_input = input.to_token_stream().into_iter().next();
+        let Some(row @ TokenTree::Ident(_)) = tokenized_input else {
+            panic!("input `{input}` must start with the identifier of its row");
+        };
+        row
+    }
+
+    fn tokenize_input<II: InputIndicator>(&self, input: II) -> TokenStream {
+        if !self.elide_bounds_checks {
+            return quote!(#input);
+        }
+
+        let row = Self::row_name(input);
+        let column = input.column();
+        quote!(*#row.uget(#column))
+    }
+
+    fn binding_name<II: InputIndicator>(&self, circuit: &ConstraintCircuit<II>) -> TokenStream {
+        match &circuit.expression {
+            CircuitExpression::BConstant(bfe) if self.generic_field => {
+                let bfe = Self::tokenize_bfe(*bfe);
+                quote!(FF::from(#bfe))
+            }
+            CircuitExpression::XConstant(xfe) if self.generic_field => {
+                let Some(bfe) = xfe.unlift() else {
+                    panic!("generic field code cannot represent extension field constant {xfe}");
+                };
+                let bfe = Self::tokenize_bfe(bfe);
+                quote!(FF::from(#bfe))
+            }
             CircuitExpression::BConstant(bfe) => Self::tokenize_bfe(*bfe),
             CircuitExpression::XConstant(xfe) => Self::tokenize_xfe(*xfe),
-            CircuitExpression::Input(idx) => quote!(#idx),
+            CircuitExpression::Input(input) => self.tokenize_input(*input),
             CircuitExpression::Challenge(challenge) => quote!(challenges[#challenge]),
             CircuitExpression::BinaryOperation(_, _, _) => {
                 let node_ident = format_ident!("node_{}", circuit.id);
@@ -330,9 +899,16 @@ impl RustBackend {
 
 #[cfg(test)]
 mod tests {
+    use ndarray::Array2;
+    use ndarray::ArrayView2;
+    use triton_vm::table::challenges::Challenges;
+    use triton_vm::table::master_table::NUM_BASE_COLUMNS;
+    use triton_vm::table::master_table::NUM_EXT_COLUMNS;
+    use twenty_first::math::other::random_elements;
     use twenty_first::prelude::*;
 
     use crate::codegen::tests::print_constraints;
+    use crate::substitution::Substitutions;
 
     use super::*;
 
@@ -363,4 +939,331 @@ mod tests {
     fn print_test_constraints() {
         print_constraints::<RustBackend>(&Constraints::test_constraints());
     }
+
+    #[test]
+    fn print_mini_constraints_with_elided_bounds_checks() {
+        let code = RustBackend::constraint_evaluation_code_with_elided_bounds_checks(
+            &Constraints::mini_constraints(),
+        );
+        let syntax_tree = syn::parse2(code).unwrap();
+        println!("{}", prettyplease::unparse(&syntax_tree));
+    }
+
+    #[test]
+    fn print_mini_constraints_generic_over_field() {
+        let code = RustBackend::constraint_evaluation_code_generic_over_field(
+            &Constraints::mini_constraints(),
+        );
+        let syntax_tree = syn::parse2(code).unwrap();
+        println!("{}", prettyplease::unparse(&syntax_tree));
+    }
+
+    #[test]
+    fn generic_mini_constraints_fixture_is_up_to_date() {
+        let code = RustBackend::constraint_evaluation_code_generic_over_field(
+            &Constraints::mini_constraints(),
+        );
+        let generated: syn::File = syn::parse2(code).unwrap();
+        let fixture = include_str!("generic_mini_constraints.rs");
+        let mut fixture: syn::File = syn::parse_str(fixture).unwrap();
+        fixture.attrs.clear();
+
+        assert_eq!(
+            prettyplease::unparse(&generated),
+            prettyplease::unparse(&fixture)
+        );
+    }
+
+    #[test]
+    fn generic_code_evaluated_over_extension_field_agrees_with_circuit_evaluation() {
+        use crate::codegen::generic_mini_constraints as generic;
+
+        let constraints = Constraints::mini_constraints();
+        let base_rows = Array2::from_shape_vec((2, 1), random_elements(2)).unwrap();
+        let ext_rows = Array2::from_shape_vec((2, 2), random_elements(4)).unwrap();
+        let challenges: Vec<XFieldElement> = random_elements(Challenges::COUNT);
+
+        let lifted_base_rows = base_rows.mapv(XFieldElement::from);
+        let base_row = lifted_base_rows.row(0);
+        let ext_row = ext_rows.row(0);
+        let next_base_row = lifted_base_rows.row(1);
+        let next_ext_row = ext_rows.row(1);
+
+        let (init_base, init_ext) = evaluate_in_order_of_generated_code(
+            &constraints.init(),
+            base_rows.view(),
+            ext_rows.view(),
+            &challenges,
+        );
+        let expected = init_base.into_iter().chain(init_ext).collect_vec();
+        let actual = generic::evaluate_initial_constraints(base_row, ext_row, &challenges);
+        assert_eq!(expected, actual);
+
+        let cons = generic::evaluate_consistency_constraints(base_row, ext_row, &challenges);
+        let tran = generic::evaluate_transition_constraints(
+            base_row,
+            ext_row,
+            next_base_row,
+            next_ext_row,
+            &challenges,
+        );
+        let term = generic::evaluate_terminal_constraints(base_row, ext_row, &challenges);
+        assert!(cons.is_empty());
+        assert!(tran.is_empty());
+        assert!(term.is_empty());
+    }
+
+    #[test]
+    fn unchecked_code_implements_evaluable_for_dedicated_type_without_bounds_checks() {
+        let code =
+            RustBackend::unchecked_constraint_evaluation_code(&Constraints::mini_constraints())
+                .to_string();
+        assert!(code.contains("pub struct UncheckedConstraints"));
+        assert_eq!(2, code.matches("for UncheckedConstraints").count());
+        assert!(!code.contains("MasterExtTable {"));
+        assert!(!code.contains("Quotientable"));
+        assert!(code.contains("ext_row . uget (1usize)"));
+        assert!(!code.contains("ext_row ["));
+    }
+
+    #[test]
+    fn print_test_constraints_without_bounds_checks() {
+        let (constraints, _, _) = lowered_test_constraints_and_random_rows();
+        let code = RustBackend::unchecked_constraint_evaluation_code(&constraints);
+        let syntax_tree = syn::parse2(code).unwrap();
+        println!("{}", prettyplease::unparse(&syntax_tree));
+    }
+
+    #[test]
+    fn unchecked_test_constraints_fixture_is_up_to_date() {
+        let (constraints, _, _) = lowered_test_constraints_and_random_rows();
+        let code = RustBackend::unchecked_constraint_evaluation_code(&constraints);
+        let generated: syn::File = syn::parse2(code).unwrap();
+        let fixture = include_str!("unchecked_test_constraints.rs");
+        let mut fixture: syn::File = syn::parse_str(fixture).unwrap();
+        fixture.attrs.clear();
+
+        assert_eq!(
+            prettyplease::unparse(&generated),
+            prettyplease::unparse(&fixture)
+        );
+    }
+
+    #[test]
+    fn code_without_bounds_checks_agrees_with_circuit_evaluation() {
+        use triton_vm::table::extension_table::Evaluable;
+
+        use crate::codegen::unchecked_test_constraints::UncheckedConstraints;
+
+        let (constraints, base_rows, ext_rows) = lowered_test_constraints_and_random_rows();
+        let challenges = Challenges {
+            challenges: random_elements(Challenges::COUNT).try_into().unwrap(),
+        };
+        let lifted_base_rows = base_rows.mapv(XFieldElement::from);
+
+        macro_rules! evaluate {
+            ($constraints:expr) => {{
+                let (base_values, ext_values) = evaluate_in_order_of_generated_code(
+                    &$constraints,
+                    base_rows.view(),
+                    ext_rows.view(),
+                    &challenges.challenges,
+                );
+                [base_values, ext_values].concat()
+            }};
+        }
+
+        macro_rules! assert_agreement {
+            ($field:ty, $base_rows:expr) => {{
+                let base_row = $base_rows.row(0);
+                let ext_row = ext_rows.row(0);
+                let next_base_row = $base_rows.row(1);
+                let next_ext_row = ext_rows.row(1);
+
+                let init =
+                    <UncheckedConstraints as Evaluable<$field>>::evaluate_initial_constraints(
+                        base_row,
+                        ext_row,
+                        &challenges,
+                    );
+                let cons =
+                    <UncheckedConstraints as Evaluable<$field>>::evaluate_consistency_constraints(
+                        base_row,
+                        ext_row,
+                        &challenges,
+                    );
+                let tran =
+                    <UncheckedConstraints as Evaluable<$field>>::evaluate_transition_constraints(
+                        base_row,
+                        ext_row,
+                        next_base_row,
+                        next_ext_row,
+                        &challenges,
+                    );
+                let term =
+                    <UncheckedConstraints as Evaluable<$field>>::evaluate_terminal_constraints(
+                        base_row,
+                        ext_row,
+                        &challenges,
+                    );
+
+                assert_eq!(evaluate!(constraints.init()), init);
+                assert_eq!(evaluate!(constraints.cons()), cons);
+                assert_eq!(evaluate!(constraints.tran()), tran);
+                assert_eq!(evaluate!(constraints.term()), term);
+            }};
+        }
+
+        assert_agreement!(BFieldElement, base_rows);
+        assert_agreement!(XFieldElement, lifted_base_rows);
+    }
+
+    #[test]
+    fn code_with_elided_bounds_checks_asserts_minimal_row_lengths() {
+        let code = RustBackend::constraint_evaluation_code_with_elided_bounds_checks(
+            &Constraints::mini_constraints(),
+        )
+        .to_string();
+
+        let assertion = "assert ! (base_row . len () >= 1usize && ext_row . len () >= 2usize";
+        assert_eq!(2, code.matches(assertion).count());
+        assert!(code.contains("base_row . uget (0usize)"));
+        assert!(code.contains("ext_row . uget (1usize)"));
+        assert!(!code.contains("base_row ["));
+        assert!(!code.contains("ext_row ["));
+    }
+
+    #[test]
+    fn code_with_bounds_checks_contains_no_unsafe_code() {
+        let code = RustBackend::constraint_evaluation_code(&Constraints::test_constraints());
+        assert!(!code.to_string().contains("unsafe"));
+    }
+
+    #[test]
+    fn print_test_constraints_split_by_table() {
+        let mut constraints = Constraints::test_constraints();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let code = RustBackend::constraint_evaluation_code_split_by_table(&constraints, false);
+        for (table, code) in code.tables {
+            let syntax_tree = syn::parse2(code).unwrap();
+            println!("// {table}\n{}", prettyplease::unparse(&syntax_tree));
+        }
+        let syntax_tree = syn::parse2(code.dispatcher).unwrap();
+        println!("{}", prettyplease::unparse(&syntax_tree));
+    }
+
+    #[test]
+    fn dispatcher_declares_one_module_per_table() {
+        let mut constraints = Constraints::test_constraints();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let code = RustBackend::constraint_evaluation_code_split_by_table(&constraints, true);
+        let tables = code.tables.iter().map(|&(table, _)| table).collect_vec();
+        let expected_tables = [
+            "first_test_table",
+            "second_test_table",
+            "degree_lowering_table",
+        ];
+        assert_eq!(expected_tables.to_vec(), tables);
+
+        let dispatcher = code.dispatcher.to_string();
+        for table in tables {
+            assert!(dispatcher.contains(&format!("mod {table} ;")));
+        }
+        for (_, code) in code.tables {
+            assert!(code.to_string().contains("unsafe"));
+        }
+    }
+
+    /// The values of the given constraints in the order in which the generated code produces
+    /// them: the values of all base constraints, followed by those of all extension constraints.
+    fn evaluate_in_order_of_generated_code<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+        base_rows: ArrayView2<BFieldElement>,
+        ext_rows: ArrayView2<XFieldElement>,
+        challenges: &[XFieldElement],
+    ) -> (Vec<XFieldElement>, Vec<XFieldElement>) {
+        let (base_constraints, ext_constraints): (Vec<_>, Vec<_>) = constraints
+            .iter()
+            .partition(|constraint| constraint.evaluates_to_base_element());
+        let evaluate = |constraints: Vec<&ConstraintCircuit<II>>| {
+            constraints
+                .into_iter()
+                .map(|constraint| constraint.evaluate(base_rows, ext_rows, challenges))
+                .collect_vec()
+        };
+
+        (evaluate(base_constraints), evaluate(ext_constraints))
+    }
+
+    /// The [test constraints](Constraints::test_constraints), lowered to the AIR's target degree,
+    /// as well as two random consecutive base and extension rows wide enough for all of them.
+    fn lowered_test_constraints_and_random_rows(
+    ) -> (Constraints, Array2<BFieldElement>, Array2<XFieldElement>) {
+        let mut constraints = Constraints::test_constraints();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+
+        // The test constraints' degree-lowering columns come after the master tables' columns.
+        let num_new_columns = |substitutions: &Substitutions| {
+            let Substitutions {
+                init,
+                cons,
+                tran,
+                term,
+            } = substitutions;
+            init.len() + cons.len() + tran.len() + term.len()
+        };
+        let num_base_columns = NUM_BASE_COLUMNS + num_new_columns(&substitutions.base);
+        let num_ext_columns = NUM_EXT_COLUMNS + num_new_columns(&substitutions.ext);
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let base_rows = random_elements(2 * num_base_columns);
+        let ext_rows = random_elements(2 * num_ext_columns);
+        let base_rows = Array2::from_shape_vec([2, num_base_columns], base_rows).unwrap();
+        let ext_rows = Array2::from_shape_vec([2, num_ext_columns], ext_rows).unwrap();
+
+        (constraints, base_rows, ext_rows)
+    }
+
+    #[test]
+    fn per_table_modules_collectively_evaluate_like_monolithic_code() {
+        let (constraints, base_rows, ext_rows) = lowered_test_constraints_and_random_rows();
+        let challenges = random_elements(Challenges::COUNT);
+
+        macro_rules! assert_equivalence {
+            ($constraint_type:ident) => {{
+                let (base_values, ext_values) = evaluate_in_order_of_generated_code(
+                    &constraints.$constraint_type(),
+                    base_rows.view(),
+                    ext_rows.view(),
+                    &challenges,
+                );
+                let monolithic_values = [base_values, ext_values].concat();
+
+                let (base_values, ext_values): (Vec<_>, Vec<_>) = constraints
+                    .split_by_table()
+                    .into_iter()
+                    .map(|(_, table)| {
+                        evaluate_in_order_of_generated_code(
+                            &table.$constraint_type(),
+                            base_rows.view(),
+                            ext_rows.view(),
+                            &challenges,
+                        )
+                    })
+                    .unzip();
+                let dispatched_values = [base_values.concat(), ext_values.concat()].concat();
+
+                assert_eq!(monolithic_values, dispatched_values);
+            }};
+        }
+
+        assert_equivalence!(init);
+        assert_equivalence!(cons);
+        assert_equivalence!(tran);
+        assert_equivalence!(term);
+    }
 }
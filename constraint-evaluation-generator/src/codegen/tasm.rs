@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 
+use itertools::izip;
 use itertools::Itertools;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::quote;
 use quote::ToTokens;
 use twenty_first::prelude::bfe;
@@ -10,14 +13,18 @@ use twenty_first::prelude::BFieldElement;
 use twenty_first::prelude::XFieldElement;
 
 use triton_vm::air::memory_layout;
+use triton_vm::air::memory_layout::CoefficientOrder;
 use triton_vm::instruction::Instruction;
 use triton_vm::op_stack::NumberOfWords;
+use triton_vm::op_stack::OpStackElement;
+use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
 use triton_vm::table::constraint_circuit::BinOp;
 use triton_vm::table::constraint_circuit::CircuitExpression;
 use triton_vm::table::constraint_circuit::ConstraintCircuit;
 use triton_vm::table::constraint_circuit::InputIndicator;
 
 use crate::codegen::Codegen;
+use crate::codegen::NodeLoad;
 use crate::codegen::TasmBackend;
 use crate::constraints::Constraints;
 
@@ -33,6 +40,13 @@ const OUT_ARRAY_OFFSET: usize = {
     out_array_offset_in_words / EXTENSION_DEGREE
 };
 
+// The output array of the batched constraint evaluation must end before the output array of
+// the evaluation of a single row begins.
+const _: () = assert!(
+    memory_layout::BATCHED_OUT_ARRAY_OFFSET + memory_layout::MAX_BATCHED_OUT_ARRAY_LEN
+        <= OUT_ARRAY_OFFSET * EXTENSION_DEGREE
+);
+
 /// Convenience macro to get raw opcodes of any [`Instruction`] variant, including its argument if
 /// applicable.
 ///
@@ -76,40 +90,179 @@ impl Codegen for TasmBackend {
     ///
     /// [tasm]: triton_vm::prelude::triton_asm
     fn constraint_evaluation_code(constraints: &Constraints) -> TokenStream {
+        Self::constraint_evaluation_code_with_coefficient_order(
+            constraints,
+            CoefficientOrder::default(),
+        )
+    }
+}
+
+impl TasmBackend {
+    /// Like [`constraint_evaluation_code`](Codegen::constraint_evaluation_code), but the
+    /// emitted Triton assembly writes the coefficients of every evaluated constraint to the
+    /// output array in the given order.
+    pub(crate) fn constraint_evaluation_code_with_coefficient_order(
+        constraints: &Constraints,
+        coefficient_order: CoefficientOrder,
+    ) -> TokenStream {
         let doc_comment = Self::doc_comment_static_version();
 
-        let mut backend = Self::statically_known_input_locations();
+        let mut backend = Self::statically_known_input_locations(coefficient_order);
         let init_constraints = backend.tokenize_circuits(&constraints.init());
         let cons_constraints = backend.tokenize_circuits(&constraints.cons());
         let tran_constraints = backend.tokenize_circuits(&constraints.tran());
         let term_constraints = backend.tokenize_circuits(&constraints.term());
-        let prepare_return_values = Self::prepare_return_values();
-        let num_instructions = init_constraints.len()
-            + cons_constraints.len()
-            + tran_constraints.len()
-            + term_constraints.len()
-            + prepare_return_values.len();
-        let num_instructions = u64::try_from(num_instructions).unwrap();
-
-        let convert_and_decode_assembled_instructions = quote!(
-            let raw_instructions = raw_instructions
-                .into_iter()
-                .map(BFieldElement::new)
-                .collect::<Vec<_>>();
-            let program = Program::decode(&raw_instructions).unwrap();
-
-            let irrelevant_label = |_: &_| String::new();
-            program
-                .into_iter()
-                .map(|instruction| instruction.map_call_address(irrelevant_label))
-                .map(LabelledInstruction::Instruction)
-                .collect()
+        backend.assert_output_array_matches_rust_backend(constraints);
+        let cost_summary = Self::doc_comment_cost_summary(
+            constraints,
+            [
+                Self::num_instructions(&init_constraints),
+                Self::num_instructions(&cons_constraints),
+                Self::num_instructions(&tran_constraints),
+                Self::num_instructions(&term_constraints),
+            ],
         );
+        let constraints_by_category = [
+            init_constraints,
+            cons_constraints,
+            tran_constraints,
+            term_constraints,
+        ];
+        let num_constraints = [
+            constraints.num_init(),
+            constraints.num_cons(),
+            constraints.num_tran(),
+            constraints.num_term(),
+        ];
+        let category_names = ["init", "cons", "tran", "term"];
+        let constraint_types = ["Initial", "Consistency", "Transition", "Terminal"];
+        let out_array_offset = u64::try_from(OUT_ARRAY_OFFSET * EXTENSION_DEGREE).unwrap();
+        let extension_degree = u64::try_from(EXTENSION_DEGREE).unwrap();
+
+        let mut single_category_functions = vec![];
+        let mut call_single_category_functions = vec![];
+        for (name, constraint_type, constraints, num_constraints) in izip!(
+            category_names,
+            constraint_types,
+            constraints_by_category,
+            num_constraints
+        ) {
+            let function_name = format_ident!("static_{name}_constraints_tasm");
+            let doc_comment = Self::doc_comment_single_category(constraint_type);
+            let constraint_type = format_ident!("{constraint_type}");
+            single_category_functions.push(quote!(
+                #[doc = #doc_comment]
+                #[allow(unused_variables)]
+                pub fn #function_name(
+                    mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+                    out_array_offset: usize,
+                ) -> Vec<LabelledInstruction> {
+                    let free_mem_page_ptr = mem_layout.free_mem_page_ptr.value();
+                    let curr_base_row_ptr = mem_layout.curr_base_row_ptr.value();
+                    let curr_ext_row_ptr = mem_layout.curr_ext_row_ptr.value();
+                    let next_base_row_ptr = mem_layout.next_base_row_ptr.value();
+                    let next_ext_row_ptr = mem_layout.next_ext_row_ptr.value();
+                    let challenges_ptr = mem_layout.challenges_ptr.value();
+                    let out_array_ptr = free_mem_page_ptr
+                        + #out_array_offset
+                        + #extension_degree * out_array_offset as u64;
+
+                    raw_instructions_to_labelled_instructions(vec![#(#constraints,)*])
+                }
+            ));
+            if num_constraints == 0 {
+                continue;
+            }
+            call_single_category_functions.push(quote!(
+                if categories.contains(ConstraintType::#constraint_type) {
+                    instructions.extend(#function_name(mem_layout, out_array_len));
+                    out_array_len += #num_constraints;
+                }
+            ));
+        }
+
+        let prepare_return_values = Self::prepare_return_values();
+        let doc_comment_subset = Self::doc_comment_subset("static_air_constraint_evaluation_tasm");
 
         let statically_known_input_locations = quote!(
             #[doc = #doc_comment]
             pub fn static_air_constraint_evaluation_tasm(
                 mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+            ) -> Vec<LabelledInstruction> {
+                static_air_constraint_evaluation_tasm_for(mem_layout, ConstraintCategorySet::ALL)
+            }
+
+            #[doc = #doc_comment_subset]
+            pub fn static_air_constraint_evaluation_tasm_for(
+                mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+                categories: ConstraintCategorySet,
+            ) -> Vec<LabelledInstruction> {
+                let mut out_array_len = 0;
+                let mut instructions = vec![];
+                #(#call_single_category_functions)*
+                debug_assert_eq!(categories.num_constraints(), out_array_len);
+
+                let free_mem_page_ptr = mem_layout.free_mem_page_ptr.value();
+                let return_values = vec![#(#prepare_return_values,)*];
+                instructions.extend(raw_instructions_to_labelled_instructions(return_values));
+                instructions
+            }
+
+            #(#single_category_functions)*
+        );
+
+        let doc_comment = Self::doc_comment_on_stack_version();
+
+        let mut backend =
+            Self::statically_known_input_locations_with_results_on_stack(coefficient_order);
+        let init_constraints = backend.tokenize_circuits(&constraints.init());
+        let cons_constraints = backend.tokenize_circuits(&constraints.cons());
+        let tran_constraints = backend.tokenize_circuits(&constraints.tran());
+        let term_constraints = backend.tokenize_circuits(&constraints.term());
+        backend.assert_output_array_matches_rust_backend(constraints);
+        let select_categories = izip!(
+            constraint_types,
+            [
+                init_constraints,
+                cons_constraints,
+                tran_constraints,
+                term_constraints,
+            ],
+            [
+                constraints.num_init(),
+                constraints.num_cons(),
+                constraints.num_tran(),
+                constraints.num_term(),
+            ]
+        )
+        .filter(|&(_, _, num_constraints)| num_constraints > 0)
+        .map(|(constraint_type, constraints, _)| {
+            let constraint_type = format_ident!("{constraint_type}");
+            quote!(
+                if categories.contains(ConstraintType::#constraint_type) {
+                    raw_instructions.extend([#(#constraints,)*]);
+                }
+            )
+        });
+        let doc_comment_subset =
+            Self::doc_comment_subset_on_stack("static_air_constraint_evaluation_tasm_on_stack");
+
+        let results_on_stack = quote!(
+            #[doc = #doc_comment]
+            pub fn static_air_constraint_evaluation_tasm_on_stack(
+                mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+            ) -> Vec<LabelledInstruction> {
+                static_air_constraint_evaluation_tasm_on_stack_for(
+                    mem_layout,
+                    ConstraintCategorySet::ALL,
+                )
+            }
+
+            #[doc = #doc_comment_subset]
+            #[allow(unused_variables)]
+            pub fn static_air_constraint_evaluation_tasm_on_stack_for(
+                mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+                categories: ConstraintCategorySet,
             ) -> Vec<LabelledInstruction> {
                 let free_mem_page_ptr = mem_layout.free_mem_page_ptr.value();
                 let curr_base_row_ptr = mem_layout.curr_base_row_ptr.value();
@@ -118,39 +271,45 @@ impl Codegen for TasmBackend {
                 let next_ext_row_ptr = mem_layout.next_ext_row_ptr.value();
                 let challenges_ptr = mem_layout.challenges_ptr.value();
 
-                let raw_instructions = vec![
-                    #num_instructions,
-                    #(#init_constraints,)*
-                    #(#cons_constraints,)*
-                    #(#tran_constraints,)*
-                    #(#term_constraints,)*
-                    #(#prepare_return_values,)*
-                ];
-                #convert_and_decode_assembled_instructions
+                let mut raw_instructions = vec![];
+                #(#select_categories)*
+                raw_instructions_to_labelled_instructions(raw_instructions)
             }
         );
 
         let doc_comment = Self::doc_comment_dynamic_version();
 
-        let mut backend = Self::dynamically_known_input_locations();
+        let mut backend = Self::dynamically_known_input_locations(coefficient_order);
         let move_row_pointers = backend.write_row_pointers_to_ram();
         let init_constraints = backend.tokenize_circuits(&constraints.init());
         let cons_constraints = backend.tokenize_circuits(&constraints.cons());
         let tran_constraints = backend.tokenize_circuits(&constraints.tran());
         let term_constraints = backend.tokenize_circuits(&constraints.term());
-        let prepare_return_values = Self::prepare_return_values();
-        let num_instructions = move_row_pointers.len()
-            + init_constraints.len()
-            + cons_constraints.len()
-            + tran_constraints.len()
-            + term_constraints.len()
-            + prepare_return_values.len();
-        let num_instructions = u64::try_from(num_instructions).unwrap();
+        backend.assert_output_array_matches_rust_backend(constraints);
+        let assemble_instructions = Self::assemble_raw_instructions(
+            constraints,
+            &move_row_pointers,
+            [
+                init_constraints,
+                cons_constraints,
+                tran_constraints,
+                term_constraints,
+            ],
+        );
+        let doc_comment_subset = Self::doc_comment_subset("dynamic_air_constraint_evaluation_tasm");
 
         let dynamically_known_input_locations = quote!(
             #[doc = #doc_comment]
             pub fn dynamic_air_constraint_evaluation_tasm(
                 mem_layout: DynamicTasmConstraintEvaluationMemoryLayout,
+            ) -> Vec<LabelledInstruction> {
+                dynamic_air_constraint_evaluation_tasm_for(mem_layout, ConstraintCategorySet::ALL)
+            }
+
+            #[doc = #doc_comment_subset]
+            pub fn dynamic_air_constraint_evaluation_tasm_for(
+                mem_layout: DynamicTasmConstraintEvaluationMemoryLayout,
+                categories: ConstraintCategorySet,
             ) -> Vec<LabelledInstruction> {
                 let num_pointer_pointers = 4;
                 let free_mem_page_ptr = mem_layout.free_mem_page_ptr.value() + num_pointer_pointers;
@@ -160,58 +319,323 @@ impl Codegen for TasmBackend {
                 let next_ext_row_ptr = mem_layout.free_mem_page_ptr.value() + 3;
                 let challenges_ptr = mem_layout.challenges_ptr.value();
 
-                let raw_instructions = vec![
-                    #num_instructions,
-                    #(#move_row_pointers,)*
-                    #(#init_constraints,)*
-                    #(#cons_constraints,)*
-                    #(#tran_constraints,)*
-                    #(#term_constraints,)*
-                    #(#prepare_return_values,)*
-                ];
-                #convert_and_decode_assembled_instructions
+                #assemble_instructions
+            }
+        );
+
+        let doc_comment = Self::doc_comment_batched_version();
+        let num_constraints = constraints.total();
+        let batched_rows = quote!(
+            #[doc = #doc_comment]
+            pub fn static_air_constraint_evaluation_tasm_batched(
+                mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+                num_rows: usize,
+            ) -> Vec<LabelledInstruction> {
+                let dynamic_mem_layout = DynamicTasmConstraintEvaluationMemoryLayout {
+                    free_mem_page_ptr: mem_layout.free_mem_page_ptr,
+                    challenges_ptr: mem_layout.challenges_ptr,
+                };
+                let single_row_evaluation =
+                    dynamic_air_constraint_evaluation_tasm(dynamic_mem_layout);
+                batch_air_constraint_evaluation_tasm(
+                    mem_layout,
+                    num_rows,
+                    #num_constraints,
+                    single_row_evaluation,
+                )
             }
         );
 
         let uses = Self::uses();
+        let raw_instructions_to_labelled_instructions =
+            Self::raw_instructions_to_labelled_instructions();
         quote!(
+            #![doc = #cost_summary]
             #uses
             #statically_known_input_locations
+            #results_on_stack
             #dynamically_known_input_locations
+            #batched_rows
+            #raw_instructions_to_labelled_instructions
         )
     }
-}
 
-impl TasmBackend {
-    fn statically_known_input_locations() -> Self {
+    pub(super) fn statically_known_input_locations(coefficient_order: CoefficientOrder) -> Self {
         Self {
             scope: HashSet::new(),
+            duplicated_on_stack: HashSet::new(),
+            kept_on_stack: HashMap::new(),
+            keep_shared_nodes_on_stack: true,
+            stored_nodes: vec![],
+            node_loads: vec![],
+            add_base_field_constants_with_addi: true,
             elements_written: 0,
             input_location_is_static: true,
+            coefficient_order,
+            results_on_stack: false,
+        }
+    }
+
+    fn statically_known_input_locations_with_results_on_stack(
+        coefficient_order: CoefficientOrder,
+    ) -> Self {
+        Self {
+            results_on_stack: true,
+            ..Self::statically_known_input_locations(coefficient_order)
         }
     }
 
-    fn dynamically_known_input_locations() -> Self {
+    fn dynamically_known_input_locations(coefficient_order: CoefficientOrder) -> Self {
         Self {
             input_location_is_static: false,
-            ..Self::statically_known_input_locations()
+            ..Self::statically_known_input_locations(coefficient_order)
         }
     }
 
+    /// Code that assembles the raw instructions evaluating the constraints of the selected
+    /// `categories`. The evaluated constraints of each selected category are written to the
+    /// output array right after those of the previously selected category. The instructions
+    /// evaluating the constraints of one category, given in the order initial, consistency,
+    /// transition, terminal, must write to the output array relative to `out_array_ptr`.
+    fn assemble_raw_instructions(
+        constraints: &Constraints,
+        preamble: &[TokenStream],
+        constraints_by_category: [Vec<TokenStream>; 4],
+    ) -> TokenStream {
+        let num_constraints = [
+            constraints.num_init(),
+            constraints.num_cons(),
+            constraints.num_tran(),
+            constraints.num_term(),
+        ];
+        let constraint_types = [
+            quote!(ConstraintType::Initial),
+            quote!(ConstraintType::Consistency),
+            quote!(ConstraintType::Transition),
+            quote!(ConstraintType::Terminal),
+        ];
+        let select_categories = izip!(constraint_types, constraints_by_category, num_constraints)
+            .filter(|&(_, _, num_constraints)| num_constraints > 0)
+            .map(|(constraint_type, constraints, num_constraints)| {
+                let num_words = u64::try_from(num_constraints * EXTENSION_DEGREE).unwrap();
+                quote!(
+                    if categories.contains(#constraint_type) {
+                        let out_array_ptr = out_array_start + out_array_len;
+                        raw_instructions.extend([#(#constraints,)*]);
+                        out_array_len += #num_words;
+                    }
+                )
+            });
+
+        let out_array_offset = u64::try_from(OUT_ARRAY_OFFSET * EXTENSION_DEGREE).unwrap();
+        let extension_degree = u64::try_from(EXTENSION_DEGREE).unwrap();
+        let prepare_return_values = Self::prepare_return_values();
+        quote!(
+            let out_array_start = free_mem_page_ptr + #out_array_offset;
+            let mut out_array_len = 0;
+            let mut raw_instructions = vec![#(#preamble,)*];
+            #(#select_categories)*
+            debug_assert_eq!(
+                #extension_degree * categories.num_constraints() as u64,
+                out_array_len,
+            );
+            raw_instructions.extend([#(#prepare_return_values,)*]);
+            raw_instructions_to_labelled_instructions(raw_instructions)
+        )
+    }
+
+    /// Code of a function turning the encoding of a sequence of instructions, _i.e._, the
+    /// instructions' opcodes and arguments, into the corresponding [`LabelledInstruction`]s.
+    ///
+    /// [`LabelledInstruction`]: triton_vm::instruction::LabelledInstruction
+    fn raw_instructions_to_labelled_instructions() -> TokenStream {
+        quote!(
+            fn raw_instructions_to_labelled_instructions(
+                raw_instructions: Vec<u64>,
+            ) -> Vec<LabelledInstruction> {
+                let raw_instructions =
+                    [vec![raw_instructions.len() as u64], raw_instructions].concat();
+                let raw_instructions = raw_instructions
+                    .into_iter()
+                    .map(BFieldElement::new)
+                    .collect::<Vec<_>>();
+                let program = Program::decode(&raw_instructions).unwrap();
+
+                let irrelevant_label = |_: &_| String::new();
+                program
+                    .into_iter()
+                    .map(|instruction| instruction.map_call_address(irrelevant_label))
+                    .map(LabelledInstruction::Instruction)
+                    .collect()
+            }
+        )
+    }
+
+    /// Assert that the emitted Triton assembly writes exactly one element to the output array
+    /// per constraint, _i.e._, as many elements as the [`RustBackend`][rust] evaluates, and
+    /// that the output array, to which [`prepare_return_values`][ret] points, fits into the
+    /// free memory page.
+    ///
+    /// Must be called after tokenizing the constraints of all four types.
+    ///
+    /// [rust]: crate::codegen::RustBackend
+    /// [ret]: Self::prepare_return_values
+    fn assert_output_array_matches_rust_backend(&self, constraints: &Constraints) {
+        let num_constraints = constraints.total();
+        assert_eq!(
+            num_constraints, self.elements_written,
+            "constraint evaluation in TASM writes {} elements for {num_constraints} constraints",
+            self.elements_written,
+        );
+
+        let out_array_end = (OUT_ARRAY_OFFSET + self.elements_written) * EXTENSION_DEGREE;
+        assert!(
+            out_array_end <= memory_layout::MEM_PAGE_SIZE,
+            "output array of constraint evaluation in TASM exceeds the free memory page",
+        );
+    }
+
+    /// The number of words, counted from the [memory layout][layout]'s `free_mem_page_ptr`,
+    /// that the emitted Triton assembly writes to when evaluating the given constraints. This
+    /// is the larger of the end of the region holding [shared nodes][shared], and the end of
+    /// the output array.
+    ///
+    /// [layout]: memory_layout::IntegralMemoryLayout
+    /// [shared]: Self::required_shared_nodes_mem_words
+    pub(crate) fn required_free_mem_words(constraints: &Constraints) -> usize {
+        let shared_nodes_end = Self::required_shared_nodes_mem_words(constraints);
+        let out_array_end = (OUT_ARRAY_OFFSET + constraints.total()) * EXTENSION_DEGREE;
+        shared_nodes_end.max(out_array_end)
+    }
+
+    /// The number of words, counted from the [memory layout][layout]'s `free_mem_page_ptr`,
+    /// that the emitted Triton assembly uses for nodes shared between constraints when
+    /// evaluating the given constraints. Determined by the largest ID of any stored node.
+    ///
+    /// The [batched evaluation][batched] keeps its results in the same memory page, starting
+    /// at [`BATCHED_OUT_ARRAY_OFFSET`][offset]. The shared nodes must end before that offset,
+    /// or evaluating one row overwrites the results of the previous rows.
+    ///
+    /// [layout]: memory_layout::IntegralMemoryLayout
+    /// [batched]: triton_vm::air::batch_air_constraint_evaluation_tasm
+    /// [offset]: memory_layout::BATCHED_OUT_ARRAY_OFFSET
+    pub(crate) fn required_shared_nodes_mem_words(constraints: &Constraints) -> usize {
+        let mut backend = Self::statically_known_input_locations(CoefficientOrder::default());
+        let max_stored_node_id = [
+            backend.max_stored_node_id(&constraints.init()),
+            backend.max_stored_node_id(&constraints.cons()),
+            backend.max_stored_node_id(&constraints.tran()),
+            backend.max_stored_node_id(&constraints.term()),
+        ]
+        .into_iter()
+        .flatten()
+        .max();
+
+        max_stored_node_id.map_or(0, |id| (id + 1) * EXTENSION_DEGREE)
+    }
+
+    fn max_stored_node_id<II: InputIndicator>(
+        &mut self,
+        constraints: &[ConstraintCircuit<II>],
+    ) -> Option<usize> {
+        self.tokenize_circuits(constraints);
+        self.scope.iter().max().copied()
+    }
+
     fn uses() -> TokenStream {
         quote!(
             use twenty_first::prelude::BFieldCodec;
             use twenty_first::prelude::BFieldElement;
             use crate::instruction::LabelledInstruction;
             use crate::Program;
+            use crate::air::batch_air_constraint_evaluation_tasm;
             use crate::air::memory_layout::StaticTasmConstraintEvaluationMemoryLayout;
             use crate::air::memory_layout::DynamicTasmConstraintEvaluationMemoryLayout;
+            use crate::table::ConstraintCategorySet;
+            use crate::table::ConstraintType;
             // for rustdoc – https://github.com/rust-lang/rust/issues/74563
             #[allow(unused_imports)]
             use crate::table::extension_table::Quotientable;
         )
     }
 
+    /// A summary of how expensive the evaluation of each constraint type is: the maximal degree
+    /// of any of its constraints as well as the number of emitted instructions when the input
+    /// locations are statically known.
+    fn doc_comment_cost_summary(constraints: &Constraints, num_instructions: [usize; 4]) -> String {
+        let max_degrees = [
+            Self::max_degree(&constraints.init()),
+            Self::max_degree(&constraints.cons()),
+            Self::max_degree(&constraints.tran()),
+            Self::max_degree(&constraints.term()),
+        ];
+        let constraint_types = ["initial", "consistency", "transition", "terminal"];
+
+        let rows = izip!(constraint_types, max_degrees, num_instructions)
+            .map(|(constraint_type, max_degree, num_instructions)| {
+                let max_degree = max_degree.map_or_else(|| "-".to_owned(), |d| d.to_string());
+                format!(" | {constraint_type} | {max_degree} | {num_instructions} |")
+            })
+            .join("\n");
+
+        format!(
+            " Cost of evaluating the AIR constraints in Triton assembly.\n\n \
+             | constraint type | max degree | instructions |\n \
+             |:----------------|-----------:|-------------:|\n\
+             {rows}\n"
+        )
+    }
+
+    /// The number of instructions in the given raw instructions, _i.e._, in a sequence of
+    /// opcodes, each followed by its argument, if any. Not to be confused with the number of
+    /// words.
+    fn num_instructions(raw_instructions: &[TokenStream]) -> usize {
+        let mut num_instructions = 0;
+        let mut word_index = 0;
+        while let Some(opcode) = raw_instructions.get(word_index) {
+            let opcode = syn::parse2::<syn::LitInt>(opcode.clone()).unwrap();
+            let opcode = opcode.base10_parse::<u32>().unwrap();
+            let instruction = Instruction::try_from(opcode).unwrap();
+            word_index += instruction.size();
+            num_instructions += 1;
+        }
+        num_instructions
+    }
+
+    fn max_degree<II: InputIndicator>(constraints: &[ConstraintCircuit<II>]) -> Option<isize> {
+        constraints.iter().map(|circuit| circuit.degree()).max()
+    }
+
+    fn doc_comment_subset(full_version: &str) -> String {
+        format!(
+            "
+         Like [`{full_version}`], except that only the constraints of the given categories are
+         evaluated. Consequently, `*evaluated_constraints` points to an array of length
+         [`categories.num_constraints()`][num], containing the evaluations of the selected
+         categories' constraints in the order initial, consistency, transition, terminal.
+
+         [num]: ConstraintCategorySet::num_constraints
+        "
+        )
+    }
+
+    fn doc_comment_single_category(constraint_type: &str) -> String {
+        format!(
+            "
+         Like [`static_air_constraint_evaluation_tasm`], except that only the constraints of
+         type [`{constraint_type}`](ConstraintType::{constraint_type}) are evaluated, and that the emitted code leaves the stack
+         unchanged.
+
+         The evaluations are written to the output array, starting at the element with index
+         `out_array_offset`. In particular, evaluating the constraints of every type with the
+         offset that type's constraints have in the output array of
+         [`static_air_constraint_evaluation_tasm`] results in the same output array. The types can
+         be evaluated in any order, with arbitrary code in between that leaves the memory
+         region pointed to by `*free_memory_page` intact.
+        "
+        )
+    }
+
     fn doc_comment_static_version() -> &'static str {
         "
          The emitted Triton assembly has the following signature:
@@ -254,6 +678,58 @@ impl TasmBackend {
         "
     }
 
+    fn doc_comment_on_stack_version() -> &'static str {
+        "
+         Like [`static_air_constraint_evaluation_tasm`], except that the evaluated constraints
+         are left on the op stack instead of being written to an output array in memory. This
+         avoids the round-trip through RAM if the caller consumes the evaluations directly.
+
+         The emitted Triton assembly has the following signature:
+
+         # Signature
+
+         ```text
+         BEFORE: _
+         AFTER:  _ [evaluated_constraints: [XFieldElement; NUM_CONSTRAINTS]]
+         ```
+
+         The evaluations are pushed in the order of the output array of
+         [`static_air_constraint_evaluation_tasm`], _i.e._, the evaluation of the last
+         constraint ends up on top of the stack. The coefficients of each evaluation are laid out
+         as if the element had been read from the output array using `read_mem 3`: for the
+         default, little-endian [coefficient order][order], the constant coefficient is on top.
+
+         # Requirements
+
+         The memory layout must be [integral].
+
+         # Guarantees
+
+         - The emitted code does not declare any labels.
+         - The emitted code is “straight-line”, _i.e._, does not contain any of the instructions
+           `call`, `return`, `recurse`, `recurse_or_return`, or `skiz`.
+         - The emitted code does not contain instruction `halt`.
+         - All memory write access of the emitted code is within the bounds of the memory region
+           pointed to by `*free_memory_page`. Only intermediate values are written to memory.
+
+         [integral]: crate::air::memory_layout::IntegralMemoryLayout::is_integral
+         [order]: crate::air::memory_layout::CoefficientOrder
+        "
+    }
+
+    fn doc_comment_subset_on_stack(full_version: &str) -> String {
+        format!(
+            "
+         Like [`{full_version}`], except that only the constraints of the given categories are
+         evaluated. Consequently, [`categories.num_constraints()`][num] evaluations are left on
+         the stack, those of the selected categories' constraints in the order initial,
+         consistency, transition, terminal.
+
+         [num]: ConstraintCategorySet::num_constraints
+        "
+        )
+    }
+
     fn doc_comment_dynamic_version() -> &'static str {
         "
          The emitted Triton assembly has the following signature:
@@ -296,6 +772,59 @@ impl TasmBackend {
         "
     }
 
+    fn doc_comment_batched_version() -> &'static str {
+        "
+         Like [`static_air_constraint_evaluation_tasm`], but evaluates the constraints for
+         `num_rows` consecutive rows in one go. Row `i` of each of the input lists starts at the
+         respective pointer of the memory layout, offset by `i` times the length of that list's
+         rows, _i.e._, `i·NUM_BASE_COLUMNS` or `i·NUM_EXT_COLUMNS` [`XFieldElement`][xfe]s.
+
+         The emitted Triton assembly is a subroutine with the following signature:
+
+         # Signature
+
+         ```text
+         BEFORE: _
+         AFTER:  _ *evaluated_constraints
+         ```
+         # Requirements
+
+         In order for this method to emit Triton assembly, various memory regions need to be
+         declared. This is done through [`StaticTasmConstraintEvaluationMemoryLayout`]. The memory
+         layout must be [integral] when the length of each of the row lists is multiplied by
+         `num_rows`.
+
+         # Guarantees
+
+         Since the rows are evaluated in a loop, the emitted code is _not_ straight-line.
+         Instead:
+
+         - The emitted code declares the labels `tasm_air_constraints_batched`, the subroutine's
+           entry point, and `tasm_air_constraints_batched_loop`. It must be invoked using
+           `call tasm_air_constraints_batched`, and it must be placed where it is not reached by
+           straight-line execution, for example after instruction `halt`.
+         - The emitted code contains the instructions `call`, `return`, `recurse`, and `skiz`.
+         - The emitted code does not contain instruction `halt`.
+         - All memory write access of the emitted code is within the bounds of the memory region
+           pointed to by `*free_memory_page`.
+         - `*evaluated_constraints` points to an array of [`XFieldElement`][xfe]s of length
+           `num_rows·`[`NUM_CONSTRAINTS`][total], which starts at the
+           [batched output array][out]. It is the concatenation of the evaluations of each row,
+           laid out like the evaluations of [`static_air_constraint_evaluation_tasm`].
+
+         # Panics
+
+         Panics if the evaluations of all rows exceed the
+         [maximal length of the batched output array][max].
+
+         [integral]: crate::air::memory_layout::IntegralMemoryLayout::is_integral
+         [xfe]: twenty_first::prelude::XFieldElement
+         [total]: crate::table::master_table::MasterExtTable::NUM_CONSTRAINTS
+         [out]: crate::air::memory_layout::BATCHED_OUT_ARRAY_OFFSET
+         [max]: crate::air::memory_layout::MAX_BATCHED_OUT_ARRAY_LEN
+        "
+    }
+
     /// Moves the dynamic arguments ({current, next} {main, aux} row pointers)
     /// to static addresses dedicated to them.
     fn write_row_pointers_to_ram(&self) -> Vec<TokenStream> {
@@ -322,11 +851,38 @@ impl TasmBackend {
         .collect()
     }
 
-    fn tokenize_circuits<II: InputIndicator>(
+    /// Code evaluating the given constraints and writing them to the output.
+    ///
+    /// If shared nodes are to be [kept on the stack][kept], the code is generated twice: the
+    /// loads of shared nodes emitted in the first pass determine which nodes can be kept on the
+    /// stack in the second pass.
+    ///
+    /// [kept]: Self::keep_shared_nodes_on_stack
+    pub(super) fn tokenize_circuits<II: InputIndicator>(
+        &mut self,
+        constraints: &[ConstraintCircuit<II>],
+    ) -> Vec<TokenStream> {
+        self.kept_on_stack = HashMap::new();
+        if !self.keep_shared_nodes_on_stack {
+            self.duplicated_on_stack = HashSet::new();
+            return self.tokenize_circuits_with_current_stack_usage(constraints);
+        }
+
+        self.duplicated_on_stack = Self::nodes_used_only_as_both_operands(constraints);
+        let elements_written = self.elements_written;
+        self.tokenize_circuits_with_current_stack_usage(constraints);
+        self.elements_written = elements_written;
+        self.kept_on_stack = self.nodes_to_keep_on_stack();
+        self.tokenize_circuits_with_current_stack_usage(constraints)
+    }
+
+    fn tokenize_circuits_with_current_stack_usage<II: InputIndicator>(
         &mut self,
         constraints: &[ConstraintCircuit<II>],
     ) -> Vec<TokenStream> {
         self.scope = HashSet::new();
+        self.stored_nodes = vec![];
+        self.node_loads = vec![];
         let store_shared_nodes = self.store_all_shared_nodes(constraints);
 
         // to match the `RustBackend`, base constraints must be emitted first
@@ -335,12 +891,91 @@ impl TasmBackend {
             .partition(|constraint| constraint.evaluates_to_base_element());
         let sorted_constraints = base_constraints.into_iter().chain(ext_constraints);
         let write_to_output = sorted_constraints
-            .map(|c| self.write_evaluated_constraint_into_output_list(c))
+            .enumerate()
+            .map(|(i, c)| self.write_evaluated_constraint_into_output_list(c, i))
             .concat();
 
         [store_shared_nodes, write_to_output].concat()
     }
 
+    /// The IDs of all shared nodes that are referenced by exactly one binary operation, which
+    /// uses the node as both of its operands. Storing such a node to memory is unnecessary:
+    /// its value can be duplicated on the stack right after computing it.
+    fn nodes_used_only_as_both_operands<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+    ) -> HashSet<usize> {
+        fn collect<II: InputIndicator>(
+            circuit: &ConstraintCircuit<II>,
+            visited: &mut HashSet<usize>,
+            nodes: &mut HashSet<usize>,
+        ) {
+            if !visited.insert(circuit.id) {
+                return;
+            }
+            let CircuitExpression::BinaryOperation(_, lhs, rhs) = &circuit.expression else {
+                return;
+            };
+
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+            let is_bin_op = matches!(lhs.expression, CircuitExpression::BinaryOperation(..));
+            if lhs.id == rhs.id && lhs.ref_count == 2 && is_bin_op {
+                nodes.insert(lhs.id);
+            }
+            collect(&lhs, visited, nodes);
+            collect(&rhs, visited, nodes);
+        }
+
+        let mut visited = HashSet::new();
+        let mut nodes = HashSet::new();
+        for constraint in constraints {
+            collect(constraint, &mut visited, &mut nodes);
+        }
+        nodes
+    }
+
+    /// The shared nodes that can be kept on the stack instead of being stored to memory, each
+    /// mapped to the index of the last [stored node](Self::stored_nodes) using it. Relies on
+    /// the [loads](Self::node_loads) emitted when generating the code without keeping any node
+    /// on the stack.
+    ///
+    /// At most one node is kept on the stack at any time. Since the value of every stored node is
+    /// computed with the stack in the same state, the value of a node kept on the stack is then
+    /// right below the words needed for computing the nodes using it, and can be duplicated from
+    /// there. This requires that all of the node's uses are in the evaluation of other stored
+    /// nodes, and that the node's value never gets too deep in the stack to be duplicated.
+    ///
+    /// The nodes are selected greedily by their last use, which maximizes the number of nodes
+    /// kept on the stack.
+    fn nodes_to_keep_on_stack(&self) -> HashMap<usize, usize> {
+        let max_stack_height = NUM_OP_STACK_REGISTERS - EXTENSION_DEGREE;
+        let mut candidates = vec![];
+        for (stored_node_index, &node_id) in self.stored_nodes.iter().enumerate() {
+            let is_load_of_node = |load: &&NodeLoad| load.node_id == node_id;
+            let loads = self.node_loads.iter().filter(is_load_of_node).collect_vec();
+            let is_too_deep = |load: &&NodeLoad| load.stack_height > max_stack_height;
+            if loads.iter().any(is_too_deep) {
+                continue;
+            }
+            let users: Option<Vec<_>> = loads.iter().map(|load| load.stored_node_index).collect();
+            let Some(last_user) = users.and_then(|users| users.into_iter().max()) else {
+                continue;
+            };
+            candidates.push((stored_node_index, last_user, node_id));
+        }
+        candidates.sort_by_key(|&(_, last_user, _)| last_user);
+
+        let mut kept_on_stack = HashMap::new();
+        let mut last_user_of_kept_node = None;
+        for (stored_node_index, last_user, node_id) in candidates {
+            if last_user_of_kept_node.is_some_and(|last| stored_node_index <= last) {
+                continue;
+            }
+            kept_on_stack.insert(node_id, last_user);
+            last_user_of_kept_node = Some(last_user);
+        }
+        kept_on_stack
+    }
+
     fn store_all_shared_nodes<II: InputIndicator>(
         &mut self,
         constraints: &[ConstraintCircuit<II>],
@@ -376,51 +1011,107 @@ impl TasmBackend {
             return vec![];
         };
 
-        if constraint.ref_count < ref_count {
+        if constraint.ref_count < ref_count || self.duplicated_on_stack.contains(&constraint.id) {
             let out_left = self.store_single_shared_node_of_ref_count(&lhs.borrow(), ref_count);
             let out_right = self.store_single_shared_node_of_ref_count(&rhs.borrow(), ref_count);
             return [out_left, out_right].concat();
         }
 
         assert_eq!(constraint.ref_count, ref_count);
-        let evaluate = self.evaluate_single_node(constraint);
-        let store = Self::store_ext_field_element(constraint.id);
+        let stored_node_index = self.stored_nodes.len();
+        let evaluate = self.evaluate_node(constraint, Some(stored_node_index), 0);
+        let store = match self.kept_on_stack.contains_key(&constraint.id) {
+            true => vec![],
+            false => Self::store_ext_field_element(IOList::FreeMemPage, constraint.id),
+        };
+        let drop_kept_node = match self.kept_on_stack.values().contains(&stored_node_index) {
+            true => instr!(Pop(NumberOfWords::N3)),
+            false => vec![],
+        };
+        self.stored_nodes.push(constraint.id);
         let is_new_insertion = self.scope.insert(constraint.id);
         assert!(is_new_insertion);
 
-        [evaluate, store].concat()
+        [evaluate, store, drop_kept_node].concat()
     }
 
     fn evaluate_single_node<II: InputIndicator>(
-        &self,
+        &mut self,
         constraint: &ConstraintCircuit<II>,
+    ) -> Vec<TokenStream> {
+        self.evaluate_node(constraint, None, 0)
+    }
+
+    /// Evaluate the node, recording all loads of stored nodes. See also [`NodeLoad`].
+    fn evaluate_node<II: InputIndicator>(
+        &mut self,
+        constraint: &ConstraintCircuit<II>,
+        stored_node_index: Option<usize>,
+        stack_height: usize,
     ) -> Vec<TokenStream> {
         if self.scope.contains(&constraint.id) {
-            return self.load_node(constraint);
+            self.node_loads.push(NodeLoad {
+                node_id: constraint.id,
+                stored_node_index,
+                stack_height,
+            });
+            return match self.kept_on_stack.contains_key(&constraint.id) {
+                true => Self::duplicate_ext_field_element(stack_height),
+                false => self.load_node(constraint),
+            };
         }
 
         let CircuitExpression::BinaryOperation(binop, lhs, rhs) = &constraint.expression else {
             return self.load_node(constraint);
         };
 
-        let lhs = self.evaluate_single_node(&lhs.borrow());
-        let rhs = self.evaluate_single_node(&rhs.borrow());
+        let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+        // Adding a base-field constant only changes the extension field element's constant
+        // coefficient, which is on top of the stack.
+        if self.add_base_field_constants_with_addi && *binop == BinOp::Add {
+            if let CircuitExpression::BConstant(bfe) = rhs.expression {
+                let lhs = self.evaluate_node(&lhs, stored_node_index, stack_height);
+                return [lhs, instr!(AddI(bfe))].concat();
+            }
+            if let CircuitExpression::BConstant(bfe) = lhs.expression {
+                let rhs = self.evaluate_node(&rhs, stored_node_index, stack_height);
+                return [rhs, instr!(AddI(bfe))].concat();
+            }
+        }
+
+        let lhs_code = self.evaluate_node(&lhs, stored_node_index, stack_height);
+        let rhs_code = match lhs.id == rhs.id {
+            true => Self::duplicate_ext_field_element(0),
+            false => {
+                let stack_height = stack_height + EXTENSION_DEGREE;
+                self.evaluate_node(&rhs, stored_node_index, stack_height)
+            }
+        };
         let binop = match binop {
             BinOp::Add => instr!(XxAdd),
             BinOp::Mul => instr!(XxMul),
         };
-        [lhs, rhs, binop].concat()
+        [lhs_code, rhs_code, binop].concat()
     }
 
+    /// Evaluate the constraint and write the result to the output array at the given index,
+    /// which is relative to the start of the current constraint type's part of the output array.
     fn write_evaluated_constraint_into_output_list<II: InputIndicator>(
         &mut self,
         constraint: &ConstraintCircuit<II>,
+        element_index: usize,
     ) -> Vec<TokenStream> {
         let evaluated_constraint = self.evaluate_single_node(constraint);
-        let element_index = OUT_ARRAY_OFFSET + self.elements_written;
-        let store_element = Self::store_ext_field_element(element_index);
+        let reorder_coefficients = match self.coefficient_order {
+            CoefficientOrder::LittleEndian => vec![],
+            CoefficientOrder::BigEndian => instr!(Swap(OpStackElement::ST2)),
+        };
+        let store_element = match self.results_on_stack {
+            true => vec![],
+            false => Self::store_ext_field_element(IOList::OutArray, element_index),
+        };
         self.elements_written += 1;
-        [evaluated_constraint, store_element].concat()
+        [evaluated_constraint, reorder_coefficients, store_element].concat()
     }
 
     fn load_node<II: InputIndicator>(&self, circuit: &ConstraintCircuit<II>) -> Vec<TokenStream> {
@@ -433,6 +1124,18 @@ impl TasmBackend {
         }
     }
 
+    /// Duplicate the extension field element below the given number of words on the stack.
+    fn duplicate_ext_field_element(num_words_above: usize) -> Vec<TokenStream> {
+        let stack_index = num_words_above + EXTENSION_DEGREE - 1;
+        let stack_element = OpStackElement::try_from(u32::try_from(stack_index).unwrap()).unwrap();
+        [
+            instr!(Dup(stack_element)),
+            instr!(Dup(stack_element)),
+            instr!(Dup(stack_element)),
+        ]
+        .concat()
+    }
+
     fn load_ext_field_constant(xfe: XFieldElement) -> Vec<TokenStream> {
         let [c0, c1, c2] = xfe.coefficients.map(|c| push!(c));
         [c2, c1, c0].concat()
@@ -495,13 +1198,11 @@ impl TasmBackend {
         bfe!(u64::try_from(word_index).unwrap())
     }
 
-    fn store_ext_field_element(element_index: usize) -> Vec<TokenStream> {
-        let free_mem_page = IOList::FreeMemPage;
-
+    fn store_ext_field_element(list: IOList, element_index: usize) -> Vec<TokenStream> {
         let word_offset = element_index * EXTENSION_DEGREE;
         let word_index = u64::try_from(word_offset).unwrap();
 
-        let push_address = push!(free_mem_page + word_index);
+        let push_address = push!(list + word_index);
         let write_mem = instr!(WriteMem(NumberOfWords::N3));
         let pop = instr!(Pop(NumberOfWords::N1));
 
@@ -519,6 +1220,7 @@ impl TasmBackend {
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum IOList {
     FreeMemPage,
+    OutArray,
     CurrBaseRow,
     CurrExtRow,
     NextBaseRow,
@@ -530,6 +1232,7 @@ impl ToTokens for IOList {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             IOList::FreeMemPage => tokens.extend(quote!(free_mem_page_ptr)),
+            IOList::OutArray => tokens.extend(quote!(out_array_ptr)),
             IOList::CurrBaseRow => tokens.extend(quote!(curr_base_row_ptr)),
             IOList::CurrExtRow => tokens.extend(quote!(curr_ext_row_ptr)),
             IOList::NextBaseRow => tokens.extend(quote!(next_base_row_ptr)),
@@ -541,6 +1244,38 @@ impl ToTokens for IOList {
 
 #[cfg(test)]
 mod tests {
+    use triton_vm::table::constraint_circuit::ConstraintCircuitBuilder;
+    use triton_vm::table::constraint_circuit::SingleRowIndicator;
+
+    use triton_vm::air::batch_air_constraint_evaluation_tasm;
+    use triton_vm::air::memory_layout::DynamicTasmConstraintEvaluationMemoryLayout;
+    use triton_vm::air::memory_layout::StaticTasmConstraintEvaluationMemoryLayout;
+    use triton_vm::air::tasm_air_constraints::static_air_constraint_evaluation_tasm;
+    use triton_vm::instruction::Instruction;
+    use triton_vm::instruction::LabelledInstruction;
+    use triton_vm::prelude::Program;
+    use twenty_first::prelude::BFieldCodec;
+
+    use std::collections::HashMap;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use syn::Expr;
+    use syn::ExprBinary;
+    use syn::ExprLit;
+    use syn::Lit;
+    use test_strategy::proptest;
+    use triton_vm::op_stack::NUM_OP_STACK_REGISTERS;
+    use triton_vm::prelude::triton_asm;
+    use triton_vm::prelude::NonDeterminism;
+    use triton_vm::prelude::VMState;
+    use triton_vm::table::challenges::Challenges;
+    use triton_vm::table::NUM_BASE_COLUMNS;
+    use triton_vm::table::NUM_EXT_COLUMNS;
+
+    use crate::codegen::tasm_diff::TasmDiff;
+    use crate::codegen::tests::opcode_histogram;
     use crate::codegen::tests::print_constraints;
 
     use super::*;
@@ -554,4 +1289,698 @@ mod tests {
     fn print_test_constraints() {
         print_constraints::<TasmBackend>(&Constraints::test_constraints());
     }
+
+    #[test]
+    fn only_categories_with_constraints_can_be_selected() {
+        let code = TasmBackend::constraint_evaluation_code(&Constraints::test_constraints());
+        let code = code.to_string();
+
+        // one of each per static input locations, results left on stack, and dynamic input
+        // locations
+        let num_selections = |ty| {
+            code.matches(&format!("contains (ConstraintType :: {ty})"))
+                .count()
+        };
+        assert_eq!(3, num_selections("Initial"));
+        assert_eq!(0, num_selections("Consistency"));
+        assert_eq!(3, num_selections("Transition"));
+        assert_eq!(0, num_selections("Terminal"));
+    }
+
+    #[test]
+    fn generated_code_starts_with_cost_summary() {
+        let constraints = Constraints::test_constraints();
+        let code = TasmBackend::constraint_evaluation_code(&constraints);
+        let file: syn::File = syn::parse2(code).unwrap();
+        let [summary] = file.attrs.as_slice() else {
+            panic!("expected exactly one inner attribute");
+        };
+        let summary = summary.to_token_stream().to_string();
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let init_instructions = backend.tokenize_circuits(&constraints.init());
+        let tran_instructions = backend.tokenize_circuits(&constraints.tran());
+        let num_init_instructions = instructions(&init_instructions).len();
+        let num_tran_instructions = instructions(&tran_instructions).len();
+        assert!(num_init_instructions < init_instructions.len());
+        let init_degree = TasmBackend::max_degree(&constraints.init()).unwrap();
+        let tran_degree = TasmBackend::max_degree(&constraints.tran()).unwrap();
+
+        assert!(summary.contains(&format!(
+            "| initial | {init_degree} | {num_init_instructions} |"
+        )));
+        assert!(summary.contains("| consistency | - | 0 |"));
+        assert!(summary.contains(&format!(
+            "| transition | {tran_degree} | {num_tran_instructions} |"
+        )));
+        assert!(summary.contains("| terminal | - | 0 |"));
+    }
+
+    #[test]
+    fn output_array_of_all_constraints_matches_rust_backend() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        backend.tokenize_circuits(&constraints.init());
+        backend.tokenize_circuits(&constraints.cons());
+        backend.tokenize_circuits(&constraints.tran());
+        backend.tokenize_circuits(&constraints.term());
+        backend.assert_output_array_matches_rust_backend(&constraints);
+    }
+
+    #[test]
+    #[should_panic(expected = "writes 6 elements for 7 constraints")]
+    fn output_array_missing_a_constraint_is_detected() {
+        let constraints = Constraints::test_constraints();
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        backend.tokenize_circuits(&constraints.init());
+        backend.tokenize_circuits(&constraints.tran()[1..]);
+        backend.assert_output_array_matches_rust_backend(&constraints);
+    }
+
+    #[test]
+    fn required_free_mem_words_for_all_constraints_fit_into_one_memory_page() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let required_free_mem_words = TasmBackend::required_free_mem_words(&constraints);
+        assert!(required_free_mem_words < memory_layout::MEM_PAGE_SIZE);
+    }
+
+    #[test]
+    fn shared_nodes_of_all_constraints_end_before_batched_output_array() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let shared_nodes_end = TasmBackend::required_shared_nodes_mem_words(&constraints);
+        assert!(shared_nodes_end <= memory_layout::BATCHED_OUT_ARRAY_OFFSET);
+    }
+
+    #[test]
+    fn required_free_mem_words_covers_output_array() {
+        let constraints = Constraints::test_constraints();
+        let num_constraints = constraints.init.len()
+            + constraints.cons.len()
+            + constraints.tran.len()
+            + constraints.term.len();
+
+        let required_free_mem_words = TasmBackend::required_free_mem_words(&constraints);
+        let out_array_end = (OUT_ARRAY_OFFSET + num_constraints) * EXTENSION_DEGREE;
+        assert_eq!(out_array_end, required_free_mem_words);
+    }
+
+    /// The instructions of the given code, which must start with an instruction. The arguments
+    /// of the instructions are not parsed.
+    fn instructions(code: &[TokenStream]) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        let mut word_index = 0;
+        while let Some(opcode) = code.get(word_index) {
+            let opcode = evaluate_raw_instruction(opcode, &[]);
+            let instruction = Instruction::try_from(opcode).unwrap();
+            word_index += instruction.size();
+            instructions.push(instruction);
+        }
+        instructions
+    }
+
+    /// The number of `write_mem` instructions in the given code, which must start with an
+    /// instruction.
+    fn num_write_mem_instructions(code: &[TokenStream]) -> usize {
+        instructions(code)
+            .into_iter()
+            .filter(|instruction| matches!(instruction, Instruction::WriteMem(_)))
+            .count()
+    }
+
+    /// The total number of `write_mem` instructions in the code evaluating all constraints.
+    fn num_write_mem_instructions_for_all_categories(
+        mut backend: TasmBackend,
+        constraints: &Constraints,
+    ) -> usize {
+        [
+            backend.tokenize_circuits(&constraints.init()),
+            backend.tokenize_circuits(&constraints.cons()),
+            backend.tokenize_circuits(&constraints.tran()),
+            backend.tokenize_circuits(&constraints.term()),
+        ]
+        .iter()
+        .map(|code| num_write_mem_instructions(code))
+        .sum()
+    }
+
+    #[test]
+    fn keeping_shared_nodes_on_stack_reduces_number_of_stores_of_all_constraints() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let backend = TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let backend_storing_all_shared_nodes = TasmBackend {
+            keep_shared_nodes_on_stack: false,
+            ..backend.clone()
+        };
+
+        let num_write_mems = num_write_mem_instructions_for_all_categories(backend, &constraints);
+        let num_write_mems_storing_all_shared_nodes = num_write_mem_instructions_for_all_categories(
+            backend_storing_all_shared_nodes,
+            &constraints,
+        );
+        assert!(num_write_mems < num_write_mems_storing_all_shared_nodes);
+    }
+
+    #[test]
+    fn shared_node_used_only_by_stored_nodes_is_kept_on_stack() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let input = |i| circuit_builder.input(SingleRowIndicator::BaseRow(i));
+        let product = input(0) * input(1);
+        let users = [
+            product.clone() + input(2),
+            product.clone() * input(3),
+            product + input(4),
+        ];
+        let init = users
+            .into_iter()
+            .flat_map(|user| [user.clone() * input(5), user * input(6)])
+            .collect();
+        let constraints = Constraints {
+            init,
+            cons: vec![],
+            tran: vec![],
+            term: vec![],
+            provenance: vec![],
+        };
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let code = backend.tokenize_circuits(&constraints.init());
+        assert_eq!(1, backend.kept_on_stack.len());
+        assert_eq!(3 + 6, num_write_mem_instructions(&code));
+
+        backend.keep_shared_nodes_on_stack = false;
+        let code = backend.tokenize_circuits(&constraints.init());
+        assert_eq!(4 + 6, num_write_mem_instructions(&code));
+    }
+
+    #[test]
+    fn squared_shared_node_is_duplicated_instead_of_stored() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let input = |i| circuit_builder.input(SingleRowIndicator::BaseRow(i));
+        let sum = input(0) + input(1);
+        let constraints = Constraints {
+            init: vec![sum.clone() * sum],
+            cons: vec![],
+            tran: vec![],
+            term: vec![],
+            provenance: vec![],
+        };
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let code = backend.tokenize_circuits(&constraints.init());
+        assert_eq!(1, num_write_mem_instructions(&code));
+        assert!(backend.scope.is_empty());
+    }
+
+    #[test]
+    fn adding_base_field_constant_uses_addi() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let input = circuit_builder.input(SingleRowIndicator::BaseRow(0));
+        let constant = circuit_builder.b_constant(bfe!(42));
+
+        let commuting_circuit_builder = ConstraintCircuitBuilder::new();
+        let commuted_input = commuting_circuit_builder.input(SingleRowIndicator::BaseRow(0));
+        let commuted_constant = commuting_circuit_builder.b_constant(bfe!(42));
+
+        let constraints = Constraints {
+            init: vec![input + constant],
+            cons: vec![commuted_constant + commuted_input],
+            tran: vec![],
+            term: vec![],
+            provenance: vec![],
+        };
+        let [sum] = constraints.init().try_into().unwrap();
+        let [commuted_sum] = constraints.cons().try_into().unwrap();
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let CircuitExpression::BinaryOperation(_, lhs, _) = &sum.expression else {
+            panic!("expected a binary operation");
+        };
+        let load_input = backend.load_node(&lhs.borrow());
+        let expected_code = [load_input, instr!(AddI(bfe!(42)))].concat();
+        let to_strings = |code: &[TokenStream]| code.iter().map(|t| t.to_string()).collect_vec();
+
+        let code = backend.evaluate_single_node(&sum);
+        assert_eq!(to_strings(&expected_code), to_strings(&code));
+        let code = backend.evaluate_single_node(&commuted_sum);
+        assert_eq!(to_strings(&expected_code), to_strings(&code));
+    }
+
+    #[test]
+    fn adding_base_field_constants_reduces_instruction_count_of_all_constraints() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let num_instructions = |mut backend: TasmBackend| {
+            [
+                backend.tokenize_circuits(&constraints.init()),
+                backend.tokenize_circuits(&constraints.cons()),
+                backend.tokenize_circuits(&constraints.tran()),
+                backend.tokenize_circuits(&constraints.term()),
+            ]
+            .iter()
+            .map(|code| instructions(code).len())
+            .sum::<usize>()
+        };
+
+        // Without `addi`, adding a base-field constant requires three `push` instructions to
+        // load it as an extension field element, plus one `xx_add`.
+        let backend = TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let backend_without_addi = TasmBackend {
+            add_base_field_constants_with_addi: false,
+            ..backend.clone()
+        };
+        assert!(num_instructions(backend) < num_instructions(backend_without_addi));
+    }
+
+    /// The value of a single raw instruction as emitted by the [`TasmBackend`], _i.e._, of an
+    /// integer literal, a pointer, or the sum of a pointer and an integer literal.
+    fn evaluate_raw_instruction(raw_instruction: &TokenStream, pointers: &[(&str, u64)]) -> u64 {
+        let expression = syn::parse2(raw_instruction.clone()).unwrap();
+        evaluate_raw_instruction_expression(&expression, pointers)
+    }
+
+    fn evaluate_raw_instruction_expression(expression: &Expr, pointers: &[(&str, u64)]) -> u64 {
+        match expression {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(literal),
+                ..
+            }) => literal.base10_parse().unwrap(),
+            Expr::Path(path) => {
+                let name = path.path.get_ident().unwrap().to_string();
+                let pointer = pointers.iter().find(|&&(pointer, _)| pointer == name);
+                let Some(&(_, value)) = pointer else {
+                    panic!("unknown pointer `{name}`");
+                };
+                value
+            }
+            Expr::Binary(ExprBinary {
+                left,
+                op: syn::BinOp::Add(_),
+                right,
+                ..
+            }) => {
+                let left = evaluate_raw_instruction_expression(left, pointers);
+                let right = evaluate_raw_instruction_expression(right, pointers);
+                left + right
+            }
+            _ => panic!("unexpected raw instruction `{}`", quote!(#expression)),
+        }
+    }
+
+    /// The instructions that the emitted function `static_air_constraint_evaluation_tasm`
+    /// returns for the given memory layout. Evaluates the raw instructions emitted by the
+    /// [`TasmBackend`] like [`TasmBackend::assemble_raw_instructions`] does, which avoids
+    /// having to compile the emitted code.
+    fn static_air_constraint_evaluation_instructions(
+        constraints: &Constraints,
+        mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+        coefficient_order: CoefficientOrder,
+    ) -> Vec<LabelledInstruction> {
+        let pointers = static_mem_layout_pointers(mem_layout);
+        let backend = TasmBackend::statically_known_input_locations(coefficient_order);
+        air_constraint_evaluation_instructions(backend, constraints, pointers)
+    }
+
+    fn static_mem_layout_pointers(
+        mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+    ) -> [(&'static str, u64); 6] {
+        [
+            ("free_mem_page_ptr", mem_layout.free_mem_page_ptr.value()),
+            ("curr_base_row_ptr", mem_layout.curr_base_row_ptr.value()),
+            ("curr_ext_row_ptr", mem_layout.curr_ext_row_ptr.value()),
+            ("next_base_row_ptr", mem_layout.next_base_row_ptr.value()),
+            ("next_ext_row_ptr", mem_layout.next_ext_row_ptr.value()),
+            ("challenges_ptr", mem_layout.challenges_ptr.value()),
+        ]
+    }
+
+    /// Like [`static_air_constraint_evaluation_instructions`], but for the emitted function
+    /// `dynamic_air_constraint_evaluation_tasm`.
+    fn dynamic_air_constraint_evaluation_instructions(
+        constraints: &Constraints,
+        mem_layout: DynamicTasmConstraintEvaluationMemoryLayout,
+    ) -> Vec<LabelledInstruction> {
+        let free_mem_page_ptr = mem_layout.free_mem_page_ptr.value();
+        let pointers = [
+            ("free_mem_page_ptr", free_mem_page_ptr + 4),
+            ("curr_base_row_ptr", free_mem_page_ptr),
+            ("curr_ext_row_ptr", free_mem_page_ptr + 1),
+            ("next_base_row_ptr", free_mem_page_ptr + 2),
+            ("next_ext_row_ptr", free_mem_page_ptr + 3),
+            ("challenges_ptr", mem_layout.challenges_ptr.value()),
+        ];
+        let backend = TasmBackend::dynamically_known_input_locations(CoefficientOrder::default());
+        air_constraint_evaluation_instructions(backend, constraints, pointers)
+    }
+
+    fn air_constraint_evaluation_instructions(
+        mut backend: TasmBackend,
+        constraints: &Constraints,
+        pointers: [(&str, u64); 6],
+    ) -> Vec<LabelledInstruction> {
+        let (_, free_mem_page_ptr) = pointers[0];
+        let out_array_start =
+            free_mem_page_ptr + u64::try_from(OUT_ARRAY_OFFSET * EXTENSION_DEGREE).unwrap();
+        let mut out_array_len = 0;
+
+        let preamble = match backend.input_location_is_static {
+            true => vec![],
+            false => backend.write_row_pointers_to_ram(),
+        };
+        let instructions_by_category = [
+            backend.tokenize_circuits(&constraints.init()),
+            backend.tokenize_circuits(&constraints.cons()),
+            backend.tokenize_circuits(&constraints.tran()),
+            backend.tokenize_circuits(&constraints.term()),
+        ];
+        let num_constraints = [
+            constraints.num_init(),
+            constraints.num_cons(),
+            constraints.num_tran(),
+            constraints.num_term(),
+        ];
+
+        let evaluate = |raw_instruction| evaluate_raw_instruction(raw_instruction, &pointers);
+        let mut raw_instructions = preamble.iter().map(evaluate).collect_vec();
+        for (instructions, num_constraints) in izip!(instructions_by_category, num_constraints) {
+            let out_array_ptr = [("out_array_ptr", out_array_start + out_array_len)];
+            let pointers = [pointers.as_slice(), &out_array_ptr].concat();
+            let evaluate = |raw_instruction| evaluate_raw_instruction(raw_instruction, &pointers);
+            raw_instructions.extend(instructions.iter().map(evaluate));
+            out_array_len += u64::try_from(num_constraints * EXTENSION_DEGREE).unwrap();
+        }
+        if !backend.results_on_stack {
+            raw_instructions.extend(TasmBackend::prepare_return_values().iter().map(evaluate));
+        }
+
+        let raw_instructions = [vec![raw_instructions.len() as u64], raw_instructions].concat();
+        let raw_instructions = raw_instructions.into_iter().map(BFieldElement::new);
+        let program = Program::decode(&raw_instructions.collect_vec()).unwrap();
+        program.labelled_instructions()
+    }
+
+    fn static_mem_layout() -> StaticTasmConstraintEvaluationMemoryLayout {
+        let mem_page = |i: u64| bfe!(i * memory_layout::MEM_PAGE_SIZE as u64);
+        StaticTasmConstraintEvaluationMemoryLayout {
+            free_mem_page_ptr: mem_page(0),
+            curr_base_row_ptr: mem_page(1),
+            curr_ext_row_ptr: mem_page(2),
+            next_base_row_ptr: mem_page(3),
+            next_ext_row_ptr: mem_page(4),
+            challenges_ptr: mem_page(5),
+        }
+    }
+
+    #[test]
+    fn evaluated_raw_instructions_of_test_constraints_form_a_program() {
+        let constraints = Constraints::test_constraints();
+        let instructions = static_air_constraint_evaluation_instructions(
+            &constraints,
+            static_mem_layout(),
+            CoefficientOrder::default(),
+        );
+
+        let mut backend =
+            TasmBackend::statically_known_input_locations(CoefficientOrder::default());
+        let num_init_instructions = backend.tokenize_circuits(&constraints.init()).len();
+        let num_tran_instructions = backend.tokenize_circuits(&constraints.tran()).len();
+        let num_raw_instructions = instructions
+            .iter()
+            .map(|instruction| match instruction {
+                LabelledInstruction::Instruction(instruction) => instruction.size(),
+                _ => 0,
+            })
+            .sum::<usize>();
+        let num_return_instructions = TasmBackend::prepare_return_values().len();
+        assert_eq!(
+            num_init_instructions + num_tran_instructions + num_return_instructions,
+            num_raw_instructions
+        );
+    }
+
+    #[test]
+    fn extension_field_multiplication_dominates_arithmetic_of_all_constraints() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+        let instructions = static_air_constraint_evaluation_instructions(
+            &constraints,
+            static_mem_layout(),
+            CoefficientOrder::default(),
+        );
+
+        // Moving operands between memory and stack, _i.e._, instructions like `push`, `pop`, or
+        // `read_mem`, is more frequent than any arithmetic and is deliberately not considered.
+        let arithmetic_instructions = [
+            Instruction::Add,
+            Instruction::Mul,
+            Instruction::Invert,
+            Instruction::XxAdd,
+            Instruction::XxMul,
+            Instruction::XInvert,
+            Instruction::XbMul,
+        ];
+        let histogram = opcode_histogram(&instructions);
+        let num_xx_mul = histogram[&Instruction::XxMul];
+        for instruction in arithmetic_instructions {
+            let count = histogram.get(&instruction).copied().unwrap_or_default();
+            assert!(
+                count <= num_xx_mul,
+                "{instruction} ({count}) must not be more frequent than xx_mul ({num_xx_mul})"
+            );
+        }
+    }
+
+    /// Requires the committed constraint evaluation code to be generated, like all tests of
+    /// [Triton VM](triton_vm) that evaluate constraints.
+    #[test]
+    fn regenerating_unchanged_constraints_yields_empty_diff_against_committed_code() {
+        let mut constraints = Constraints::all();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+        let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
+
+        let committed = static_air_constraint_evaluation_tasm(static_mem_layout());
+        let regenerated = static_air_constraint_evaluation_instructions(
+            &constraints,
+            static_mem_layout(),
+            CoefficientOrder::default(),
+        );
+        let diff = TasmDiff::new(&committed, &regenerated);
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    /// The number of columns per row that the [test constraints][test] refer to.
+    ///
+    /// [test]: Constraints::test_constraints
+    const NUM_TEST_COLUMNS: usize = 4;
+
+    /// Evaluate the [test constraints][test] in Triton VM on the given inputs, which are the
+    /// elements of the four rows, followed by the challenges. Returns the final RAM and a
+    /// pointer to the evaluated constraints.
+    ///
+    /// [test]: Constraints::test_constraints
+    fn evaluate_test_constraints_in_triton_vm(
+        coefficient_order: CoefficientOrder,
+        inputs: &[XFieldElement],
+    ) -> (HashMap<BFieldElement, BFieldElement>, BFieldElement) {
+        let constraints = Constraints::test_constraints();
+        let instructions = static_air_constraint_evaluation_instructions(
+            &constraints,
+            static_mem_layout(),
+            coefficient_order,
+        );
+        let vm_state = run_test_constraint_evaluation(instructions, inputs);
+
+        let out_array_ptr = vm_state.op_stack[OpStackElement::ST0];
+        (vm_state.ram, out_array_ptr)
+    }
+
+    /// Run the given instructions evaluating the [test constraints][test] in Triton VM, with
+    /// the inputs laid out in memory like [`evaluate_test_constraints_in_triton_vm`] does.
+    /// Returns the final state.
+    ///
+    /// [test]: Constraints::test_constraints
+    fn run_test_constraint_evaluation(
+        instructions: Vec<LabelledInstruction>,
+        inputs: &[XFieldElement],
+    ) -> VMState {
+        let mem_layout = static_mem_layout();
+        let (rows, challenges) = inputs.split_at(4 * NUM_TEST_COLUMNS);
+        let row_pointers = [
+            mem_layout.curr_base_row_ptr,
+            mem_layout.curr_ext_row_ptr,
+            mem_layout.next_base_row_ptr,
+            mem_layout.next_ext_row_ptr,
+        ];
+        let lists = izip!(row_pointers, rows.chunks(NUM_TEST_COLUMNS))
+            .chain([(mem_layout.challenges_ptr, challenges)]);
+
+        let mut ram = HashMap::new();
+        for (list_pointer, list) in lists {
+            for (i, word) in memory_layout::xfes_to_bfes(list).into_iter().enumerate() {
+                ram.insert(list_pointer + bfe!(i as u64), word);
+            }
+        }
+
+        let program = Program::new(&[instructions, triton_asm!(halt)].concat());
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let mut vm_state = VMState::new(&program, [].into(), non_determinism);
+        vm_state.run().unwrap();
+        vm_state
+    }
+
+    #[proptest(cases = 20)]
+    fn evaluations_left_on_stack_match_evaluations_written_to_memory(
+        #[strategy(vec(arb(), 4 * NUM_TEST_COLUMNS + Challenges::COUNT))] inputs: Vec<
+            XFieldElement,
+        >,
+    ) {
+        let constraints = Constraints::test_constraints();
+        let num_constraints = constraints.total();
+        let (ram, out_array_ptr) =
+            evaluate_test_constraints_in_triton_vm(CoefficientOrder::LittleEndian, &inputs);
+        let expected = memory_layout::read_xfe_list(&ram, out_array_ptr, num_constraints)?;
+
+        let backend = TasmBackend::statically_known_input_locations_with_results_on_stack(
+            CoefficientOrder::LittleEndian,
+        );
+        let pointers = static_mem_layout_pointers(static_mem_layout());
+        let instructions = air_constraint_evaluation_instructions(backend, &constraints, pointers);
+        let vm_state = run_test_constraint_evaluation(instructions, &inputs);
+
+        let stack = &vm_state.op_stack.stack;
+        prop_assert_eq!(
+            NUM_OP_STACK_REGISTERS + num_constraints * EXTENSION_DEGREE,
+            stack.len()
+        );
+        let (_, evaluations) = stack.split_at(NUM_OP_STACK_REGISTERS);
+        let on_stack = evaluations
+            .chunks_exact(EXTENSION_DEGREE)
+            .map(|chunk| XFieldElement::new([chunk[2], chunk[1], chunk[0]]))
+            .collect_vec();
+        prop_assert_eq!(expected, on_stack);
+    }
+
+    #[proptest(cases = 10)]
+    fn batched_evaluation_of_test_constraints_matches_repeated_single_row_evaluation(
+        #[strategy(0_usize..4)] _num_rows: usize,
+        #[strategy(vec(vec(arb(), 4 * NUM_TEST_COLUMNS), #_num_rows))] rows: Vec<
+            Vec<XFieldElement>,
+        >,
+        #[strategy(vec(arb(), Challenges::COUNT))] challenges: Vec<XFieldElement>,
+    ) {
+        let constraints = Constraints::test_constraints();
+        let num_constraints = constraints.total();
+        let mut expected = vec![];
+        for row in &rows {
+            let inputs = [row.as_slice(), &challenges].concat();
+            let (ram, out_array_ptr) =
+                evaluate_test_constraints_in_triton_vm(CoefficientOrder::LittleEndian, &inputs);
+            let evaluations = memory_layout::read_xfe_list(&ram, out_array_ptr, num_constraints);
+            expected.extend(evaluations.unwrap());
+        }
+
+        let mem_layout = static_mem_layout();
+        let list_pointers_and_row_lengths = [
+            (mem_layout.curr_base_row_ptr, NUM_BASE_COLUMNS),
+            (mem_layout.curr_ext_row_ptr, NUM_EXT_COLUMNS),
+            (mem_layout.next_base_row_ptr, NUM_BASE_COLUMNS),
+            (mem_layout.next_ext_row_ptr, NUM_EXT_COLUMNS),
+        ];
+        let mut ram = HashMap::new();
+        for (row_index, row) in rows.iter().enumerate() {
+            let lists = izip!(list_pointers_and_row_lengths, row.chunks(NUM_TEST_COLUMNS));
+            for ((list_pointer, row_len), list) in lists {
+                let row_pointer =
+                    list_pointer + bfe!((row_index * row_len * EXTENSION_DEGREE) as u64);
+                for (i, word) in memory_layout::xfes_to_bfes(list).into_iter().enumerate() {
+                    ram.insert(row_pointer + bfe!(i as u64), word);
+                }
+            }
+        }
+        for (i, word) in memory_layout::xfes_to_bfes(&challenges)
+            .into_iter()
+            .enumerate()
+        {
+            ram.insert(mem_layout.challenges_ptr + bfe!(i as u64), word);
+        }
+
+        let dynamic_mem_layout = DynamicTasmConstraintEvaluationMemoryLayout {
+            free_mem_page_ptr: mem_layout.free_mem_page_ptr,
+            challenges_ptr: mem_layout.challenges_ptr,
+        };
+        let single_row_evaluation =
+            dynamic_air_constraint_evaluation_instructions(&constraints, dynamic_mem_layout);
+        let batched_evaluation = batch_air_constraint_evaluation_tasm(
+            mem_layout,
+            rows.len(),
+            num_constraints,
+            single_row_evaluation,
+        );
+        let program = triton_asm!(call tasm_air_constraints_batched halt);
+        let program = Program::new(&[program, batched_evaluation].concat());
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let mut vm_state = VMState::new(&program, [].into(), non_determinism);
+        vm_state.run()?;
+
+        let out_array_ptr = vm_state.op_stack[OpStackElement::ST0];
+        let num_evaluations = rows.len() * num_constraints;
+        let batched = memory_layout::read_xfe_list(&vm_state.ram, out_array_ptr, num_evaluations);
+        prop_assert_eq!(expected, batched.unwrap());
+    }
+
+    #[proptest(cases = 20)]
+    fn evaluated_constraints_are_identical_in_either_coefficient_order(
+        #[strategy(vec(arb(), 4 * NUM_TEST_COLUMNS + Challenges::COUNT))] inputs: Vec<
+            XFieldElement,
+        >,
+    ) {
+        let num_constraints = Constraints::test_constraints().total();
+        let read_evaluated_constraints = |coefficient_order| {
+            let (ram, out_array_ptr) =
+                evaluate_test_constraints_in_triton_vm(coefficient_order, &inputs);
+            let read_in_order = |order| {
+                memory_layout::read_xfe_list_in_order(&ram, out_array_ptr, num_constraints, order)
+                    .unwrap()
+            };
+            (
+                read_in_order(coefficient_order),
+                read_in_order(CoefficientOrder::LittleEndian),
+            )
+        };
+
+        let (little_endian, _) = read_evaluated_constraints(CoefficientOrder::LittleEndian);
+        let (big_endian, big_endian_misread) =
+            read_evaluated_constraints(CoefficientOrder::BigEndian);
+        prop_assert_eq!(&little_endian, &big_endian);
+
+        let reversed = |xfe: &XFieldElement| {
+            let [c0, c1, c2] = xfe.coefficients;
+            XFieldElement::new([c2, c1, c0])
+        };
+        prop_assert_eq!(
+            little_endian.iter().map(reversed).collect_vec(),
+            big_endian_misread
+        );
+    }
 }
@@ -0,0 +1,218 @@
+//! Instruction-level differences between two versions of the Triton assembly emitted by the
+//! [`TasmBackend`](crate::codegen::TasmBackend). When the constraints change, the regenerated
+//! code can be reviewed in terms of the instructions that were added, removed, or changed,
+//! instead of in terms of the lines of the prettified Rust code emitting those instructions.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use triton_vm::instruction::AnInstruction;
+use triton_vm::instruction::LabelledInstruction;
+
+/// A single difference between an old and a new sequence of instructions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum InstructionChange {
+    /// An instruction present in the new sequence only, at the given address of the new
+    /// sequence.
+    Added {
+        address: usize,
+        instruction: AnInstruction<String>,
+    },
+
+    /// An instruction present in the old sequence only, at the given address of the old
+    /// sequence.
+    Removed {
+        address: usize,
+        instruction: AnInstruction<String>,
+    },
+
+    /// An instruction of the old sequence that is replaced by a different one in the new
+    /// sequence.
+    Changed {
+        old_address: usize,
+        new_address: usize,
+        old: AnInstruction<String>,
+        new: AnInstruction<String>,
+    },
+}
+
+/// The differences between an old and a new sequence of instructions.
+///
+/// The instructions both sequences have in common at their start and at their end are
+/// considered unchanged. The remaining instructions are paired up in order and reported as
+/// changed; any excess instructions are reported as removed or added, respectively. This is
+/// not necessarily the smallest possible diff, but regenerating constraint evaluation code
+/// usually leaves most of it untouched, and this approach scales to the tens of thousands of
+/// instructions that code consists of.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct TasmDiff {
+    pub changes: Vec<InstructionChange>,
+}
+
+impl TasmDiff {
+    /// Labels, breakpoints, and type hints are ignored: they are not part of the emitted code.
+    pub fn new(old: &[LabelledInstruction], new: &[LabelledInstruction]) -> Self {
+        let old = Self::addressed_instructions(old);
+        let new = Self::addressed_instructions(new);
+
+        let common_prefix_len = old
+            .iter()
+            .zip(&new)
+            .take_while(|((_, old), (_, new))| old == new)
+            .count();
+        let (_, old) = old.split_at(common_prefix_len);
+        let (_, new) = new.split_at(common_prefix_len);
+
+        let common_suffix_len = old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .take_while(|((_, old), (_, new))| old == new)
+            .count();
+        let old = &old[..old.len() - common_suffix_len];
+        let new = &new[..new.len() - common_suffix_len];
+
+        let num_changed = old.len().min(new.len());
+        let changed = old
+            .iter()
+            .zip(new)
+            .map(
+                |((old_address, old), (new_address, new))| InstructionChange::Changed {
+                    old_address: *old_address,
+                    new_address: *new_address,
+                    old: old.clone(),
+                    new: new.clone(),
+                },
+            );
+        let removed =
+            old[num_changed..]
+                .iter()
+                .map(|(address, instruction)| InstructionChange::Removed {
+                    address: *address,
+                    instruction: instruction.clone(),
+                });
+        let added =
+            new[num_changed..]
+                .iter()
+                .map(|(address, instruction)| InstructionChange::Added {
+                    address: *address,
+                    instruction: instruction.clone(),
+                });
+
+        let changes = changed.chain(removed).chain(added).collect();
+        Self { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn addressed_instructions(
+        instructions: &[LabelledInstruction],
+    ) -> Vec<(usize, AnInstruction<String>)> {
+        let mut address = 0;
+        let mut addressed_instructions = vec![];
+        for labelled_instruction in instructions {
+            if let LabelledInstruction::Instruction(instruction) = labelled_instruction {
+                addressed_instructions.push((address, instruction.clone()));
+                address += instruction.size();
+            }
+        }
+        addressed_instructions
+    }
+}
+
+impl Display for InstructionChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added {
+                address,
+                instruction,
+            } => write!(f, "+ {address}: {instruction}"),
+            Self::Removed {
+                address,
+                instruction,
+            } => write!(f, "- {address}: {instruction}"),
+            Self::Changed {
+                old_address,
+                new_address,
+                old,
+                new,
+            } => write!(f, "~ {old_address} → {new_address}: {old} → {new}"),
+        }
+    }
+}
+
+impl Display for TasmDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::prelude::triton_asm;
+
+    use super::*;
+
+    #[test]
+    fn identical_instructions_have_empty_diff() {
+        let instructions = triton_asm!(push 1 push 2 add pop 1);
+        let diff = TasmDiff::new(&instructions, &instructions);
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn changed_instruction_is_reported_at_its_address() {
+        let old = triton_asm!(push 1 push 2 add pop 1);
+        let new = triton_asm!(push 1 push 2 mul pop 1);
+        let diff = TasmDiff::new(&old, &new);
+
+        let LabelledInstruction::Instruction(add) = old[2].clone() else {
+            panic!("expected an instruction");
+        };
+        let LabelledInstruction::Instruction(mul) = new[2].clone() else {
+            panic!("expected an instruction");
+        };
+        let expected_change = InstructionChange::Changed {
+            old_address: 4,
+            new_address: 4,
+            old: add,
+            new: mul,
+        };
+        assert_eq!(vec![expected_change], diff.changes);
+    }
+
+    #[test]
+    fn added_and_removed_instructions_are_reported_at_their_addresses() {
+        let old = triton_asm!(push 1 pop 1);
+        let new = triton_asm!(push 1 push 2 pop 1 pop 1);
+        let diff = TasmDiff::new(&old, &new);
+        assert_eq!(2, diff.changes.len());
+        assert!(matches!(
+            diff.changes[0],
+            InstructionChange::Added { address: 2, .. }
+        ));
+        assert!(matches!(
+            diff.changes[1],
+            InstructionChange::Added { address: 4, .. }
+        ));
+
+        let diff = TasmDiff::new(&new, &old);
+        assert_eq!(2, diff.changes.len());
+        assert!(matches!(
+            diff.changes[0],
+            InstructionChange::Removed { address: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn labels_do_not_influence_the_diff() {
+        let old = triton_asm!(push 1 pop 1);
+        let new = triton_asm!(label: push 1 pop 1);
+        assert!(TasmDiff::new(&old, &new).is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! The [Rust backend's](super::RustBackend) code evaluating the
+//! [test constraints](crate::constraints::Constraints::test_constraints) without bounds checks
+//! on the input rows. Checked in so that the generated code is type-checked by the compiler and
+//! its evaluations can be compared with those of the constraint circuits.
+//!
+//! Test `unchecked_test_constraints_fixture_is_up_to_date` makes sure this file stays in sync
+//! with the code generator. To update, replace everything below with the output of test
+//! `print_test_constraints_without_bounds_checks`.
+
+use ndarray::ArrayView1;
+use triton_vm::table::challenges::Challenges;
+use triton_vm::table::extension_table::Evaluable;
+use twenty_first::prelude::BFieldElement;
+use twenty_first::prelude::XFieldElement;
+/// Evaluates the AIR constraints like [`MasterExtTable`][table], but accesses the
+/// input rows without bounds checks.
+///
+/// [table]: triton_vm::table::master_table::MasterExtTable
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct UncheckedConstraints;
+impl Evaluable<BFieldElement> for UncheckedConstraints {
+    #[allow(unused_variables)]
+    fn evaluate_initial_constraints(
+        base_row: ArrayView1<BFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        assert!(
+            base_row.len() >= 150usize,
+            "input row is too short; index out of bounds"
+        );
+        unsafe {
+            let base_constraints = [
+                ((*base_row.uget(0usize)) * (*base_row.uget(1usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * (*base_row.uget(2usize))),
+                ((*base_row.uget(2usize)) * (*base_row.uget(149usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((((*base_row.uget(1usize)) * (*base_row.uget(1usize)))
+                            * (*base_row.uget(1usize)))
+                            * (*base_row.uget(1usize)))),
+                (*base_row.uget(149usize))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((((*base_row.uget(0usize)) * (*base_row.uget(0usize)))
+                            * (*base_row.uget(0usize)))
+                            * (*base_row.uget(0usize)))),
+            ];
+            let ext_constraints = [((*base_row.uget(149usize))
+                + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                    * (challenges[35usize])))
+                + (BFieldElement::from_raw_u64(18446744000695107601u64))];
+            base_constraints
+                .into_iter()
+                .map(|bfe| bfe.lift())
+                .chain(ext_constraints)
+                .collect()
+        }
+    }
+    #[allow(unused_variables)]
+    fn evaluate_consistency_constraints(
+        base_row: ArrayView1<BFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        vec![]
+    }
+    #[allow(unused_variables)]
+    fn evaluate_transition_constraints(
+        current_base_row: ArrayView1<BFieldElement>,
+        current_ext_row: ArrayView1<XFieldElement>,
+        next_base_row: ArrayView1<BFieldElement>,
+        next_ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        assert!(
+            current_base_row.len() >= 3usize
+                && current_ext_row.len() >= 2usize
+                && next_base_row.len() >= 3usize
+                && next_ext_row.len() >= 4usize,
+            "input row is too short; index out of bounds"
+        );
+        unsafe {
+            let base_constraints: [BFieldElement; 0] = [];
+            let ext_constraints = [
+                ((*current_base_row.uget(0usize)) * (*next_ext_row.uget(1usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((*next_base_row.uget(1usize)) * (*current_ext_row.uget(0usize)))),
+                ((*current_base_row.uget(1usize)) * (*next_ext_row.uget(2usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((*next_base_row.uget(2usize)) * (*current_ext_row.uget(1usize)))),
+                ((((*current_base_row.uget(2usize)) * (*next_ext_row.uget(0usize)))
+                    * (*next_ext_row.uget(1usize)))
+                    * (*next_ext_row.uget(3usize)))
+                    + (BFieldElement::from_raw_u64(180388626390u64)),
+                ((*current_base_row.uget(0usize)) * (challenges[44usize]))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * (challenges[37usize])),
+            ];
+            base_constraints
+                .into_iter()
+                .map(|bfe| bfe.lift())
+                .chain(ext_constraints)
+                .collect()
+        }
+    }
+    #[allow(unused_variables)]
+    fn evaluate_terminal_constraints(
+        base_row: ArrayView1<BFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        vec![]
+    }
+}
+impl Evaluable<XFieldElement> for UncheckedConstraints {
+    #[allow(unused_variables)]
+    fn evaluate_initial_constraints(
+        base_row: ArrayView1<XFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        assert!(
+            base_row.len() >= 150usize,
+            "input row is too short; index out of bounds"
+        );
+        unsafe {
+            let base_constraints = [
+                ((*base_row.uget(0usize)) * (*base_row.uget(1usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * (*base_row.uget(2usize))),
+                ((*base_row.uget(2usize)) * (*base_row.uget(149usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((((*base_row.uget(1usize)) * (*base_row.uget(1usize)))
+                            * (*base_row.uget(1usize)))
+                            * (*base_row.uget(1usize)))),
+                (*base_row.uget(149usize))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((((*base_row.uget(0usize)) * (*base_row.uget(0usize)))
+                            * (*base_row.uget(0usize)))
+                            * (*base_row.uget(0usize)))),
+            ];
+            let ext_constraints = [((*base_row.uget(149usize))
+                + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                    * (challenges[35usize])))
+                + (BFieldElement::from_raw_u64(18446744000695107601u64))];
+            base_constraints
+                .into_iter()
+                .chain(ext_constraints)
+                .collect()
+        }
+    }
+    #[allow(unused_variables)]
+    fn evaluate_consistency_constraints(
+        base_row: ArrayView1<XFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        vec![]
+    }
+    #[allow(unused_variables)]
+    fn evaluate_transition_constraints(
+        current_base_row: ArrayView1<XFieldElement>,
+        current_ext_row: ArrayView1<XFieldElement>,
+        next_base_row: ArrayView1<XFieldElement>,
+        next_ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        assert!(
+            current_base_row.len() >= 3usize
+                && current_ext_row.len() >= 2usize
+                && next_base_row.len() >= 3usize
+                && next_ext_row.len() >= 4usize,
+            "input row is too short; index out of bounds"
+        );
+        unsafe {
+            let base_constraints = [];
+            let ext_constraints = [
+                ((*current_base_row.uget(0usize)) * (*next_ext_row.uget(1usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((*next_base_row.uget(1usize)) * (*current_ext_row.uget(0usize)))),
+                ((*current_base_row.uget(1usize)) * (*next_ext_row.uget(2usize)))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * ((*next_base_row.uget(2usize)) * (*current_ext_row.uget(1usize)))),
+                ((((*current_base_row.uget(2usize)) * (*next_ext_row.uget(0usize)))
+                    * (*next_ext_row.uget(1usize)))
+                    * (*next_ext_row.uget(3usize)))
+                    + (BFieldElement::from_raw_u64(180388626390u64)),
+                ((*current_base_row.uget(0usize)) * (challenges[44usize]))
+                    + ((BFieldElement::from_raw_u64(18446744065119617026u64))
+                        * (challenges[37usize])),
+            ];
+            base_constraints
+                .into_iter()
+                .chain(ext_constraints)
+                .collect()
+        }
+    }
+    #[allow(unused_variables)]
+    fn evaluate_terminal_constraints(
+        base_row: ArrayView1<XFieldElement>,
+        ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        vec![]
+    }
+}
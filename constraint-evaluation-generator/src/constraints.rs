@@ -1,7 +1,13 @@
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use itertools::izip;
 use itertools::Itertools;
+use serde::Serialize;
 
 use triton_vm::table;
 use triton_vm::table::cascade_table::ExtCascadeTable;
+use triton_vm::table::constraint_circuit::CircuitExpression;
 use triton_vm::table::constraint_circuit::ConstraintCircuit;
 use triton_vm::table::constraint_circuit::ConstraintCircuitBuilder;
 use triton_vm::table::constraint_circuit::ConstraintCircuitMonad;
@@ -19,28 +25,193 @@ use triton_vm::table::processor_table::ExtProcessorTable;
 use triton_vm::table::program_table::ExtProgramTable;
 use triton_vm::table::ram_table::ExtRamTable;
 use triton_vm::table::u32_table::ExtU32Table;
+use triton_vm::table::ConstraintType;
 
 use crate::substitution::AllSubstitutions;
 use crate::substitution::Substitutions;
 
+/// The names of the parts of the AIR that constraints stem from, in the order in which their
+/// constraints appear in [`Constraints::all`]. The names are valid module names.
+const TABLE_NAMES: [&str; 10] = [
+    "program_table",
+    "processor_table",
+    "op_stack_table",
+    "ram_table",
+    "jump_stack_table",
+    "hash_table",
+    "cascade_table",
+    "lookup_table",
+    "u32_table",
+    "cross_table_argument",
+];
+
 pub(crate) struct Constraints {
     pub init: Vec<ConstraintCircuitMonad<SingleRowIndicator>>,
     pub cons: Vec<ConstraintCircuitMonad<SingleRowIndicator>>,
     pub tran: Vec<ConstraintCircuitMonad<DualRowIndicator>>,
     pub term: Vec<ConstraintCircuitMonad<SingleRowIndicator>>,
+
+    /// Which table each of the constraints stems from. Every table's constraints are
+    /// contiguous. Constraints not covered by any table are attributed to the
+    /// [degree lowering table](degree_lowering_table).
+    pub provenance: Vec<TableProvenance>,
+}
+
+/// Whether a constraint is one of the AIR's original constraints or was introduced when
+/// [lowering the degree](Constraints::lower_to_target_degree_through_substitutions) of the
+/// original constraints. See [`Constraints::origins`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) enum ConstraintOrigin {
+    Original,
+
+    /// The constraint is induced by a substitution. Substitution-induced constraints are
+    /// numbered consecutively, in the order in which they appear in the [`Constraints`].
+    SubstitutionInduced {
+        substitution_id: usize,
+    },
+}
+
+impl ConstraintOrigin {
+    /// The ID of the inducing substitution, if any.
+    pub fn substitution_id(self) -> Option<usize> {
+        match self {
+            Self::Original => None,
+            Self::SubstitutionInduced { substitution_id } => Some(substitution_id),
+        }
+    }
+}
+
+/// The number of constraints of each type that stem from one table.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct TableProvenance {
+    /// The name of the table. Usable as a module name.
+    pub table: &'static str,
+    pub num_init: usize,
+    pub num_cons: usize,
+    pub num_tran: usize,
+    pub num_term: usize,
+}
+
+/// A machine-readable summary of [`Constraints`], intended for diffing constraint systems
+/// across commits without parsing generated code. See [`Constraints::manifest`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct ConstraintsManifest {
+    pub init: CategoryManifest,
+    pub cons: CategoryManifest,
+    pub tran: CategoryManifest,
+    pub term: CategoryManifest,
+}
+
+/// Summary of all constraints of one [constraint type](ConstraintType).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct CategoryManifest {
+    pub num_constraints: usize,
+
+    /// The maximal degree of any of the constraints, or 0 if there are no constraints.
+    pub max_degree: isize,
+
+    /// The number of distinct nodes across all of the constraints.
+    pub num_nodes: usize,
+}
+
+impl ConstraintsManifest {
+    pub fn to_json(self) -> String {
+        serde_json::to_string_pretty(&self).unwrap()
+    }
+}
+
+impl CategoryManifest {
+    fn new<II: InputIndicator>(constraints: &[ConstraintCircuitMonad<II>]) -> Self {
+        let max_degree = constraints
+            .iter()
+            .map(|constraint| constraint.consume().degree())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            num_constraints: constraints.len(),
+            max_degree,
+            num_nodes: ConstraintCircuitMonad::num_nodes(constraints),
+        }
+    }
 }
 
 impl Constraints {
     pub fn all() -> Self {
+        let init = Self::initial_constraints();
+        let cons = Self::consistency_constraints();
+        let tran = Self::transition_constraints();
+        let term = Self::terminal_constraints();
+        let provenance = izip!(TABLE_NAMES, &init, &cons, &tran, &term)
+            .map(|(table, init, cons, tran, term)| TableProvenance {
+                table,
+                num_init: init.len(),
+                num_cons: cons.len(),
+                num_tran: tran.len(),
+                num_term: term.len(),
+            })
+            .collect();
+
         Self {
-            init: Self::initial_constraints(),
-            cons: Self::consistency_constraints(),
-            tran: Self::transition_constraints(),
-            term: Self::terminal_constraints(),
+            init: init.concat(),
+            cons: cons.concat(),
+            tran: tran.concat(),
+            term: term.concat(),
+            provenance,
         }
     }
 
-    fn initial_constraints() -> Vec<ConstraintCircuitMonad<SingleRowIndicator>> {
+    /// Split the constraints into the constraints of the individual tables, in order. See also
+    /// [`provenance`](Self::provenance).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provenance accounts for more constraints than there are.
+    pub fn split_by_table(&self) -> Vec<(&'static str, Self)> {
+        let mut tables = vec![];
+        let (mut init, mut cons, mut tran, mut term) = (0, 0, 0, 0);
+        for provenance in &self.provenance {
+            let table = Self {
+                init: self.init[init..init + provenance.num_init].to_vec(),
+                cons: self.cons[cons..cons + provenance.num_cons].to_vec(),
+                tran: self.tran[tran..tran + provenance.num_tran].to_vec(),
+                term: self.term[term..term + provenance.num_term].to_vec(),
+                provenance: vec![provenance.clone()],
+            };
+            tables.push((provenance.table, table));
+            init += provenance.num_init;
+            cons += provenance.num_cons;
+            tran += provenance.num_tran;
+            term += provenance.num_term;
+        }
+
+        let remaining_constraints = Self {
+            init: self.init[init..].to_vec(),
+            cons: self.cons[cons..].to_vec(),
+            tran: self.tran[tran..].to_vec(),
+            term: self.term[term..].to_vec(),
+            provenance: vec![],
+        };
+        if remaining_constraints.total() > 0 {
+            let table = "degree_lowering_table";
+            let provenance = TableProvenance {
+                table,
+                num_init: remaining_constraints.num_init(),
+                num_cons: remaining_constraints.num_cons(),
+                num_tran: remaining_constraints.num_tran(),
+                num_term: remaining_constraints.num_term(),
+            };
+            let remaining_constraints = Self {
+                provenance: vec![provenance],
+                ..remaining_constraints
+            };
+            tables.push((table, remaining_constraints));
+        }
+
+        tables
+    }
+
+    fn initial_constraints() -> Vec<Vec<ConstraintCircuitMonad<SingleRowIndicator>>> {
         let circuit_builder = ConstraintCircuitBuilder::new();
         vec![
             ExtProgramTable::initial_constraints(&circuit_builder),
@@ -54,10 +225,9 @@ impl Constraints {
             ExtU32Table::initial_constraints(&circuit_builder),
             GrandCrossTableArg::initial_constraints(&circuit_builder),
         ]
-        .concat()
     }
 
-    fn consistency_constraints() -> Vec<ConstraintCircuitMonad<SingleRowIndicator>> {
+    fn consistency_constraints() -> Vec<Vec<ConstraintCircuitMonad<SingleRowIndicator>>> {
         let circuit_builder = ConstraintCircuitBuilder::new();
         vec![
             ExtProgramTable::consistency_constraints(&circuit_builder),
@@ -71,10 +241,9 @@ impl Constraints {
             ExtU32Table::consistency_constraints(&circuit_builder),
             GrandCrossTableArg::consistency_constraints(&circuit_builder),
         ]
-        .concat()
     }
 
-    fn transition_constraints() -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+    fn transition_constraints() -> Vec<Vec<ConstraintCircuitMonad<DualRowIndicator>>> {
         let circuit_builder = ConstraintCircuitBuilder::new();
         vec![
             ExtProgramTable::transition_constraints(&circuit_builder),
@@ -88,10 +257,9 @@ impl Constraints {
             ExtU32Table::transition_constraints(&circuit_builder),
             GrandCrossTableArg::transition_constraints(&circuit_builder),
         ]
-        .concat()
     }
 
-    fn terminal_constraints() -> Vec<ConstraintCircuitMonad<SingleRowIndicator>> {
+    fn terminal_constraints() -> Vec<Vec<ConstraintCircuitMonad<SingleRowIndicator>>> {
         let circuit_builder = ConstraintCircuitBuilder::new();
         vec![
             ExtProgramTable::terminal_constraints(&circuit_builder),
@@ -105,10 +273,25 @@ impl Constraints {
             ExtU32Table::terminal_constraints(&circuit_builder),
             GrandCrossTableArg::terminal_constraints(&circuit_builder),
         ]
-        .concat()
     }
 
+    /// Lower the degree of all constraints to the [target degree of Triton VM's
+    /// AIR](master_table::AIR_TARGET_DEGREE). See
+    /// [`lower_to_target_degree_through_substitutions_with`][with].
+    ///
+    /// [with]: Self::lower_to_target_degree_through_substitutions_with
     pub fn lower_to_target_degree_through_substitutions(&mut self) -> AllSubstitutions {
+        self.lower_to_target_degree_through_substitutions_with(master_table::AIR_TARGET_DEGREE)
+    }
+
+    /// Lower the degree of all constraints to the given target degree by introducing new
+    /// columns. The returned substitutions describe the new columns and induce the constraints
+    /// guaranteeing their correctness. A lower target degree generally requires more columns,
+    /// which makes this useful for exploring the trade-off between degree and width.
+    pub fn lower_to_target_degree_through_substitutions_with(
+        &mut self,
+        target_degree: isize,
+    ) -> AllSubstitutions {
         // Subtract the degree lowering table's width from the total number of columns to guarantee
         // the same number of columns even for repeated runs of the constraint evaluation generator.
         let mut num_base_cols = table::NUM_BASE_COLUMNS - degree_lowering_table::BASE_WIDTH;
@@ -116,7 +299,7 @@ impl Constraints {
         let (init_base_substitutions, init_ext_substitutions) =
             ConstraintCircuitMonad::lower_to_degree(
                 &mut self.init,
-                master_table::AIR_TARGET_DEGREE,
+                target_degree,
                 num_base_cols,
                 num_ext_cols,
             );
@@ -126,7 +309,7 @@ impl Constraints {
         let (cons_base_substitutions, cons_ext_substitutions) =
             ConstraintCircuitMonad::lower_to_degree(
                 &mut self.cons,
-                master_table::AIR_TARGET_DEGREE,
+                target_degree,
                 num_base_cols,
                 num_ext_cols,
             );
@@ -136,7 +319,7 @@ impl Constraints {
         let (tran_base_substitutions, tran_ext_substitutions) =
             ConstraintCircuitMonad::lower_to_degree(
                 &mut self.tran,
-                master_table::AIR_TARGET_DEGREE,
+                target_degree,
                 num_base_cols,
                 num_ext_cols,
             );
@@ -146,7 +329,7 @@ impl Constraints {
         let (term_base_substitutions, term_ext_substitutions) =
             ConstraintCircuitMonad::lower_to_degree(
                 &mut self.term,
-                master_table::AIR_TARGET_DEGREE,
+                target_degree,
                 num_base_cols,
                 num_ext_cols,
             );
@@ -164,19 +347,97 @@ impl Constraints {
                 tran: tran_ext_substitutions,
                 term: term_ext_substitutions,
             },
+            target_degree,
         }
     }
 
+    /// How many distinct nodes share the same [reference count](ConstraintCircuit::ref_count),
+    /// keyed by reference count, aggregated across all [constraint types](ConstraintType).
+    /// Nodes with a reference count greater than 1 are shared between several parents and
+    /// are stored in memory by the [TASM backend](crate::codegen::TasmBackend), making this
+    /// histogram useful for tuning the degree-lowering and node-sharing thresholds.
+    ///
+    /// Unlike [`ConstraintCircuit::all_ref_counters`], every node is counted, not only every
+    /// distinct reference count.
+    pub fn ref_count_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        Self::add_ref_counts_to_histogram(&self.init(), &mut histogram);
+        Self::add_ref_counts_to_histogram(&self.cons(), &mut histogram);
+        Self::add_ref_counts_to_histogram(&self.tran(), &mut histogram);
+        Self::add_ref_counts_to_histogram(&self.term(), &mut histogram);
+        histogram
+    }
+
+    fn add_ref_counts_to_histogram<II: InputIndicator>(
+        constraints: &[ConstraintCircuit<II>],
+        histogram: &mut BTreeMap<usize, usize>,
+    ) {
+        fn add_node<II: InputIndicator>(
+            node: &ConstraintCircuit<II>,
+            visited_ids: &mut HashSet<usize>,
+            histogram: &mut BTreeMap<usize, usize>,
+        ) {
+            if !visited_ids.insert(node.id) {
+                return;
+            }
+            *histogram.entry(node.ref_count).or_default() += 1;
+            if let CircuitExpression::BinaryOperation(_, lhs, rhs) = &node.expression {
+                add_node(&lhs.borrow(), visited_ids, histogram);
+                add_node(&rhs.borrow(), visited_ids, histogram);
+            }
+        }
+
+        let mut visited_ids = HashSet::new();
+        for constraint in constraints {
+            add_node(constraint, &mut visited_ids, histogram);
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if any of the resulting constraints has a degree higher than the
+    /// [target degree](AllSubstitutions::target_degree) the substitutions were introduced for.
+    /// The panic message identifies the offending constraint.
     #[must_use]
     pub fn combine_with_substitution_induced_constraints(
         self,
-        AllSubstitutions { base, ext }: AllSubstitutions,
+        AllSubstitutions {
+            base,
+            ext,
+            target_degree,
+        }: AllSubstitutions,
     ) -> Self {
-        Self {
+        let constraints = Self {
             init: [self.init, base.init, ext.init].concat(),
             cons: [self.cons, base.cons, ext.cons].concat(),
             tran: [self.tran, base.tran, ext.tran].concat(),
             term: [self.term, base.term, ext.term].concat(),
+            provenance: self.provenance,
+        };
+        constraints.assert_degrees_are_at_most(target_degree);
+        constraints
+    }
+
+    fn assert_degrees_are_at_most(&self, target_degree: isize) {
+        use ConstraintType as CT;
+        Self::assert_degrees_of_type_are_at_most(CT::Initial, &self.init, target_degree);
+        Self::assert_degrees_of_type_are_at_most(CT::Consistency, &self.cons, target_degree);
+        Self::assert_degrees_of_type_are_at_most(CT::Transition, &self.tran, target_degree);
+        Self::assert_degrees_of_type_are_at_most(CT::Terminal, &self.term, target_degree);
+    }
+
+    fn assert_degrees_of_type_are_at_most<II: InputIndicator>(
+        constraint_type: ConstraintType,
+        constraints: &[ConstraintCircuitMonad<II>],
+        target_degree: isize,
+    ) {
+        for (index, constraint) in constraints.iter().enumerate() {
+            let degree = constraint.consume().degree();
+            assert!(
+                degree <= target_degree,
+                "{constraint_type} constraint {index} has degree {degree}, \
+                exceeding the target degree {target_degree}"
+            );
         }
     }
 
@@ -196,6 +457,79 @@ impl Constraints {
         Self::consume(&self.term)
     }
 
+    /// The number of initial constraints, _i.e._, the number of circuits returned by
+    /// [`init`](Self::init).
+    pub fn num_init(&self) -> usize {
+        self.init.len()
+    }
+
+    /// The number of consistency constraints, _i.e._, the number of circuits returned by
+    /// [`cons`](Self::cons).
+    pub fn num_cons(&self) -> usize {
+        self.cons.len()
+    }
+
+    /// The number of transition constraints, _i.e._, the number of circuits returned by
+    /// [`tran`](Self::tran).
+    pub fn num_tran(&self) -> usize {
+        self.tran.len()
+    }
+
+    /// The number of terminal constraints, _i.e._, the number of circuits returned by
+    /// [`term`](Self::term).
+    pub fn num_term(&self) -> usize {
+        self.term.len()
+    }
+
+    /// The total number of constraints across all [constraint types](ConstraintType).
+    pub fn total(&self) -> usize {
+        self.num_init() + self.num_cons() + self.num_tran() + self.num_term()
+    }
+
+    /// The number of constraints, their maximal degree, and their number of nodes, for
+    /// every [constraint type](ConstraintType).
+    pub fn manifest(&self) -> ConstraintsManifest {
+        ConstraintsManifest {
+            init: CategoryManifest::new(&self.init),
+            cons: CategoryManifest::new(&self.cons),
+            tran: CategoryManifest::new(&self.tran),
+            term: CategoryManifest::new(&self.term),
+        }
+    }
+
+    /// The [origin](ConstraintOrigin) of every constraint, in the order initial, consistency,
+    /// transition, and terminal constraints, which is also the order in which the constraints
+    /// are evaluated. The original constraints are those accounted for by the
+    /// [`provenance`](Self::provenance); all others are
+    /// [substitution-induced](Self::combine_with_substitution_induced_constraints).
+    pub fn origins(&self) -> Vec<ConstraintOrigin> {
+        let num_constraints = [
+            self.num_init(),
+            self.num_cons(),
+            self.num_tran(),
+            self.num_term(),
+        ];
+        let provenance = self.provenance.iter();
+        let num_original_constraints = [
+            provenance.clone().map(|p| p.num_init).sum::<usize>(),
+            provenance.clone().map(|p| p.num_cons).sum(),
+            provenance.clone().map(|p| p.num_tran).sum(),
+            provenance.map(|p| p.num_term).sum(),
+        ];
+
+        let mut substitution_ids = 0..;
+        let mut origins = vec![];
+        for (num_constraints, num_original) in izip!(num_constraints, num_original_constraints) {
+            origins.extend((0..num_constraints).map(|i| match i < num_original {
+                true => ConstraintOrigin::Original,
+                false => ConstraintOrigin::SubstitutionInduced {
+                    substitution_id: substitution_ids.next().unwrap(),
+                },
+            }));
+        }
+        origins
+    }
+
     fn consume<II: InputIndicator>(
         constraints: &[ConstraintCircuitMonad<II>],
     ) -> Vec<ConstraintCircuit<II>> {
@@ -207,11 +541,18 @@ impl Constraints {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use ndarray::Array2;
+    use ndarray::ArrayView2;
     use twenty_first::bfe;
+    use twenty_first::math::other::random_elements;
 
     use triton_vm::prelude::BFieldElement;
+    use triton_vm::prelude::XFieldElement;
     use triton_vm::table::challenges::ChallengeId;
+    use triton_vm::table::challenges::Challenges;
     use triton_vm::table::constraint_circuit::DualRowIndicator;
+    use triton_vm::table::NUM_BASE_COLUMNS;
+    use triton_vm::table::NUM_EXT_COLUMNS;
 
     use super::*;
 
@@ -231,16 +572,34 @@ pub(crate) mod tests {
                 cons: vec![],
                 tran: vec![],
                 term: vec![],
+                provenance: vec![],
             }
         }
 
-        /// For testing purposes only. There is no meaning behind any of the constraints.
+        /// For testing purposes only. There is no meaning behind any of the constraints, nor
+        /// behind their attribution to two made-up tables.
         pub(crate) fn test_constraints() -> Self {
+            let first_test_table = TableProvenance {
+                table: "first_test_table",
+                num_init: 2,
+                num_cons: 0,
+                num_tran: 1,
+                num_term: 0,
+            };
+            let second_test_table = TableProvenance {
+                table: "second_test_table",
+                num_init: 1,
+                num_cons: 0,
+                num_tran: 3,
+                num_term: 0,
+            };
+
             Self {
                 init: Self::small_init_constraints(),
                 cons: vec![],
                 tran: Self::small_transition_constraints(),
                 term: vec![],
+                provenance: vec![first_test_table, second_test_table],
             }
         }
 
@@ -276,5 +635,229 @@ pub(crate) mod tests {
                     - challenge(ChallengeId::StackWeight5),
             ]
         }
+
+        /// Probabilistically check whether `self` and `other` are equivalent by evaluating
+        /// both at `num_samples` random points. For every point and every
+        /// [constraint type](ConstraintType), the evaluations must agree up to the order of the
+        /// constraints. Useful when refactoring constraint definitions.
+        pub(crate) fn equivalent_to(&self, other: &Self, num_samples: usize) -> bool {
+            (0..num_samples).all(|_| {
+                let base_rows = Array2::from_shape_vec(
+                    [2, NUM_BASE_COLUMNS],
+                    random_elements(2 * NUM_BASE_COLUMNS),
+                )
+                .unwrap();
+                let ext_rows = Array2::from_shape_vec(
+                    [2, NUM_EXT_COLUMNS],
+                    random_elements(2 * NUM_EXT_COLUMNS),
+                )
+                .unwrap();
+                let challenges = random_elements(Challenges::COUNT);
+
+                let evaluate = |constraints: &Self| {
+                    constraints.sorted_evaluations(base_rows.view(), ext_rows.view(), &challenges)
+                };
+                evaluate(self) == evaluate(other)
+            })
+        }
+
+        /// The values of all constraints at the given point, sorted within every
+        /// [constraint type](ConstraintType).
+        fn sorted_evaluations(
+            &self,
+            base_rows: ArrayView2<BFieldElement>,
+            ext_rows: ArrayView2<XFieldElement>,
+            challenges: &[XFieldElement],
+        ) -> [Vec<XFieldElement>; 4] {
+            [
+                Self::sorted_evaluations_of_type(&self.init(), base_rows, ext_rows, challenges),
+                Self::sorted_evaluations_of_type(&self.cons(), base_rows, ext_rows, challenges),
+                Self::sorted_evaluations_of_type(&self.tran(), base_rows, ext_rows, challenges),
+                Self::sorted_evaluations_of_type(&self.term(), base_rows, ext_rows, challenges),
+            ]
+        }
+
+        fn sorted_evaluations_of_type<II: InputIndicator>(
+            constraints: &[ConstraintCircuit<II>],
+            base_rows: ArrayView2<BFieldElement>,
+            ext_rows: ArrayView2<XFieldElement>,
+            challenges: &[XFieldElement],
+        ) -> Vec<XFieldElement> {
+            constraints
+                .iter()
+                .map(|c| c.evaluate(base_rows, ext_rows, challenges))
+                .sorted_by_key(|xfe| xfe.coefficients.map(|c| c.value()))
+                .collect()
+        }
+
+        /// The number of distinct nodes across all constraints.
+        pub(crate) fn num_nodes(&self) -> usize {
+            ConstraintCircuitMonad::num_nodes(&self.init)
+                + ConstraintCircuitMonad::num_nodes(&self.cons)
+                + ConstraintCircuitMonad::num_nodes(&self.tran)
+                + ConstraintCircuitMonad::num_nodes(&self.term)
+        }
+    }
+
+    #[test]
+    fn constraint_counts_match_number_of_circuits() {
+        for constraints in [
+            Constraints::mini_constraints(),
+            Constraints::test_constraints(),
+            Constraints::all(),
+        ] {
+            assert_eq!(constraints.init().len(), constraints.num_init());
+            assert_eq!(constraints.cons().len(), constraints.num_cons());
+            assert_eq!(constraints.tran().len(), constraints.num_tran());
+            assert_eq!(constraints.term().len(), constraints.num_term());
+        }
+    }
+
+    #[test]
+    fn total_number_of_test_constraints_is_as_expected() {
+        assert_eq!(1, Constraints::mini_constraints().total());
+        assert_eq!(7, Constraints::test_constraints().total());
+    }
+
+    #[test]
+    fn manifest_of_test_constraints_has_expected_category_counts() {
+        let constraints = Constraints::test_constraints();
+        let manifest = constraints.manifest();
+
+        assert_eq!(3, manifest.init.num_constraints);
+        assert_eq!(0, manifest.cons.num_constraints);
+        assert_eq!(4, manifest.tran.num_constraints);
+        assert_eq!(0, manifest.term.num_constraints);
+
+        assert_eq!(5, manifest.init.max_degree);
+        assert_eq!(0, manifest.cons.max_degree);
+        assert_eq!(4, manifest.tran.max_degree);
+        assert_eq!(0, manifest.term.max_degree);
+
+        let num_nodes = [manifest.init, manifest.cons, manifest.tran, manifest.term]
+            .map(|category| category.num_nodes)
+            .iter()
+            .sum::<usize>();
+        assert_eq!(constraints.num_nodes(), num_nodes);
+    }
+
+    #[test]
+    fn manifest_json_lists_every_category() {
+        let json = Constraints::test_constraints().manifest().to_json();
+        let manifest: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for category in ["init", "cons", "tran", "term"] {
+            for field in ["num_constraints", "max_degree", "num_nodes"] {
+                assert!(manifest[category][field].is_number(), "{category}.{field}");
+            }
+        }
+        assert_eq!(4, manifest["tran"]["num_constraints"]);
+    }
+
+    #[test]
+    fn constraints_are_equivalent_to_themselves() {
+        let constraints = Constraints::test_constraints();
+        assert!(constraints.equivalent_to(&Constraints::test_constraints(), 10));
+    }
+
+    #[test]
+    fn reordered_constraints_are_equivalent() {
+        let constraints = Constraints::test_constraints();
+        let mut reordered_constraints = Constraints::test_constraints();
+        reordered_constraints.init.reverse();
+        reordered_constraints.tran.swap(0, 3);
+        assert!(constraints.equivalent_to(&reordered_constraints, 10));
+    }
+
+    #[test]
+    fn constraints_with_injected_constraint_are_not_equivalent() {
+        let constraints = Constraints::test_constraints();
+        let mut injected_constraints = Constraints::test_constraints();
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let injected_constraint = circuit_builder.input(SingleRowIndicator::BaseRow(3))
+            - circuit_builder.b_constant(bfe!(7));
+        injected_constraints.cons.push(injected_constraint);
+
+        assert!(!constraints.equivalent_to(&injected_constraints, 10));
+        assert!(!injected_constraints.equivalent_to(&constraints, 10));
+    }
+
+    #[test]
+    fn constraints_with_altered_constraint_are_not_equivalent() {
+        let constraints = Constraints::test_constraints();
+        let mut altered_constraints = Constraints::test_constraints();
+        let circuit_builder = altered_constraints.init[0].builder.clone();
+        let input = |i| circuit_builder.input(SingleRowIndicator::BaseRow(i));
+        altered_constraints.init[0] = input(0) * input(1) + input(2);
+        assert!(!constraints.equivalent_to(&altered_constraints, 10));
+    }
+
+    #[test]
+    fn ref_count_histogram_of_mini_constraints_has_expected_shape() {
+        // No node is shared. Subtraction `a - b` is represented as `a + (-1)·b`, totalling
+        // 9 nodes.
+        let histogram = Constraints::mini_constraints().ref_count_histogram();
+        assert_eq!(BTreeMap::from([(1, 9)]), histogram);
+    }
+
+    #[test]
+    fn ref_count_histogram_counts_shared_node_once_with_its_reference_count() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let base_row = |i| circuit_builder.input(SingleRowIndicator::BaseRow(i));
+        let shared_node = base_row(0) * base_row(1);
+
+        let constraints = Constraints {
+            init: vec![shared_node.clone() + base_row(2), shared_node * base_row(3)],
+            cons: vec![],
+            tran: vec![],
+            term: vec![],
+            provenance: vec![],
+        };
+
+        // The four inputs and the two constraints are referenced once, the shared product of
+        // the first two inputs is referenced by both constraints. It is counted only once.
+        let histogram = constraints.ref_count_histogram();
+        assert_eq!(BTreeMap::from([(1, 6), (2, 1)]), histogram);
+    }
+
+    #[test]
+    fn ref_count_histogram_accounts_for_every_node() {
+        let constraints = Constraints::test_constraints();
+        let histogram = constraints.ref_count_histogram();
+        assert_eq!(constraints.num_nodes(), histogram.values().sum::<usize>());
+        assert!(histogram.keys().any(|&ref_count| ref_count > 1));
+    }
+
+    #[test]
+    fn constraints_not_lowered_in_degree_are_all_original() {
+        let constraints = Constraints::test_constraints();
+        let origins = constraints.origins();
+        assert_eq!(constraints.total(), origins.len());
+        assert!(origins.iter().all(|&o| o == ConstraintOrigin::Original));
+    }
+
+    #[test]
+    fn every_substitution_induces_exactly_one_constraint() {
+        for mut constraints in [Constraints::test_constraints(), Constraints::all()] {
+            let num_original_constraints = constraints.total();
+            let substitutions = constraints.lower_to_target_degree_through_substitutions();
+            let num_substitutions = substitutions.base.len() + substitutions.ext.len();
+            assert!(num_substitutions > 0);
+
+            let constraints =
+                constraints.combine_with_substitution_induced_constraints(substitutions);
+            let origins = constraints.origins();
+            let num_original = origins
+                .iter()
+                .filter(|&&o| o == ConstraintOrigin::Original)
+                .count();
+            let substitution_ids = origins
+                .into_iter()
+                .filter_map(ConstraintOrigin::substitution_id)
+                .collect_vec();
+
+            assert_eq!(num_original_constraints, num_original);
+            assert_eq!(num_substitutions, substitution_ids.len());
+            assert_eq!((0..num_substitutions).collect_vec(), substitution_ids);
+        }
     }
 }
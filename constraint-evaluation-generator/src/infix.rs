@@ -0,0 +1,146 @@
+//! Render [constraint circuits](ConstraintCircuit) as human-readable infix expressions, for
+//! example `(curr_base[3] * challenge[7]) + next_ext[1]`. Intended for auditing the AIR.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use triton_vm::table::constraint_circuit::CircuitExpression;
+use triton_vm::table::constraint_circuit::ConstraintCircuit;
+use triton_vm::table::constraint_circuit::InputIndicator;
+
+/// The given circuit as an infix expression.
+///
+/// Inputs are labelled by the row and table they stem from, followed by the column, like
+/// `curr_base[3]` or `next_ext[1]`. Inputs of single-row constraints are labelled as stemming
+/// from the current row. Challenges are labelled by their index, like `challenge[7]`.
+///
+/// A circuit is a directed acyclic graph, and a node can be referenced by multiple other
+/// nodes. Such a shared node is rendered in full only on its first encounter, prefixed by its
+/// id, like `#5:(curr_base[0] * curr_base[1])`. Every further encounter only renders the id,
+/// like `#5`.
+pub(crate) fn to_infix_string<II: InputIndicator>(circuit: &ConstraintCircuit<II>) -> String {
+    let mut num_references = HashMap::new();
+    count_references(circuit, &mut num_references);
+
+    let shared_ids = num_references
+        .into_iter()
+        .filter(|&(_, count)| count > 1)
+        .map(|(id, _)| id)
+        .collect();
+    let mut rendered_ids = HashSet::new();
+    render(circuit, &shared_ids, &mut rendered_ids)
+}
+
+/// Count how often each binary operation is referenced. The operands of a node are only
+/// counted once, no matter how often the node itself is referenced.
+fn count_references<II: InputIndicator>(
+    node: &ConstraintCircuit<II>,
+    num_references: &mut HashMap<usize, usize>,
+) {
+    let CircuitExpression::BinaryOperation(_, lhs, rhs) = &node.expression else {
+        return;
+    };
+    let count = num_references.entry(node.id).or_insert(0);
+    *count += 1;
+    if *count > 1 {
+        return;
+    }
+    count_references(&lhs.borrow(), num_references);
+    count_references(&rhs.borrow(), num_references);
+}
+
+fn render<II: InputIndicator>(
+    node: &ConstraintCircuit<II>,
+    shared_ids: &HashSet<usize>,
+    rendered_ids: &mut HashSet<usize>,
+) -> String {
+    let (op, lhs, rhs) = match &node.expression {
+        CircuitExpression::BConstant(bfe) => return bfe.to_string(),
+        CircuitExpression::XConstant(xfe) => match xfe.unlift() {
+            Some(bfe) => return bfe.to_string(),
+            None => return xfe.to_string(),
+        },
+        CircuitExpression::Input(input) => return render_input(*input),
+        CircuitExpression::Challenge(index) => return format!("challenge[{index}]"),
+        CircuitExpression::BinaryOperation(op, lhs, rhs) => (op, lhs, rhs),
+    };
+
+    let is_shared = shared_ids.contains(&node.id);
+    if is_shared && !rendered_ids.insert(node.id) {
+        return format!("#{}", node.id);
+    }
+
+    let render_operand = |operand: &ConstraintCircuit<II>, rendered_ids: &mut _| {
+        // shared nodes are delimited by their id already
+        let rendered = render(operand, shared_ids, rendered_ids);
+        match operand.expression {
+            CircuitExpression::BinaryOperation(..) if !shared_ids.contains(&operand.id) => {
+                format!("({rendered})")
+            }
+            _ => rendered,
+        }
+    };
+    let lhs = render_operand(&lhs.borrow(), rendered_ids);
+    let rhs = render_operand(&rhs.borrow(), rendered_ids);
+    let expression = format!("{lhs} {op} {rhs}");
+
+    match is_shared {
+        true => format!("#{}:({expression})", node.id),
+        false => expression,
+    }
+}
+
+fn render_input<II: InputIndicator>(input: II) -> String {
+    let row = if input.is_current_row() {
+        "curr"
+    } else {
+        "next"
+    };
+    let table = if input.is_base_table_column() {
+        "base"
+    } else {
+        "ext"
+    };
+    format!("{row}_{table}[{}]", input.column())
+}
+
+#[cfg(test)]
+mod tests {
+    use triton_vm::table::challenges::ChallengeId;
+    use triton_vm::table::constraint_circuit::ConstraintCircuitBuilder;
+    use triton_vm::table::constraint_circuit::DualRowIndicator;
+
+    use crate::constraints::Constraints;
+
+    use super::*;
+
+    #[test]
+    fn infix_string_of_mini_constraints_matches_snapshot() {
+        let constraints = Constraints::mini_constraints();
+        let infix_strings = constraints
+            .init()
+            .iter()
+            .map(to_infix_string)
+            .collect::<Vec<_>>();
+
+        let challenge_index = ChallengeId::StackWeight5.index();
+        let expected =
+            format!("(curr_base[0] * challenge[{challenge_index}]) + (-1 * (curr_ext[1] * 42))");
+        assert_eq!(vec![expected], infix_strings);
+    }
+
+    #[test]
+    fn shared_node_is_rendered_in_full_only_once() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let curr_base_row = |i| circuit_builder.input(DualRowIndicator::CurrentBaseRow(i));
+        let next_ext_row = |i| circuit_builder.input(DualRowIndicator::NextExtRow(i));
+
+        let shared = curr_base_row(3) * next_ext_row(1);
+        let shared_id = shared.clone().consume().id;
+        let constraint = shared.clone() * shared + curr_base_row(0);
+
+        let expected =
+            format!("(#{shared_id}:(curr_base[3] * next_ext[1]) * #{shared_id}) + curr_base[0]");
+        assert_eq!(expected, to_infix_string(&constraint.consume()));
+    }
+}
@@ -12,21 +12,48 @@
 //!
 //! The constraint generator can be run by executing
 //! `cargo run --bin constraint-evaluation-generator`
-//! in the root of the repository.
+//! in the root of the repository. Passing the flag `--elide-bounds-checks` makes the
+//! generated Rust code access its input rows without bounds checks, guarded by one
+//! up-front length assertion per evaluating function. Passing the flag `--split-by-table`
+//! emits the Rust code as one module per table plus a dispatcher, which allows the compiler to
+//! process the tables in parallel. Passing the flag `--big-endian-coefficients` makes the
+//! generated Triton assembly write the coefficients of every evaluated constraint in
+//! big-endian order; Triton VM's own tests expect the default, little-endian order.
+//! Passing the flag `--ref-count-histogram` prints how many nodes of the final constraints
+//! share each reference count, which helps tuning the degree-lowering threshold.
+//! Passing the flag `--infix` prints every constraint as a human-readable infix expression,
+//! which helps auditing the AIR.
+//! Passing the flag `--generic-field` additionally writes Rust code that evaluates the
+//! constraints generically over any field `FF: FiniteField + From<BFieldElement>` to
+//! `triton-vm/src/table/generic_constraints.rs`. That file is not part of Triton VM's module
+//! tree; it can be `include!`d by crates wanting to evaluate the constraints in other fields.
+//!
+//! Additionally, a machine-readable summary of the constraints, listing the number of
+//! constraints, their maximal degree, and their number of nodes for every constraint type, is
+//! written to `constraints_manifest.json`. This allows comparing constraint systems across
+//! commits without parsing the generated code.
 
 #![warn(missing_debug_implementations)]
 #![warn(missing_docs)]
 
 use proc_macro2::TokenStream;
+use std::env;
+use std::fs::create_dir_all;
 use std::fs::write;
+use triton_vm::air::memory_layout;
+use triton_vm::air::memory_layout::CoefficientOrder;
+use triton_vm::table::constraint_circuit::ConstraintCircuit;
+use triton_vm::table::constraint_circuit::InputIndicator;
 
 use crate::codegen::Codegen;
 use crate::codegen::RustBackend;
 use crate::codegen::TasmBackend;
+use crate::constraints::ConstraintOrigin;
 use crate::constraints::Constraints;
 
 mod codegen;
 mod constraints;
+mod infix;
 mod substitution;
 
 fn main() {
@@ -35,17 +62,90 @@ fn main() {
     let degree_lowering_table_code = substitutions.generate_degree_lowering_table_code();
 
     let constraints = constraints.combine_with_substitution_induced_constraints(substitutions);
-    let rust = RustBackend::constraint_evaluation_code(&constraints);
-    let tasm = TasmBackend::constraint_evaluation_code(&constraints);
+    let num_substitution_induced_constraints = constraints
+        .origins()
+        .into_iter()
+        .filter_map(ConstraintOrigin::substitution_id)
+        .count();
+    println!(
+        "{} constraints, {num_substitution_induced_constraints} of which are induced by \
+        degree-lowering substitutions",
+        constraints.total(),
+    );
+    if env::args().any(|arg| arg == "--ref-count-histogram") {
+        for (ref_count, num_nodes) in constraints.ref_count_histogram() {
+            println!("{num_nodes} nodes with reference count {ref_count}");
+        }
+    }
+
+    if env::args().any(|arg| arg == "--infix") {
+        print_infix("initial", &constraints.init());
+        print_infix("consistency", &constraints.cons());
+        print_infix("transition", &constraints.tran());
+        print_infix("terminal", &constraints.term());
+    }
+
+    let manifest = constraints.manifest().to_json();
+    write("constraints_manifest.json", manifest).unwrap();
+
+    let elide_bounds_checks = env::args().any(|arg| arg == "--elide-bounds-checks");
+    let split_by_table = env::args().any(|arg| arg == "--split-by-table");
+    let coefficient_order = match env::args().any(|arg| arg == "--big-endian-coefficients") {
+        true => CoefficientOrder::BigEndian,
+        false => CoefficientOrder::LittleEndian,
+    };
+    let tasm = TasmBackend::constraint_evaluation_code_with_coefficient_order(
+        &constraints,
+        coefficient_order,
+    );
+    let required_free_mem_words = TasmBackend::required_free_mem_words(&constraints);
+    assert!(
+        required_free_mem_words <= memory_layout::MEM_PAGE_SIZE,
+        "constraint evaluation in TASM requires {required_free_mem_words} words of free memory",
+    );
+    let shared_nodes_end = TasmBackend::required_shared_nodes_mem_words(&constraints);
+    assert!(
+        shared_nodes_end <= memory_layout::BATCHED_OUT_ARRAY_OFFSET,
+        "shared nodes of constraint evaluation in TASM overlap the batched output array",
+    );
 
     write_code_to_file(
         degree_lowering_table_code,
         "triton-vm/src/table/degree_lowering_table.rs",
     );
-    write_code_to_file(rust, "triton-vm/src/table/constraints.rs");
+    if split_by_table {
+        let rust = RustBackend::constraint_evaluation_code_split_by_table(
+            &constraints,
+            elide_bounds_checks,
+        );
+        create_dir_all("triton-vm/src/table/constraints").unwrap();
+        for (table, code) in rust.tables {
+            write_code_to_file(code, &format!("triton-vm/src/table/constraints/{table}.rs"));
+        }
+        write_code_to_file(rust.dispatcher, "triton-vm/src/table/constraints.rs");
+    } else {
+        let rust = match elide_bounds_checks {
+            true => RustBackend::constraint_evaluation_code_with_elided_bounds_checks(&constraints),
+            false => RustBackend::constraint_evaluation_code(&constraints),
+        };
+        write_code_to_file(rust, "triton-vm/src/table/constraints.rs");
+    }
+    if env::args().any(|arg| arg == "--generic-field") {
+        let generic = RustBackend::constraint_evaluation_code_generic_over_field(&constraints);
+        write_code_to_file(generic, "triton-vm/src/table/generic_constraints.rs");
+    }
     write_code_to_file(tasm, "triton-vm/src/air/tasm_air_constraints.rs");
 }
 
+fn print_infix<II: InputIndicator>(constraint_type: &str, constraints: &[ConstraintCircuit<II>]) {
+    for (i, constraint) in constraints.iter().enumerate() {
+        println!(
+            "{constraint_type} constraint {i}: {}",
+            infix::to_infix_string(constraint)
+        );
+    }
+}
+
 fn write_code_to_file(code: TokenStream, file_name: &str) {
     let syntax_tree = syn::parse2(code).unwrap();
     let code = prettyplease::unparse(&syntax_tree);
@@ -54,6 +154,10 @@ fn write_code_to_file(code: TokenStream, file_name: &str) {
 
 #[cfg(test)]
 mod tests {
+    use triton_vm::table::constraint_circuit::ConstraintCircuitBuilder;
+    use triton_vm::table::constraint_circuit::DualRowIndicator;
+    use triton_vm::table::master_table;
+
     use super::*;
 
     #[test]
@@ -80,10 +184,64 @@ mod tests {
         let _ = substitutions.generate_degree_lowering_table_code();
     }
 
+    #[test]
+    fn degree_lowering_table_code_is_reproducible() {
+        let generate_code = || {
+            let mut constraints = Constraints::all();
+            let substitutions = constraints.lower_to_target_degree_through_substitutions();
+            substitutions
+                .generate_degree_lowering_table_code()
+                .to_string()
+        };
+        assert_eq!(generate_code(), generate_code());
+    }
+
     #[test]
     fn constraints_and_substitutions_can_be_combined() {
         let mut constraints = Constraints::test_constraints();
         let substitutions = constraints.lower_to_target_degree_through_substitutions();
         let _ = constraints.combine_with_substitution_induced_constraints(substitutions);
     }
+
+    #[test]
+    fn lowering_to_smaller_target_degree_requires_more_substitutions() {
+        let num_substitutions = |target_degree| {
+            let mut constraints = Constraints::test_constraints();
+            let substitutions =
+                constraints.lower_to_target_degree_through_substitutions_with(target_degree);
+            substitutions.base.len() + substitutions.ext.len()
+        };
+        assert!(num_substitutions(2) > num_substitutions(4));
+    }
+
+    #[test]
+    fn constraints_lowered_to_any_target_degree_can_be_combined_with_substitutions() {
+        let target_degree_above_air_target_degree = master_table::AIR_TARGET_DEGREE + 1;
+        for target_degree in [2, 4, target_degree_above_air_target_degree] {
+            let mut constraints = Constraints::test_constraints();
+            let substitutions =
+                constraints.lower_to_target_degree_through_substitutions_with(target_degree);
+            let constraints =
+                constraints.combine_with_substitution_induced_constraints(substitutions);
+            let manifest = constraints.manifest();
+            let categories = [manifest.init, manifest.cons, manifest.tran, manifest.term];
+            for category in categories {
+                assert!(category.max_degree <= target_degree);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "transition constraint 4 has degree 5")]
+    fn combining_constraints_with_over_degree_constraint_fails() {
+        let mut constraints = Constraints::test_constraints();
+        let substitutions = constraints.lower_to_target_degree_through_substitutions();
+
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let input = circuit_builder.input(DualRowIndicator::CurrentBaseRow(0));
+        let over_degree_constraint = (0..4).fold(input.clone(), |acc, _| acc * input.clone());
+        constraints.tran.push(over_degree_constraint);
+
+        let _ = constraints.combine_with_substitution_induced_constraints(substitutions);
+    }
 }
@@ -18,6 +18,9 @@ use crate::codegen::RustBackend;
 pub(crate) struct AllSubstitutions {
     pub base: Substitutions,
     pub ext: Substitutions,
+
+    /// The degree the constraints were lowered to when introducing these substitutions.
+    pub target_degree: isize,
 }
 
 pub(crate) struct Substitutions {
@@ -110,7 +113,7 @@ impl AllSubstitutions {
 }
 
 impl Substitutions {
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.init.len() + self.cons.len() + self.tran.len() + self.term.len()
     }
 
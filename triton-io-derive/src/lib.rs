@@ -0,0 +1,83 @@
+//! Derive macro for the trait `TritonIO` of [Triton VM](https://triton-vm.org), which packs a
+//! struct into the public input or output of a program, and unpacks it from there.
+//!
+//! The macro is re-exported by Triton VM; use it from there, not from this crate.
+
+#![warn(missing_debug_implementations)]
+#![warn(missing_docs)]
+
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+
+/// Derive `triton_vm::proof::TritonIO` for a struct all fields of which implement
+/// `BFieldCodec`.
+///
+/// The fields are encoded in the order of their declaration, which allows a program to
+/// `read_io` them one after the other. A field of statically known length is encoded as is; any
+/// other field is prefixed with the length of its encoding.
+#[proc_macro_derive(TritonIO)]
+pub fn derive_triton_io(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    triton_io_implementation(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn triton_io_implementation(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        let error_message = "`TritonIO` can only be derived for structs";
+        return Err(syn::Error::new_spanned(&input.ident, error_message));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let members = data.fields.members().collect::<Vec<_>>();
+    let types = data
+        .fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+    let member_names = members
+        .iter()
+        .map(|member| quote!(#member).to_string())
+        .collect::<Vec<_>>();
+    let bindings = (0..members.len())
+        .map(|i| format_ident!("field_{i}"))
+        .collect::<Vec<_>>();
+
+    let construct = match &data.fields {
+        Fields::Named(_) => quote!(Self { #(#members: #bindings),* }),
+        Fields::Unnamed(_) => quote!(Self(#(#bindings),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    Ok(quote!(
+        impl #impl_generics ::triton_vm::proof::TritonIO for #name #type_generics #where_clause {
+            fn encode_io(&self) -> ::std::vec::Vec<::triton_vm::prelude::BFieldElement> {
+                let mut sequence = ::std::vec::Vec::new();
+                #(::triton_vm::proof::encode_io_field(&self.#members, &mut sequence);)*
+                sequence
+            }
+
+            fn decode_io_prefix(
+                sequence: &[::triton_vm::prelude::BFieldElement],
+            ) -> ::std::result::Result<
+                (Self, &[::triton_vm::prelude::BFieldElement]),
+                ::triton_vm::error::TritonIOError,
+            > {
+                #(
+                    let (#bindings, sequence) = ::triton_vm::proof::decode_io_field::<#types>(
+                        sequence,
+                        #member_names,
+                    )?;
+                )*
+                ::std::result::Result::Ok((#construct, sequence))
+            }
+        }
+    ))
+}
@@ -0,0 +1,46 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+
+use triton_vm::example_programs::FIBONACCI_SEQUENCE;
+use triton_vm::prelude::*;
+
+/// The indices of the Fibonacci numbers to compute. Larger indices result in longer traces,
+/// and thus larger padded heights.
+const FIBONACCI_INDICES: [u64; 5] = [40, 80, 160, 320, 640];
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = verify_padded_heights
+}
+
+/// cargo criterion --bench verify_padded_heights
+fn verify_padded_heights(c: &mut Criterion) {
+    let stark = Stark::default();
+    let mut group = c.benchmark_group("verify_padded_heights");
+    for (claim, proof) in FIBONACCI_INDICES.map(|index| prove_fibonacci(&stark, index)) {
+        let padded_height = proof.padded_height().unwrap();
+        let bench_id = BenchmarkId::from_parameter(padded_height);
+        group.bench_with_input(bench_id, &(claim, proof), |b, (claim, proof)| {
+            b.iter(|| stark.verify(claim, proof).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn prove_fibonacci(stark: &Stark, index: u64) -> (Claim, Proof) {
+    let program = FIBONACCI_SEQUENCE.clone();
+    let public_input = PublicInput::new(bfe_vec![index]);
+    let (aet, output) = program
+        .trace_execution(public_input.clone(), NonDeterminism::default())
+        .unwrap();
+
+    let claim = Claim::about_program(&program)
+        .with_input(public_input.individual_tokens)
+        .with_output(output);
+    let proof = stark.prove(&claim, &aet).unwrap();
+    (claim, proof)
+}
@@ -0,0 +1,29 @@
+//! Feeds arbitrary bytes to the verifier. Verification of garbage must fail gracefully, _i.e._,
+//! by returning an error, never by panicking.
+//!
+//! Run with `cargo +nightly fuzz run verify_arbitrary_proof` from within the `triton-vm`
+//! directory.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use triton_vm::prelude::*;
+use triton_vm::proof_stream::ProofStream;
+
+fuzz_target!(|data: &[u8]| {
+    let proof_data = data
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .map(BFieldElement::new)
+        .collect::<Vec<_>>();
+    let Ok(proof) = Proof::decode(&proof_data) else {
+        return;
+    };
+    let Ok(_) = ProofStream::try_from(&*proof) else {
+        return;
+    };
+
+    let claim = Claim::new(Digest::default());
+    let verdict = Stark::default().verify(&claim, &proof);
+    assert!(verdict.is_err());
+});
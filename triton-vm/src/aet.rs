@@ -11,6 +11,7 @@ use ndarray::Axis;
 use strum::IntoEnumIterator;
 use twenty_first::prelude::*;
 
+use crate::error::AlgebraicExecutionTraceDecodingError;
 use crate::error::InstructionError;
 use crate::error::InstructionError::InstructionPointerOverflow;
 use crate::instruction::Instruction;
@@ -336,6 +337,125 @@ impl AlgebraicExecutionTrace {
     }
 }
 
+/// The [`BFieldCodec`]-friendly representation of an [`AlgebraicExecutionTrace`]. Traces are
+/// flattened in row-major order, maps are sorted by their keys to make the encoding
+/// deterministic.
+#[derive(Debug, Clone, BFieldCodec)]
+struct EncodableAlgebraicExecutionTrace {
+    program: Program,
+    instruction_multiplicities: Vec<u32>,
+    processor_trace: Vec<BFieldElement>,
+    op_stack_underflow_trace: Vec<BFieldElement>,
+    ram_trace: Vec<BFieldElement>,
+    program_hash_trace: Vec<BFieldElement>,
+    hash_trace: Vec<BFieldElement>,
+    sponge_trace: Vec<BFieldElement>,
+    u32_entries: Vec<([BFieldElement; 3], u64)>,
+    cascade_table_lookup_multiplicities: Vec<(u16, u64)>,
+    lookup_table_lookup_multiplicities: [u64; AlgebraicExecutionTrace::LOOKUP_TABLE_HEIGHT],
+}
+
+impl From<&AlgebraicExecutionTrace> for EncodableAlgebraicExecutionTrace {
+    fn from(aet: &AlgebraicExecutionTrace) -> Self {
+        let flatten = |trace: &Array2<BFieldElement>| trace.iter().copied().collect_vec();
+        let u32_entries = aet
+            .u32_entries
+            .iter()
+            .map(|(entry, &multiplicity)| {
+                let instruction = entry.instruction.opcode_b();
+                let entry = [instruction, entry.left_operand, entry.right_operand];
+                (entry, multiplicity)
+            })
+            .sorted_by_key(|(entry, _)| entry.map(|bfe| bfe.value()))
+            .collect();
+        let cascade_table_lookup_multiplicities = aet
+            .cascade_table_lookup_multiplicities
+            .iter()
+            .map(|(&limb, &multiplicity)| (limb, multiplicity))
+            .sorted()
+            .collect();
+
+        Self {
+            program: aet.program.clone(),
+            instruction_multiplicities: aet.instruction_multiplicities.clone(),
+            processor_trace: flatten(&aet.processor_trace),
+            op_stack_underflow_trace: flatten(&aet.op_stack_underflow_trace),
+            ram_trace: flatten(&aet.ram_trace),
+            program_hash_trace: flatten(&aet.program_hash_trace),
+            hash_trace: flatten(&aet.hash_trace),
+            sponge_trace: flatten(&aet.sponge_trace),
+            u32_entries,
+            cascade_table_lookup_multiplicities,
+            lookup_table_lookup_multiplicities: aet.lookup_table_lookup_multiplicities,
+        }
+    }
+}
+
+impl TryFrom<EncodableAlgebraicExecutionTrace> for AlgebraicExecutionTrace {
+    type Error = AlgebraicExecutionTraceDecodingError;
+
+    fn try_from(encodable: EncodableAlgebraicExecutionTrace) -> Result<Self, Self::Error> {
+        let unflatten = |trace: Vec<BFieldElement>, width: usize| {
+            let num_elements = trace.len();
+            let shape_mismatch =
+                || AlgebraicExecutionTraceDecodingError::TraceShapeMismatch(num_elements, width);
+            if !num_elements.is_multiple_of(width) {
+                return Err(shape_mismatch());
+            }
+            Array2::from_shape_vec((num_elements / width, width), trace)
+                .map_err(|_| shape_mismatch())
+        };
+
+        let mut u32_entries = HashMap::new();
+        for ([instruction, left_operand, right_operand], multiplicity) in encodable.u32_entries {
+            let instruction = Instruction::try_from(instruction)?;
+            let entry = U32TableEntry::new(instruction, left_operand, right_operand);
+            u32_entries.insert(entry, multiplicity);
+        }
+
+        Ok(Self {
+            program: encodable.program,
+            instruction_multiplicities: encodable.instruction_multiplicities,
+            processor_trace: unflatten(encodable.processor_trace, processor_table::BASE_WIDTH)?,
+            op_stack_underflow_trace: unflatten(
+                encodable.op_stack_underflow_trace,
+                op_stack_table::BASE_WIDTH,
+            )?,
+            ram_trace: unflatten(encodable.ram_trace, ram_table::BASE_WIDTH)?,
+            program_hash_trace: unflatten(encodable.program_hash_trace, hash_table::BASE_WIDTH)?,
+            hash_trace: unflatten(encodable.hash_trace, hash_table::BASE_WIDTH)?,
+            sponge_trace: unflatten(encodable.sponge_trace, hash_table::BASE_WIDTH)?,
+            u32_entries,
+            cascade_table_lookup_multiplicities: encodable
+                .cascade_table_lookup_multiplicities
+                .into_iter()
+                .collect(),
+            lookup_table_lookup_multiplicities: encodable.lookup_table_lookup_multiplicities,
+        })
+    }
+}
+
+/// Allows shipping an [`AlgebraicExecutionTrace`] to a different machine, for example, to
+/// separate execution of a program from proof generation. See also
+/// [`Stark::prove_from_trace`](crate::stark::Stark::prove_from_trace).
+impl BFieldCodec for AlgebraicExecutionTrace {
+    type Error = AlgebraicExecutionTraceDecodingError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let encodable = *EncodableAlgebraicExecutionTrace::decode(sequence)
+            .map_err(|err| AlgebraicExecutionTraceDecodingError::DecodingError(err.into()))?;
+        Ok(Box::new(encodable.try_into()?))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        EncodableAlgebraicExecutionTrace::from(self).encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        None
+    }
+}
+
 impl TableHeight {
     fn new(table: TableId, height: usize) -> Self {
         Self { table, height }
@@ -357,6 +477,7 @@ impl Ord for TableHeight {
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
 
     use crate::prelude::*;
 
@@ -372,6 +493,39 @@ mod tests {
         assert!(expected == padded_program);
     }
 
+    #[test]
+    fn encoding_and_decoding_trace_is_identity() {
+        let program =
+            triton_program!(read_io 2 lt push 7 split sponge_init sponge_squeeze write_io 5 halt);
+        let public_input = PublicInput::new(bfe_vec![3, 5]);
+        let (aet, _) = program
+            .trace_execution(public_input, NonDeterminism::default())
+            .unwrap();
+        assert!(!aet.u32_entries.is_empty());
+
+        let encoding = aet.encode();
+        let decoded_aet = *AlgebraicExecutionTrace::decode(&encoding).unwrap();
+        assert!(aet.program == decoded_aet.program);
+        assert!(aet.processor_trace == decoded_aet.processor_trace);
+        assert!(aet.sponge_trace == decoded_aet.sponge_trace);
+        assert!(aet.u32_entries == decoded_aet.u32_entries);
+        assert!(encoding == decoded_aet.encode());
+    }
+
+    #[test]
+    fn decoding_trace_with_malformed_table_fails() {
+        let program = triton_program!(halt);
+        let (aet, _) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+        let mut encodable = EncodableAlgebraicExecutionTrace::from(&aet);
+        encodable.ram_trace.push(bfe!(0));
+
+        let_assert!(Err(err) = AlgebraicExecutionTrace::decode(&encodable.encode()));
+        let_assert!(AlgebraicExecutionTraceDecodingError::TraceShapeMismatch(_, width) = err);
+        assert!(ram_table::BASE_WIDTH == width);
+    }
+
     #[test]
     fn height_of_any_table_can_be_computed() {
         let program = triton_program!(halt);
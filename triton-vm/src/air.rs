@@ -1,7 +1,135 @@
+use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
+use twenty_first::prelude::*;
+
+use crate::air::memory_layout::StaticTasmConstraintEvaluationMemoryLayout;
+use crate::instruction::LabelledInstruction;
+use crate::table::NUM_BASE_COLUMNS;
+use crate::table::NUM_EXT_COLUMNS;
+use crate::triton_asm;
+
 pub mod memory_layout;
 #[rustfmt::skip]
 pub mod tasm_air_constraints;
 
+/// Wrap the [Triton assembly][tasm] evaluating the AIR constraints of a single row with
+/// [dynamically known input locations][dyn] into a subroutine evaluating them for `num_rows`
+/// consecutive rows. The `single_row_evaluation` must evaluate `num_constraints` constraints.
+///
+/// Row `i` of each of the input lists starts at the respective pointer of the memory layout,
+/// offset by `i` times the length of that list's rows. The evaluations are written to the
+/// [batched output array](memory_layout::BATCHED_OUT_ARRAY_OFFSET), one row after the other.
+///
+/// See [`static_air_constraint_evaluation_tasm_batched`][batched] for the signature and the
+/// guarantees of the emitted code.
+///
+/// # Panics
+///
+/// Panics if the evaluations of all rows do not fit into the
+/// [batched output array](memory_layout::MAX_BATCHED_OUT_ARRAY_LEN).
+///
+/// [tasm]: crate::prelude::triton_asm
+/// [dyn]: tasm_air_constraints::dynamic_air_constraint_evaluation_tasm
+/// [batched]: tasm_air_constraints::static_air_constraint_evaluation_tasm_batched
+pub fn batch_air_constraint_evaluation_tasm(
+    mem_layout: StaticTasmConstraintEvaluationMemoryLayout,
+    num_rows: usize,
+    num_constraints: usize,
+    single_row_evaluation: Vec<LabelledInstruction>,
+) -> Vec<LabelledInstruction> {
+    let num_words_per_row = num_constraints * EXTENSION_DEGREE;
+    let num_words = num_rows * num_words_per_row;
+    assert!(
+        num_words <= memory_layout::MAX_BATCHED_OUT_ARRAY_LEN,
+        "evaluating {num_rows} rows requires {num_words} words for the output array",
+    );
+
+    let out_array_offset = bfe!(memory_layout::BATCHED_OUT_ARRAY_OFFSET as u64);
+    let out_array_ptr = mem_layout.free_mem_page_ptr + out_array_offset;
+    let out_array_end = out_array_ptr + bfe!(num_words as u64);
+    let curr_base_row_ptr = mem_layout.curr_base_row_ptr;
+    let curr_ext_row_ptr = mem_layout.curr_ext_row_ptr;
+    let next_base_row_ptr = mem_layout.next_base_row_ptr;
+    let next_ext_row_ptr = mem_layout.next_ext_row_ptr;
+    let base_row_len = NUM_BASE_COLUMNS * EXTENSION_DEGREE;
+    let ext_row_len = NUM_EXT_COLUMNS * EXTENSION_DEGREE;
+
+    let set_up_and_call_loop = triton_asm!(
+        tasm_air_constraints_batched:
+            // _
+            push {out_array_end}
+            push {out_array_ptr}
+            push {curr_base_row_ptr}
+            push {curr_ext_row_ptr}
+            push {next_base_row_ptr}
+            push {next_ext_row_ptr}
+            // _ *out_end *out *curr_base *curr_ext *next_base *next_ext
+            call tasm_air_constraints_batched_loop
+            pop 5
+            pop 1
+            push {out_array_ptr}
+            // _ *evaluated_constraints
+            return
+
+        // INVARIANT: _ *out_end *out *curr_base *curr_ext *next_base *next_ext
+        tasm_air_constraints_batched_loop:
+            dup 5
+            dup 5
+            eq
+            skiz
+                return
+            dup 3
+            dup 3
+            dup 3
+            dup 3
+    );
+
+    // _ *out_end *out *curr_base *curr_ext *next_base *next_ext *evaluated
+    let copy_evaluations_to_out_array = (0..num_words_per_row)
+        .step_by(5)
+        .flat_map(|offset| {
+            let num_words = (num_words_per_row - offset).min(5);
+            let last_word_offset = offset + num_words - 1;
+            let out_ptr_index = 5 + num_words;
+            triton_asm!(
+                dup 0
+                addi {last_word_offset}
+                read_mem {num_words}
+                pop 1
+                dup {out_ptr_index}
+                addi {offset}
+                write_mem {num_words}
+                pop 1
+            )
+        })
+        .collect();
+
+    let advance_pointers_and_recurse = triton_asm!(
+            pop 1
+            addi {ext_row_len}
+            swap 1
+            addi {base_row_len}
+            swap 1
+            swap 2
+            addi {ext_row_len}
+            swap 2
+            swap 3
+            addi {base_row_len}
+            swap 3
+            swap 4
+            addi {num_words_per_row}
+            swap 4
+            recurse
+    );
+
+    [
+        set_up_and_call_loop,
+        single_row_evaluation,
+        copy_evaluations_to_out_array,
+        advance_pointers_and_recurse,
+    ]
+    .concat()
+}
+
 #[cfg(test)]
 mod test {
     use itertools::Itertools;
@@ -10,21 +138,34 @@ mod test {
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use std::collections::HashMap;
+    use strum::EnumIter;
+    use strum::IntoEnumIterator;
     use test_strategy::proptest;
-    use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
     use twenty_first::prelude::*;
 
     use crate::air::tasm_air_constraints::dynamic_air_constraint_evaluation_tasm;
+    use crate::air::tasm_air_constraints::dynamic_air_constraint_evaluation_tasm_for;
     use crate::air::tasm_air_constraints::static_air_constraint_evaluation_tasm;
+    use crate::air::tasm_air_constraints::static_air_constraint_evaluation_tasm_for;
+    use crate::air::tasm_air_constraints::static_air_constraint_evaluation_tasm_on_stack_for;
+    use crate::air::tasm_air_constraints::static_cons_constraints_tasm;
+    use crate::air::tasm_air_constraints::static_init_constraints_tasm;
+    use crate::air::tasm_air_constraints::static_term_constraints_tasm;
+    use crate::air::tasm_air_constraints::static_tran_constraints_tasm;
     use crate::instruction::AnInstruction;
+    use crate::op_stack::NUM_OP_STACK_REGISTERS;
     use crate::prelude::*;
     use crate::table::challenges::Challenges;
     use crate::table::extension_table::Evaluable;
     use crate::table::extension_table::Quotientable;
     use crate::table::master_table::MasterExtTable;
+    use crate::table::ConstraintCategorySet;
+    use crate::table::ConstraintName;
+    use crate::table::ConstraintType;
     use crate::table::NUM_BASE_COLUMNS;
     use crate::table::NUM_EXT_COLUMNS;
 
+    use super::memory_layout::read_xfe_list;
     use super::memory_layout::DynamicTasmConstraintEvaluationMemoryLayout;
     use super::memory_layout::IntegralMemoryLayout;
     use super::memory_layout::StaticTasmConstraintEvaluationMemoryLayout;
@@ -56,7 +197,24 @@ mod test {
         static_memory_layout: StaticTasmConstraintEvaluationMemoryLayout,
     }
 
+    /// Every way to evaluate the AIR constraints. Adding a variant includes the corresponding
+    /// evaluator in the [cross-check of all backends](all_backends_agree).
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, EnumIter)]
+    enum Backend {
+        Rust,
+        StaticTasm,
+        DynamicTasm,
+    }
+
     impl ConstraintEvaluationPoint {
+        fn evaluate_all_constraints(&self, backend: Backend) -> Vec<XFieldElement> {
+            match backend {
+                Backend::Rust => self.evaluate_all_constraints_rust(),
+                Backend::StaticTasm => self.evaluate_all_constraints_tasm_static(),
+                Backend::DynamicTasm => self.evaluate_all_constraints_tasm_dynamic(),
+            }
+        }
+
         fn evaluate_all_constraints_rust(&self) -> Vec<XFieldElement> {
             let init = MasterExtTable::evaluate_initial_constraints(
                 self.curr_base_row.view(),
@@ -84,42 +242,154 @@ mod test {
             [init, cons, tran, term].concat()
         }
 
+        /// Rust code that reconstructs this exact point from literals. Useful for turning a
+        /// failing test case into a permanent regression test: paste the code into a test
+        /// in this module.
+        fn to_reproduction_code(&self) -> String {
+            let bfe_literal = |bfe: BFieldElement| format!("bfe!({}u64)", bfe.value());
+            let xfe_literal = |xfe: &XFieldElement| {
+                let [c_0, c_1, c_2] = xfe.coefficients.map(|c| c.value());
+                format!("xfe!([{c_0}u64, {c_1}u64, {c_2}u64])")
+            };
+            let xfe_literals = |xfes: &[XFieldElement]| xfes.iter().map(xfe_literal).join(", ");
+            let row_literal = |row: &Array1<XFieldElement>| {
+                format!(
+                    "Array1::from(vec![{}])",
+                    xfe_literals(row.as_slice().unwrap())
+                )
+            };
+
+            let layout = self.static_memory_layout;
+            format!(
+                "let point = ConstraintEvaluationPoint {{\n    \
+                    curr_base_row: {curr_base_row},\n    \
+                    curr_ext_row: {curr_ext_row},\n    \
+                    next_base_row: {next_base_row},\n    \
+                    next_ext_row: {next_ext_row},\n    \
+                    challenges: Challenges {{ challenges: [{challenges}] }},\n    \
+                    static_memory_layout: StaticTasmConstraintEvaluationMemoryLayout {{\n        \
+                        free_mem_page_ptr: {free_mem_page_ptr},\n        \
+                        curr_base_row_ptr: {curr_base_row_ptr},\n        \
+                        curr_ext_row_ptr: {curr_ext_row_ptr},\n        \
+                        next_base_row_ptr: {next_base_row_ptr},\n        \
+                        next_ext_row_ptr: {next_ext_row_ptr},\n        \
+                        challenges_ptr: {challenges_ptr},\n    \
+                    }},\n\
+                }};",
+                curr_base_row = row_literal(&self.curr_base_row),
+                curr_ext_row = row_literal(&self.curr_ext_row),
+                next_base_row = row_literal(&self.next_base_row),
+                next_ext_row = row_literal(&self.next_ext_row),
+                challenges = xfe_literals(&self.challenges.challenges),
+                free_mem_page_ptr = bfe_literal(layout.free_mem_page_ptr),
+                curr_base_row_ptr = bfe_literal(layout.curr_base_row_ptr),
+                curr_ext_row_ptr = bfe_literal(layout.curr_ext_row_ptr),
+                next_base_row_ptr = bfe_literal(layout.next_base_row_ptr),
+                next_ext_row_ptr = bfe_literal(layout.next_ext_row_ptr),
+                challenges_ptr = bfe_literal(layout.challenges_ptr),
+            )
+        }
+
+        /// The inverse of [`to_reproduction_code`](Self::to_reproduction_code). Relies on the
+        /// literals appearing in the code in the order of the point's fields.
+        fn from_reproduction_code(code: &str) -> Self {
+            let mut literals = code
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter_map(|token| token.strip_suffix("u64"))
+                .map(|literal| bfe!(literal.parse::<u64>().unwrap()));
+            let mut xfes = |n: usize| {
+                (0..n)
+                    .map(|_| xfe!([(); 3].map(|()| literals.next().unwrap())))
+                    .collect_vec()
+            };
+
+            let curr_base_row = Array1::from(xfes(NUM_BASE_COLUMNS));
+            let curr_ext_row = Array1::from(xfes(NUM_EXT_COLUMNS));
+            let next_base_row = Array1::from(xfes(NUM_BASE_COLUMNS));
+            let next_ext_row = Array1::from(xfes(NUM_EXT_COLUMNS));
+            let challenges = xfes(Challenges::COUNT).try_into().unwrap();
+            let challenges = Challenges { challenges };
+
+            let layout_ptrs = literals.collect_vec();
+            let [free_mem_page_ptr, curr_base_row_ptr, curr_ext_row_ptr, next_base_row_ptr, next_ext_row_ptr, challenges_ptr] =
+                layout_ptrs.try_into().unwrap();
+            let static_memory_layout = StaticTasmConstraintEvaluationMemoryLayout {
+                free_mem_page_ptr,
+                curr_base_row_ptr,
+                curr_ext_row_ptr,
+                next_base_row_ptr,
+                next_ext_row_ptr,
+                challenges_ptr,
+            };
+
+            Self {
+                curr_base_row,
+                curr_ext_row,
+                next_base_row,
+                next_ext_row,
+                challenges,
+                static_memory_layout,
+            }
+        }
+
         fn evaluate_all_constraints_tasm_static(&self) -> Vec<XFieldElement> {
-            let program = self.tasm_static_constraint_evaluation_code();
+            self.evaluate_constraints_tasm_static(ConstraintCategorySet::ALL)
+        }
+
+        fn evaluate_all_constraints_tasm_dynamic(&self) -> Vec<XFieldElement> {
+            self.evaluate_constraints_tasm_dynamic(ConstraintCategorySet::ALL)
+        }
+
+        fn evaluate_constraints_tasm_static(
+            &self,
+            categories: ConstraintCategorySet,
+        ) -> Vec<XFieldElement> {
+            let program = self.tasm_static_constraint_evaluation_code(categories);
             let mut vm_state =
                 self.set_up_triton_vm_to_evaluate_constraints_in_tasm_static(&program);
 
             vm_state.run().unwrap();
 
             let output_list_ptr = vm_state.op_stack.pop().unwrap().value();
-            let num_quotients = MasterExtTable::NUM_CONSTRAINTS;
+            let num_quotients = categories.num_constraints();
             Self::read_xfe_list_at_address(vm_state.ram, output_list_ptr, num_quotients)
         }
 
-        fn evaluate_all_constraints_tasm_dynamic(&self) -> Vec<XFieldElement> {
-            let program = self.tasm_dynamic_constraint_evaluation_code();
+        fn evaluate_constraints_tasm_dynamic(
+            &self,
+            categories: ConstraintCategorySet,
+        ) -> Vec<XFieldElement> {
+            let program = self.tasm_dynamic_constraint_evaluation_code(categories);
             let mut vm_state =
                 self.set_up_triton_vm_to_evaluate_constraints_in_tasm_dynamic(&program);
 
             vm_state.run().unwrap();
 
             let output_list_ptr = vm_state.op_stack.pop().unwrap().value();
-            let num_quotients = MasterExtTable::NUM_CONSTRAINTS;
+            let num_quotients = categories.num_constraints();
             Self::read_xfe_list_at_address(vm_state.ram, output_list_ptr, num_quotients)
         }
 
-        fn tasm_static_constraint_evaluation_code(&self) -> Program {
-            let mut source_code = static_air_constraint_evaluation_tasm(self.static_memory_layout);
+        fn tasm_static_constraint_evaluation_code(
+            &self,
+            categories: ConstraintCategorySet,
+        ) -> Program {
+            let mut source_code =
+                static_air_constraint_evaluation_tasm_for(self.static_memory_layout, categories);
             source_code.push(triton_instr!(halt));
             Program::new(&source_code)
         }
 
-        fn tasm_dynamic_constraint_evaluation_code(&self) -> Program {
+        fn tasm_dynamic_constraint_evaluation_code(
+            &self,
+            categories: ConstraintCategorySet,
+        ) -> Program {
             let dynamic_memory_layout = DynamicTasmConstraintEvaluationMemoryLayout {
                 free_mem_page_ptr: self.static_memory_layout.free_mem_page_ptr,
                 challenges_ptr: self.static_memory_layout.challenges_ptr,
             };
-            let mut source_code = dynamic_air_constraint_evaluation_tasm(dynamic_memory_layout);
+            let mut source_code =
+                dynamic_air_constraint_evaluation_tasm_for(dynamic_memory_layout, categories);
             source_code.push(triton_instr!(halt));
             Program::new(&source_code)
         }
@@ -134,13 +404,12 @@ mod test {
             let next_ext_row_ptr = self.static_memory_layout.next_ext_row_ptr;
             let challenges_ptr = self.static_memory_layout.challenges_ptr;
 
-            let mut ram = HashMap::default();
-            Self::extend_ram_at_address(&mut ram, self.curr_base_row.to_vec(), curr_base_row_ptr);
-            Self::extend_ram_at_address(&mut ram, self.curr_ext_row.to_vec(), curr_ext_row_ptr);
-            Self::extend_ram_at_address(&mut ram, self.next_base_row.to_vec(), next_base_row_ptr);
-            Self::extend_ram_at_address(&mut ram, self.next_ext_row.to_vec(), next_ext_row_ptr);
-            Self::extend_ram_at_address(&mut ram, self.challenges.challenges, challenges_ptr);
-            let non_determinism = NonDeterminism::default().with_ram(ram);
+            let non_determinism = NonDeterminism::default()
+                .with_ram_list_at(curr_base_row_ptr, self.curr_base_row.to_vec())
+                .with_ram_list_at(curr_ext_row_ptr, self.curr_ext_row.to_vec())
+                .with_ram_list_at(next_base_row_ptr, self.next_base_row.to_vec())
+                .with_ram_list_at(next_ext_row_ptr, self.next_ext_row.to_vec())
+                .with_ram_list_at(challenges_ptr, self.challenges.challenges);
 
             VMState::new(program, PublicInput::default(), non_determinism)
         }
@@ -167,49 +436,261 @@ mod test {
             vm_state
         }
 
-        fn extend_ram_at_address(
-            ram: &mut HashMap<BFieldElement, BFieldElement>,
-            list: impl IntoIterator<Item = impl Into<XFieldElement>>,
-            address: BFieldElement,
-        ) {
-            let list = list.into_iter().flat_map(|xfe| xfe.into().coefficients);
-            let indexed_list = list.enumerate();
-            let offset_address = |i| bfe!(i as u64) + address;
-            let ram_extension = indexed_list.map(|(i, bfe)| (offset_address(i), bfe));
-            ram.extend(ram_extension);
-        }
-
         fn read_xfe_list_at_address(
             ram: HashMap<BFieldElement, BFieldElement>,
             address: u64,
             len: usize,
         ) -> Vec<XFieldElement> {
-            let mem_region_end = address + (len * EXTENSION_DEGREE) as u64;
-            (address..mem_region_end)
-                .map(BFieldElement::new)
-                .map(|i| ram[&i])
-                .chunks(EXTENSION_DEGREE)
-                .into_iter()
-                .map(|c| XFieldElement::try_from(c.collect_vec()).unwrap())
-                .collect()
+            read_xfe_list(&ram, bfe!(address), len).unwrap()
         }
     }
 
+    /// Like [`prop_assert_eq!`], but only reports the constraints whose evaluations differ,
+    /// identified by their [name](ConstraintName).
+    fn prop_assert_constraint_evaluations_agree(
+        expected: &[XFieldElement],
+        actual: &[XFieldElement],
+    ) -> Result<(), TestCaseError> {
+        if expected.len() != actual.len() {
+            let reason = format!(
+                "expected {} constraint evaluations but got {}",
+                expected.len(),
+                actual.len()
+            );
+            return Err(TestCaseError::Fail(reason.into()));
+        }
+
+        let mismatches = ConstraintName::all()
+            .into_iter()
+            .zip(expected.iter().zip(actual))
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(name, (expected, actual))| format!("{name}: expected {expected}, got {actual}"))
+            .collect_vec();
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        let reason = format!(
+            "{} of {} constraint evaluations differ:\n{}",
+            mismatches.len(),
+            expected.len(),
+            mismatches.join("\n")
+        );
+        Err(TestCaseError::Fail(reason.into()))
+    }
+
+    #[test]
+    fn constraint_evaluation_mismatch_report_lists_only_differing_constraints() {
+        let expected = vec![xfe!(0); MasterExtTable::NUM_CONSTRAINTS];
+        let mut actual = expected.clone();
+        let num_init_and_cons_constraints =
+            MasterExtTable::NUM_INITIAL_CONSTRAINTS + MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS;
+        actual[num_init_and_cons_constraints + 3] = xfe!(42);
+
+        let result = prop_assert_constraint_evaluations_agree(&expected, &actual);
+        let Err(TestCaseError::Fail(reason)) = result else {
+            panic!("mismatch must be reported");
+        };
+        let report = reason.message();
+        assert!(report.starts_with("1 of"));
+        assert!(report.contains("transition constraint 3"));
+        assert!(2 == report.lines().count());
+
+        assert!(prop_assert_constraint_evaluations_agree(&expected, &expected).is_ok());
+        assert!(prop_assert_constraint_evaluations_agree(&expected, &actual[1..]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn rust_constraint_evaluator_rejects_too_short_input_row() {
+        let base_row = Array1::from_elem(NUM_BASE_COLUMNS, bfe!(0));
+        let ext_row = Array1::from_elem(NUM_EXT_COLUMNS, xfe!(0));
+        let too_short_ext_row = Array1::from_elem(1, xfe!(0));
+        MasterExtTable::evaluate_transition_constraints(
+            base_row.view(),
+            ext_row.view(),
+            base_row.view(),
+            too_short_ext_row.view(),
+            &Challenges::default(),
+        );
+    }
+
     #[proptest]
     fn triton_constraints_and_assembly_constraints_agree(point: ConstraintEvaluationPoint) {
         let all_constraints_rust = point.evaluate_all_constraints_rust();
         let all_constraints_tasm_static = point.evaluate_all_constraints_tasm_static();
-        prop_assert_eq!(all_constraints_rust.clone(), all_constraints_tasm_static);
+        prop_assert_constraint_evaluations_agree(
+            &all_constraints_rust,
+            &all_constraints_tasm_static,
+        )?;
 
         let all_constraints_tasm_dynamic = point.evaluate_all_constraints_tasm_dynamic();
-        prop_assert_eq!(all_constraints_rust, all_constraints_tasm_dynamic);
+        prop_assert_constraint_evaluations_agree(
+            &all_constraints_rust,
+            &all_constraints_tasm_dynamic,
+        )?;
+    }
+
+    #[proptest]
+    fn all_backends_agree(point: ConstraintEvaluationPoint) {
+        let evaluations = Backend::iter()
+            .map(|backend| (backend, point.evaluate_all_constraints(backend)))
+            .collect_vec();
+
+        for ((backend, evaluation), (other_backend, other_evaluation)) in
+            evaluations.iter().tuple_combinations()
+        {
+            let result = prop_assert_constraint_evaluations_agree(evaluation, other_evaluation);
+            let Err(TestCaseError::Fail(reason)) = result else {
+                continue;
+            };
+            let reason = reason.message();
+            let reason = format!("backends {backend:?} and {other_backend:?} disagree: {reason}");
+            return Err(TestCaseError::Fail(reason.into()));
+        }
+    }
+
+    #[proptest(cases = 3)]
+    fn evaluating_constraint_categories_individually_is_like_evaluating_all_constraints(
+        point: ConstraintEvaluationPoint,
+    ) {
+        let evaluate_categories_individually =
+            |evaluate: &dyn Fn(ConstraintCategorySet) -> Vec<XFieldElement>| {
+                ConstraintType::iter()
+                    .flat_map(|constraint_type| evaluate(constraint_type.into()))
+                    .collect_vec()
+            };
+
+        let all_constraints_tasm_static = point.evaluate_all_constraints_tasm_static();
+        let individual_categories_tasm_static = evaluate_categories_individually(&|categories| {
+            point.evaluate_constraints_tasm_static(categories)
+        });
+        prop_assert_constraint_evaluations_agree(
+            &all_constraints_tasm_static,
+            &individual_categories_tasm_static,
+        )?;
+
+        let all_constraints_tasm_dynamic = point.evaluate_all_constraints_tasm_dynamic();
+        let individual_categories_tasm_dynamic = evaluate_categories_individually(&|categories| {
+            point.evaluate_constraints_tasm_dynamic(categories)
+        });
+        prop_assert_constraint_evaluations_agree(
+            &all_constraints_tasm_dynamic,
+            &individual_categories_tasm_dynamic,
+        )?;
+    }
+
+    #[proptest(cases = 3)]
+    fn evaluating_constraint_types_out_of_order_fills_output_array_like_evaluating_all(
+        point: ConstraintEvaluationPoint,
+    ) {
+        let program = point.tasm_static_constraint_evaluation_code(ConstraintCategorySet::ALL);
+        let mut vm_state = point.set_up_triton_vm_to_evaluate_constraints_in_tasm_static(&program);
+        vm_state.run()?;
+        let output_list_ptr = vm_state.op_stack.pop()?.value();
+        let num_constraints = MasterExtTable::NUM_CONSTRAINTS;
+        let all_constraints = ConstraintEvaluationPoint::read_xfe_list_at_address(
+            vm_state.ram,
+            output_list_ptr,
+            num_constraints,
+        );
+
+        let num_init = MasterExtTable::NUM_INITIAL_CONSTRAINTS;
+        let num_cons = MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS;
+        let num_tran = MasterExtTable::NUM_TRANSITION_CONSTRAINTS;
+        let mem_layout = point.static_memory_layout;
+        let source_code = [
+            static_term_constraints_tasm(mem_layout, num_init + num_cons + num_tran),
+            triton_asm!(push 42 pop 1),
+            static_tran_constraints_tasm(mem_layout, num_init + num_cons),
+            static_init_constraints_tasm(mem_layout, 0),
+            static_cons_constraints_tasm(mem_layout, num_init),
+            triton_asm!(halt),
+        ]
+        .concat();
+        let program = Program::new(&source_code);
+        let mut vm_state = point.set_up_triton_vm_to_evaluate_constraints_in_tasm_static(&program);
+        vm_state.run()?;
+        prop_assert_eq!(NUM_OP_STACK_REGISTERS, vm_state.op_stack.len());
+        let individual_types = ConstraintEvaluationPoint::read_xfe_list_at_address(
+            vm_state.ram,
+            output_list_ptr,
+            num_constraints,
+        );
+        prop_assert_constraint_evaluations_agree(&all_constraints, &individual_types)?;
+    }
+
+    #[proptest(cases = 3)]
+    fn evaluating_a_subset_of_constraint_categories_skips_the_others(
+        point: ConstraintEvaluationPoint,
+    ) {
+        let all_constraints = point.evaluate_all_constraints_tasm_static();
+        let num_init = MasterExtTable::NUM_INITIAL_CONSTRAINTS;
+        let num_cons = MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS;
+        let num_tran = MasterExtTable::NUM_TRANSITION_CONSTRAINTS;
+        let init_constraints = &all_constraints[..num_init];
+        let tran_constraints = &all_constraints[num_init + num_cons..][..num_tran];
+
+        let categories = ConstraintCategorySet::INITIAL | ConstraintCategorySet::TRANSITION;
+        let selected_constraints = point.evaluate_constraints_tasm_static(categories);
+        let expected = [init_constraints, tran_constraints].concat();
+        prop_assert_eq!(expected, selected_constraints);
+    }
+
+    #[proptest(cases = 3)]
+    fn evaluations_left_on_stack_agree_with_evaluations_written_to_memory(
+        point: ConstraintEvaluationPoint,
+    ) {
+        let all_categories = ConstraintCategorySet::ALL;
+        let some_categories = ConstraintCategorySet::INITIAL | ConstraintCategorySet::TRANSITION;
+        for categories in [all_categories, some_categories] {
+            let expected = point.evaluate_constraints_tasm_static(categories);
+
+            let mem_layout = point.static_memory_layout;
+            let mut source_code =
+                static_air_constraint_evaluation_tasm_on_stack_for(mem_layout, categories);
+            source_code.push(triton_instr!(halt));
+            let program = Program::new(&source_code);
+            let mut vm_state =
+                point.set_up_triton_vm_to_evaluate_constraints_in_tasm_static(&program);
+            vm_state.run()?;
+
+            let mut on_stack = vec![];
+            for _ in 0..categories.num_constraints() {
+                on_stack.push(vm_state.op_stack.pop_extension_field_element()?);
+            }
+            on_stack.reverse();
+            prop_assert_eq!(NUM_OP_STACK_REGISTERS, vm_state.op_stack.len());
+            prop_assert_constraint_evaluations_agree(&expected, &on_stack)?;
+        }
+    }
+
+    #[proptest]
+    fn constraint_evaluation_point_survives_round_trip_through_reproduction_code(
+        point: ConstraintEvaluationPoint,
+    ) {
+        let code = point.to_reproduction_code();
+        let reconstructed_point = ConstraintEvaluationPoint::from_reproduction_code(&code);
+        prop_assert_eq!(&point.curr_base_row, &reconstructed_point.curr_base_row);
+        prop_assert_eq!(&point.next_ext_row, &reconstructed_point.next_ext_row);
+        prop_assert_eq!(
+            point.static_memory_layout,
+            reconstructed_point.static_memory_layout
+        );
+
+        // The memory layout only matters to Triton assembly, which is run in Triton VM.
+        let expected = point.evaluate_all_constraints_rust();
+        for backend in Backend::iter() {
+            let actual = reconstructed_point.evaluate_all_constraints(backend);
+            prop_assert_constraint_evaluations_agree(&expected, &actual)?;
+        }
     }
 
     #[proptest]
     fn triton_assembly_constraint_evaluators_do_not_write_outside_of_dedicated_memory_region(
         point: ConstraintEvaluationPoint,
     ) {
-        let program = point.tasm_static_constraint_evaluation_code();
+        let program = point.tasm_static_constraint_evaluation_code(ConstraintCategorySet::ALL);
         let mut initial_state =
             point.set_up_triton_vm_to_evaluate_constraints_in_tasm_static(&program);
         let mut terminal_state = initial_state.clone();
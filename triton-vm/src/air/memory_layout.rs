@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 use arbitrary::Arbitrary;
 use itertools::Itertools;
+use strum::Display;
+use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 
+use crate::error::LayoutError;
+use crate::error::XFieldElementConversionError;
 use crate::table::challenges::Challenges;
 use crate::table::NUM_BASE_COLUMNS;
 use crate::table::NUM_EXT_COLUMNS;
@@ -9,6 +18,21 @@ use crate::table::NUM_EXT_COLUMNS;
 /// The minimal required size of a memory page in [`BFieldElement`]s.
 pub const MEM_PAGE_SIZE: usize = 1 << 32;
 
+/// An offset from the free memory page's start, in number of [`BFieldElement`]s. Indicates the
+/// start of the array the [batched Triton assembly AIR constraint evaluator][batched] writes
+/// its results to. The array lies between the region the evaluation of a single row uses for
+/// intermediate values, at the start of the page, and that row's results, at the end of the
+/// page.
+///
+/// [batched]: crate::air::tasm_air_constraints::static_air_constraint_evaluation_tasm_batched
+pub const BATCHED_OUT_ARRAY_OFFSET: usize = MEM_PAGE_SIZE / 2;
+
+/// The maximal length of the array the [batched Triton assembly AIR constraint
+/// evaluator][batched] writes its results to, in number of [`BFieldElement`]s.
+///
+/// [batched]: crate::air::tasm_air_constraints::static_air_constraint_evaluation_tasm_batched
+pub const MAX_BATCHED_OUT_ARRAY_LEN: usize = MEM_PAGE_SIZE / 4;
+
 /// Memory layout guarantees for the [Triton assembly AIR constraint evaluator][tasm_air]
 /// with input lists at dynamically known memory locations.
 ///
@@ -59,40 +83,124 @@ pub trait IntegralMemoryLayout {
     /// Determine if the memory layout's constraints are met, _i.e._, whether the
     /// various pointers point to large enough regions of memory.
     fn is_integral(&self) -> bool {
-        let memory_regions = self.memory_regions();
-        if memory_regions.iter().unique().count() != memory_regions.len() {
-            return false;
-        }
+        self.integrality_error().is_none()
+    }
 
-        let disjoint_from_all_others = |region| {
-            let mut all_other_regions = memory_regions.iter().filter(|&r| r != region);
-            all_other_regions.all(|r| r.disjoint_from(region))
-        };
-        memory_regions.iter().all(disjoint_from_all_others)
+    /// The reason the memory layout is not [integral](Self::is_integral), if any: the first
+    /// two memory regions that overlap.
+    fn integrality_error(&self) -> Option<LayoutError> {
+        let named_memory_regions = self.named_memory_regions();
+        let (region_name, region, other_region_name, other_region) = named_memory_regions
+            .iter()
+            .tuple_combinations()
+            .find(|((_, region), (_, other_region))| {
+                region == other_region || region.overlaps(other_region)
+            })
+            .map(|(&(name, region), &(other_name, other_region))| {
+                (name, region, other_name, other_region)
+            })?;
+
+        Some(LayoutError::OverlappingRegions {
+            region_name,
+            region,
+            other_region_name,
+            other_region,
+        })
+    }
+
+    fn memory_regions(&self) -> Box<[MemoryRegion]> {
+        let named_memory_regions = self.named_memory_regions();
+        named_memory_regions
+            .iter()
+            .map(|&(_, region)| region)
+            .collect()
+    }
+
+    /// The [memory regions](MemoryRegion) of the memory layout, each along with the name of
+    /// the pointer to it, like `"free_mem_page_ptr"`. Useful for dumping the memory map.
+    fn regions(&self) -> Vec<(&'static str, MemoryRegion)> {
+        let named_memory_regions = self.named_memory_regions();
+        named_memory_regions
+            .iter()
+            .map(|&(name, region)| (name.pointer_name(), region))
+            .collect()
     }
 
-    fn memory_regions(&self) -> Box<[MemoryRegion]>;
+    /// The [memory regions](MemoryRegion) of the memory layout, each along with its purpose.
+    fn named_memory_regions(&self) -> Box<[(MemoryRegionName, MemoryRegion)]>;
+}
+
+/// The purpose of a [`MemoryRegion`] in a memory layout for the
+/// [Triton assembly AIR constraint evaluator][tasm_air].
+///
+/// [tasm_air]: crate::air::tasm_air_constraints
+#[derive(Debug, Display, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum MemoryRegionName {
+    FreeMemPage,
+    CurrBaseRow,
+    CurrExtRow,
+    NextBaseRow,
+    NextExtRow,
+    Challenges,
+}
+
+impl MemoryRegionName {
+    /// The name of the memory layout's field pointing to the region of this purpose.
+    pub const fn pointer_name(self) -> &'static str {
+        match self {
+            Self::FreeMemPage => "free_mem_page_ptr",
+            Self::CurrBaseRow => "curr_base_row_ptr",
+            Self::CurrExtRow => "curr_ext_row_ptr",
+            Self::NextBaseRow => "next_base_row_ptr",
+            Self::NextExtRow => "next_ext_row_ptr",
+            Self::Challenges => "challenges_ptr",
+        }
+    }
 }
 
 impl IntegralMemoryLayout for StaticTasmConstraintEvaluationMemoryLayout {
-    fn memory_regions(&self) -> Box<[MemoryRegion]> {
+    fn named_memory_regions(&self) -> Box<[(MemoryRegionName, MemoryRegion)]> {
         let all_regions = [
-            MemoryRegion::new(self.free_mem_page_ptr, MEM_PAGE_SIZE),
-            MemoryRegion::new(self.curr_base_row_ptr, NUM_BASE_COLUMNS),
-            MemoryRegion::new(self.curr_ext_row_ptr, NUM_EXT_COLUMNS),
-            MemoryRegion::new(self.next_base_row_ptr, NUM_BASE_COLUMNS),
-            MemoryRegion::new(self.next_ext_row_ptr, NUM_EXT_COLUMNS),
-            MemoryRegion::new(self.challenges_ptr, Challenges::COUNT),
+            (
+                MemoryRegionName::FreeMemPage,
+                MemoryRegion::new(self.free_mem_page_ptr, MEM_PAGE_SIZE),
+            ),
+            (
+                MemoryRegionName::CurrBaseRow,
+                MemoryRegion::new(self.curr_base_row_ptr, NUM_BASE_COLUMNS),
+            ),
+            (
+                MemoryRegionName::CurrExtRow,
+                MemoryRegion::new(self.curr_ext_row_ptr, NUM_EXT_COLUMNS),
+            ),
+            (
+                MemoryRegionName::NextBaseRow,
+                MemoryRegion::new(self.next_base_row_ptr, NUM_BASE_COLUMNS),
+            ),
+            (
+                MemoryRegionName::NextExtRow,
+                MemoryRegion::new(self.next_ext_row_ptr, NUM_EXT_COLUMNS),
+            ),
+            (
+                MemoryRegionName::Challenges,
+                MemoryRegion::new(self.challenges_ptr, Challenges::COUNT),
+            ),
         ];
         Box::new(all_regions)
     }
 }
 
 impl IntegralMemoryLayout for DynamicTasmConstraintEvaluationMemoryLayout {
-    fn memory_regions(&self) -> Box<[MemoryRegion]> {
+    fn named_memory_regions(&self) -> Box<[(MemoryRegionName, MemoryRegion)]> {
         let all_regions = [
-            MemoryRegion::new(self.free_mem_page_ptr, MEM_PAGE_SIZE),
-            MemoryRegion::new(self.challenges_ptr, Challenges::COUNT),
+            (
+                MemoryRegionName::FreeMemPage,
+                MemoryRegion::new(self.free_mem_page_ptr, MEM_PAGE_SIZE),
+            ),
+            (
+                MemoryRegionName::Challenges,
+                MemoryRegion::new(self.challenges_ptr, Challenges::COUNT),
+            ),
         ];
         Box::new(all_regions)
     }
@@ -111,6 +219,16 @@ impl MemoryRegion {
         Self { start, size }
     }
 
+    /// The first address of the region.
+    pub fn start(self) -> BFieldElement {
+        self.start
+    }
+
+    /// The number of [`BFieldElement`]s the region spans.
+    pub fn size(self) -> u64 {
+        self.size
+    }
+
     pub fn disjoint_from(self, other: &Self) -> bool {
         !self.overlaps(other)
     }
@@ -128,8 +246,97 @@ impl MemoryRegion {
     }
 }
 
+impl Display for MemoryRegion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // move all arithmetic to u128 to avoid overflows
+        let start = u128::from(self.start.value());
+        let end = start + u128::from(self.size);
+        write!(f, "[{start}, {end})")
+    }
+}
+
+/// Interpret a list of [`BFieldElement`]s as a list of [`XFieldElement`]s, as they would be
+/// laid out in memory. Every [`EXTENSION_DEGREE`] consecutive [`BFieldElement`]s make up the
+/// coefficients of one [`XFieldElement`]. The inverse of [`xfes_to_bfes`].
+///
+/// Returns an error if the length of the given list is not a multiple of
+/// [`EXTENSION_DEGREE`].
+pub fn bfes_to_xfes(
+    bfes: &[BFieldElement],
+) -> Result<Vec<XFieldElement>, XFieldElementConversionError> {
+    if !bfes.len().is_multiple_of(EXTENSION_DEGREE) {
+        let length = bfes.len();
+        return Err(XFieldElementConversionError::LengthNotMultipleOfExtensionDegree(length));
+    }
+
+    let xfes = bfes
+        .chunks_exact(EXTENSION_DEGREE)
+        .map(|coefficients| XFieldElement::new(coefficients.try_into().unwrap()))
+        .collect();
+    Ok(xfes)
+}
+
+/// The order in which the coefficients of an [`XFieldElement`] are laid out in memory.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum CoefficientOrder {
+    /// The constant coefficient is stored at the lowest address. This is the layout produced
+    /// by [`xfes_to_bfes`].
+    #[default]
+    LittleEndian,
+
+    /// The constant coefficient is stored at the highest address.
+    BigEndian,
+}
+
+/// Read a list of `len` [`XFieldElement`]s from RAM, starting at the given address. The
+/// list is expected to be laid out as by [`xfes_to_bfes`].
+///
+/// Returns an error naming the first uninitialized address if any word of the
+/// corresponding memory region is missing from the given RAM.
+pub fn read_xfe_list(
+    ram: &HashMap<BFieldElement, BFieldElement>,
+    address: BFieldElement,
+    len: usize,
+) -> Result<Vec<XFieldElement>, XFieldElementConversionError> {
+    read_xfe_list_in_order(ram, address, len, CoefficientOrder::LittleEndian)
+}
+
+/// Like [`read_xfe_list`], but for lists whose elements' coefficients are laid out in the
+/// given order.
+pub fn read_xfe_list_in_order(
+    ram: &HashMap<BFieldElement, BFieldElement>,
+    address: BFieldElement,
+    len: usize,
+    coefficient_order: CoefficientOrder,
+) -> Result<Vec<XFieldElement>, XFieldElementConversionError> {
+    let num_bfes = len * EXTENSION_DEGREE;
+    let region = MemoryRegion::new(address, num_bfes);
+    let read_word = |i: usize| {
+        let word_address = address + bfe!(i as u64);
+        ram.get(&word_address)
+            .copied()
+            .ok_or(XFieldElementConversionError::UninitializedMemory {
+                region,
+                address: word_address,
+            })
+    };
+    let mut bfes: Vec<_> = (0..num_bfes).map(read_word).try_collect()?;
+    if coefficient_order == CoefficientOrder::BigEndian {
+        bfes.chunks_exact_mut(EXTENSION_DEGREE)
+            .for_each(|coefficients| coefficients.reverse());
+    }
+    bfes_to_xfes(&bfes)
+}
+
+/// Flatten a list of [`XFieldElement`]s into a list of their coefficients, as they would be
+/// laid out in memory. The inverse of [`bfes_to_xfes`].
+pub fn xfes_to_bfes(xfes: &[XFieldElement]) -> Vec<BFieldElement> {
+    xfes.iter().flat_map(|xfe| xfe.coefficients).collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
@@ -187,6 +394,51 @@ mod tests {
         prop_assert!(!region.contains_address(address));
     }
 
+    #[proptest]
+    fn regions_of_integral_static_memory_layout_are_pairwise_disjoint(
+        #[strategy(arb())]
+        #[filter(#layout.is_integral())]
+        layout: StaticTasmConstraintEvaluationMemoryLayout,
+    ) {
+        let regions = layout.regions();
+        prop_assert_eq!(6, regions.len());
+        for ((name, region), (other_name, other_region)) in regions.iter().tuple_combinations() {
+            prop_assert_ne!(name, other_name);
+            prop_assert!(
+                region.disjoint_from(other_region),
+                "{name} and {other_name} overlap"
+            );
+        }
+    }
+
+    #[test]
+    fn regions_of_memory_layouts_are_named_after_pointers() {
+        let layout = StaticTasmConstraintEvaluationMemoryLayout::default();
+        let regions = layout.regions();
+        assert_eq!(
+            (
+                "free_mem_page_ptr",
+                MemoryRegion::new(layout.free_mem_page_ptr, MEM_PAGE_SIZE)
+            ),
+            regions[0]
+        );
+        assert_eq!(
+            (
+                "challenges_ptr",
+                MemoryRegion::new(layout.challenges_ptr, Challenges::COUNT)
+            ),
+            regions[5]
+        );
+
+        let layout = DynamicTasmConstraintEvaluationMemoryLayout::default();
+        let names = layout
+            .regions()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect_vec();
+        assert_eq!(vec!["free_mem_page_ptr", "challenges_ptr"], names);
+    }
+
     #[test]
     fn definitely_integral_memory_layout_is_detected_as_integral() {
         assert!(StaticTasmConstraintEvaluationMemoryLayout::default().is_integral());
@@ -212,6 +464,160 @@ mod tests {
         assert!(!layout.is_integral());
     }
 
+    #[test]
+    fn integral_memory_layout_has_no_integrality_error() {
+        let static_layout = StaticTasmConstraintEvaluationMemoryLayout::default();
+        assert_eq!(None, static_layout.integrality_error());
+
+        let dynamic_layout = DynamicTasmConstraintEvaluationMemoryLayout::default();
+        assert_eq!(None, dynamic_layout.integrality_error());
+    }
+
+    #[test]
+    fn integrality_error_identifies_challenges_overlapping_free_mem_page() {
+        let free_mem_page_ptr = bfe!(0);
+        let challenges_ptr = bfe!(42);
+        let layout = DynamicTasmConstraintEvaluationMemoryLayout {
+            free_mem_page_ptr,
+            challenges_ptr,
+        };
+        assert!(!layout.is_integral());
+
+        let Some(err) = layout.integrality_error() else {
+            panic!("overlapping regions must be detected");
+        };
+        let LayoutError::OverlappingRegions {
+            region_name,
+            region,
+            other_region_name,
+            other_region,
+        } = err;
+        assert_eq!(MemoryRegionName::FreeMemPage, region_name);
+        assert_eq!(MemoryRegion::new(free_mem_page_ptr, MEM_PAGE_SIZE), region);
+        assert_eq!(MemoryRegionName::Challenges, other_region_name);
+        assert_eq!(
+            MemoryRegion::new(challenges_ptr, Challenges::COUNT),
+            other_region
+        );
+
+        let err_msg = err.to_string();
+        assert!(err_msg.contains("FreeMemPage"), "{err_msg}");
+        assert!(err_msg.contains("Challenges"), "{err_msg}");
+        assert!(
+            err_msg.contains(&format!("[0, {MEM_PAGE_SIZE})")),
+            "{err_msg}"
+        );
+    }
+
+    #[test]
+    fn integrality_error_identifies_challenges_overlapping_static_free_mem_page() {
+        let layout = StaticTasmConstraintEvaluationMemoryLayout {
+            challenges_ptr: bfe!(1),
+            ..Default::default()
+        };
+        let Some(LayoutError::OverlappingRegions {
+            region_name,
+            other_region_name,
+            ..
+        }) = layout.integrality_error()
+        else {
+            panic!("overlapping regions must be detected");
+        };
+        assert_eq!(MemoryRegionName::FreeMemPage, region_name);
+        assert_eq!(MemoryRegionName::Challenges, other_region_name);
+    }
+
+    #[proptest]
+    fn memory_layout_is_integral_iff_it_has_no_integrality_error(
+        #[strategy(arb())] layout: StaticTasmConstraintEvaluationMemoryLayout,
+    ) {
+        prop_assert_eq!(layout.is_integral(), layout.integrality_error().is_none());
+    }
+
+    #[proptest]
+    fn xfes_to_bfes_and_back_is_identity(#[strategy(arb())] xfes: Vec<XFieldElement>) {
+        let bfes = xfes_to_bfes(&xfes);
+        prop_assert_eq!(xfes.len() * EXTENSION_DEGREE, bfes.len());
+        prop_assert_eq!(xfes, bfes_to_xfes(&bfes).unwrap());
+    }
+
+    #[proptest]
+    fn bfes_to_xfes_and_back_is_identity(
+        #[strategy(0_usize..100)] num_xfes: usize,
+        #[strategy(vec(arb(), #num_xfes * EXTENSION_DEGREE))] bfes: Vec<BFieldElement>,
+    ) {
+        let xfes = bfes_to_xfes(&bfes).unwrap();
+        prop_assert_eq!(num_xfes, xfes.len());
+        prop_assert_eq!(bfes, xfes_to_bfes(&xfes));
+    }
+
+    #[test]
+    fn converting_bfes_of_wrong_length_to_xfes_fails() {
+        for length in [1, 2, 4, 5, 7] {
+            let bfes = vec![bfe!(42); length];
+            let Err(err) = bfes_to_xfes(&bfes) else {
+                panic!("conversion of {length} elements must fail");
+            };
+            assert_eq!(
+                XFieldElementConversionError::LengthNotMultipleOfExtensionDegree(length),
+                err
+            );
+        }
+    }
+
+    #[proptest]
+    fn xfe_list_written_to_ram_can_be_read(
+        #[strategy(arb())] address: BFieldElement,
+        #[strategy(arb())] xfes: Vec<XFieldElement>,
+    ) {
+        let ram = xfes_to_bfes(&xfes)
+            .into_iter()
+            .enumerate()
+            .map(|(i, bfe)| (address + bfe!(i as u64), bfe))
+            .collect();
+        prop_assert_eq!(
+            xfes.clone(),
+            read_xfe_list(&ram, address, xfes.len()).unwrap()
+        );
+    }
+
+    #[proptest]
+    fn big_endian_xfe_list_written_to_ram_can_be_read(
+        #[strategy(arb())] address: BFieldElement,
+        #[strategy(arb())] xfes: Vec<XFieldElement>,
+    ) {
+        let ram = xfes
+            .iter()
+            .flat_map(|xfe| xfe.coefficients.into_iter().rev())
+            .enumerate()
+            .map(|(i, bfe)| (address + bfe!(i as u64), bfe))
+            .collect();
+        let read_xfes =
+            read_xfe_list_in_order(&ram, address, xfes.len(), CoefficientOrder::BigEndian);
+        prop_assert_eq!(xfes, read_xfes.unwrap());
+    }
+
+    #[test]
+    fn reading_xfe_list_from_ram_with_missing_word_fails() {
+        let address = bfe!(100);
+        let missing_address = bfe!(104);
+        let ram = (100..106)
+            .map(|i| (bfe!(i), bfe!(42)))
+            .filter(|&(addr, _)| addr != missing_address)
+            .collect();
+
+        let err = read_xfe_list(&ram, address, 2).unwrap_err();
+        let XFieldElementConversionError::UninitializedMemory { region, address } = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(MemoryRegion::new(100_u64, 6), region);
+        assert_eq!(missing_address, address);
+
+        let err_msg = err.to_string();
+        assert!(err_msg.contains("104"), "{err_msg}");
+        assert!(err_msg.contains("[100, 106)"), "{err_msg}");
+    }
+
     #[test]
     fn memory_layout_integrity_check_does_not_panic_due_to_arithmetic_overflow() {
         let mem_layout = DynamicTasmConstraintEvaluationMemoryLayout {
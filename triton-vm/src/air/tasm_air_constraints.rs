@@ -6,6 +6,7 @@ use crate::air::memory_layout::DynamicTasmConstraintEvaluationMemoryLayout;
 use crate::air::memory_layout::StaticTasmConstraintEvaluationMemoryLayout;
 use crate::instruction::LabelledInstruction;
 use crate::table::constraints::ERROR_MESSAGE_GENERATE_CONSTRAINTS;
+use crate::table::ConstraintCategorySet;
 
 pub fn static_air_constraint_evaluation_tasm(
     _: StaticTasmConstraintEvaluationMemoryLayout,
@@ -18,3 +19,65 @@ pub fn dynamic_air_constraint_evaluation_tasm(
 ) -> Vec<LabelledInstruction> {
     panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
 }
+
+pub fn static_air_constraint_evaluation_tasm_for(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: ConstraintCategorySet,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn dynamic_air_constraint_evaluation_tasm_for(
+    _: DynamicTasmConstraintEvaluationMemoryLayout,
+    _: ConstraintCategorySet,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_air_constraint_evaluation_tasm_on_stack(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_air_constraint_evaluation_tasm_on_stack_for(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: ConstraintCategorySet,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_air_constraint_evaluation_tasm_batched(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: usize,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_init_constraints_tasm(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: usize,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_cons_constraints_tasm(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: usize,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_tran_constraints_tasm(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: usize,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
+
+pub fn static_term_constraints_tasm(
+    _: StaticTasmConstraintEvaluationMemoryLayout,
+    _: usize,
+) -> Vec<LabelledInstruction> {
+    panic!("{ERROR_MESSAGE_GENERATE_CONSTRAINTS}");
+}
@@ -243,7 +243,7 @@ mod tests {
             // Verify that batch-evaluated values match a manual evaluation
             for i in 0..order {
                 assert_eq!(
-                    poly.evaluate(b_domain.domain_value(i as u32)),
+                    poly.evaluate::<_, BFieldElement>(b_domain.domain_value(i as u32)),
                     values[i as usize]
                 );
             }
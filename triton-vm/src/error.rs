@@ -1,13 +1,23 @@
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::io;
 use std::num::TryFromIntError;
+use std::path::PathBuf;
 
 use thiserror::Error;
 use twenty_first::error::MerkleTreeError;
+use twenty_first::error::ParseBFieldElementError;
+use twenty_first::error::TryFromHexDigestError;
+use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 
+use crate::air::memory_layout::MemoryRegion;
+use crate::air::memory_layout::MemoryRegionName;
 use crate::instruction::Instruction;
+use crate::parser::ParseError;
+use crate::proof::Claim;
+use crate::proof::MAX_LOG2_PADDED_HEIGHT;
 use crate::proof_item::ProofItem;
 use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
@@ -35,7 +45,16 @@ impl Display for VMError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         writeln!(f, "VM error: {}", self.source)?;
         writeln!(f, "VM state:")?;
-        writeln!(f, "{}", self.vm_state)
+        writeln!(f, "{}", self.vm_state)?;
+
+        let recent_instructions = self.vm_state.recent_instructions();
+        if !recent_instructions.is_empty() {
+            writeln!(f, "recent instructions:")?;
+            for (address, instruction) in recent_instructions {
+                writeln!(f, "  {address}: {instruction}")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -60,6 +79,9 @@ pub enum InstructionError {
     #[error("jump stack is empty")]
     JumpStackIsEmpty,
 
+    #[error("jump stack exceeds maximum depth of {0}")]
+    JumpStackOverflow(usize),
+
     #[error("assertion failed: st0 must be 1")]
     AssertionFailed,
 
@@ -92,6 +114,9 @@ pub enum InstructionError {
 
     #[error("Triton VM has halted and cannot execute any further instructions")]
     MachineHalted,
+
+    #[error("program did not halt within {0} clock cycles")]
+    ProgramDidNotHalt(u32),
 }
 
 #[non_exhaustive]
@@ -122,10 +147,89 @@ pub enum ProofStreamError {
     #[error("the proof stream must contain exactly one log2_padded_height item")]
     TooManyLog2PaddedHeights,
 
+    #[error("log2_padded_height {0} exceeds the maximum of {MAX_LOG2_PADDED_HEIGHT}")]
+    Log2PaddedHeightTooLarge(u32),
+
+    #[error("padded height {padded_height} exceeds the limit of {limit}")]
+    PaddedHeightExceedsLimit { padded_height: usize, limit: usize },
+
+    #[error("the proof stream must contain at least one FRI response")]
+    NoFriResponse,
+
+    #[error(
+        "the proof stream's {num_fri_responses} FRI responses are inconsistent with its \
+        {num_fri_merkle_roots} FRI Merkle roots"
+    )]
+    InconsistentFriRounds {
+        num_fri_responses: usize,
+        num_fri_merkle_roots: usize,
+    },
+
+    #[error("position {0} does not fall on the boundary of a proof item")]
+    NotAnItemBoundary(usize),
+
     #[error(transparent)]
     DecodingError(#[from] <ProofStream as BFieldCodec>::Error),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ClaimDecodingError {
+    #[error("public input of length {0} exceeds the maximum of {1}")]
+    InputTooLong(u64, usize),
+
+    #[error("public output of length {0} exceeds the maximum of {1}")]
+    OutputTooLong(u64, usize),
+
+    #[error(transparent)]
+    DecodingError(#[from] <Claim as BFieldCodec>::Error),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum TritonIOError {
+    #[error("the sequence ends before field `{0}`")]
+    MissingField(&'static str),
+
+    #[error("the length of field `{0}` exceeds the remaining sequence")]
+    FieldTooLong(&'static str),
+
+    #[error("field `{0}` cannot be decoded")]
+    InvalidField(&'static str),
+
+    #[error("{0} elements remain after decoding")]
+    TrailingElements(usize),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ClaimJsonError {
+    #[error("invalid program digest: {0}")]
+    InvalidProgramDigest(#[from] TryFromHexDigestError),
+
+    #[error("public input element at index {0} is invalid: {1}")]
+    InvalidInputElement(usize, ParseBFieldElementError),
+
+    #[error("public output element at index {0} is invalid: {1}")]
+    InvalidOutputElement(usize, ParseBFieldElementError),
+
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ClaimBuildingError {
+    #[error("the program digest is missing")]
+    MissingProgramDigest,
+
+    #[error("public input element {1} at index {0} is not a canonical field element")]
+    NonCanonicalInputElement(usize, u64),
+
+    #[error("public output element {1} at index {0} is not a canonical field element")]
+    NonCanonicalOutputElement(usize, u64),
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum FriSetupError {
@@ -198,11 +302,104 @@ pub enum ProgramDecodingError {
     #[error("length of decoded program is unexpected")]
     LengthMismatch,
 
+    #[error("sequence to decode contains invalid opcode {1} at index {0}")]
+    InvalidOpcode(usize, BFieldElement),
+
     #[error("sequence to decode contains invalid instruction at index {0}: {1}")]
     InvalidInstruction(usize, InstructionError),
 
     #[error("missing argument for instruction {1} at index {0}")]
     MissingArgument(usize, Instruction),
+
+    #[error("byte length {0} is not a multiple of the size of a word, which is 8 bytes")]
+    ByteLengthNotMultipleOfWordSize(usize),
+
+    #[error("word {1} at index {0} is not a canonical field element")]
+    NonCanonicalWord(usize, u64),
+}
+
+/// An error while loading a [`Program`][program] from a file containing Triton assembly. See
+/// [`Program::from_tasm_file`][from_tasm_file].
+///
+/// [program]: crate::program::Program
+/// [from_tasm_file]: crate::program::Program::from_tasm_file
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TasmFileError {
+    #[error("cannot read file {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("{}:{line}:{column}: cannot parse Triton assembly:\n{message}", path.display())]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ProgramSizeError {
+    #[error("program has {0} instructions, exceeding the limit of {1}")]
+    TooManyInstructions(usize, usize),
+}
+
+/// An error while assembling a [`Program`][program] of limited size from Triton assembly. See
+/// [`Program::from_code_with_max_instructions`][from_code].
+///
+/// [program]: crate::program::Program
+/// [from_code]: crate::program::Program::from_code_with_max_instructions
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Error)]
+pub enum ProgramFromCodeError<'a> {
+    #[error("{0}")]
+    Parse(ParseError<'a>),
+
+    #[error(transparent)]
+    Size(#[from] ProgramSizeError),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum InstructionReplacementError {
+    #[error("address {0} is out of range for a program of length {1}")]
+    AddressOutOfRange(u64, usize),
+
+    #[error("address {0} does not point to the start of an instruction")]
+    AddressNotAtInstructionStart(u64),
+
+    #[error("call target {0} does not point to the start of an instruction")]
+    InvalidCallTarget(u64),
+}
+
+/// A likely shortcoming of some [`NonDeterminism`][nondet] with respect to a
+/// [`Program`][program]. See [`NonDeterminism::validate_for`][validate].
+///
+/// [nondet]: crate::program::NonDeterminism
+/// [program]: crate::program::Program
+/// [validate]: crate::program::NonDeterminism::validate_for
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum NondetIssue {
+    #[error("program divines at least {required} individual tokens, but {supplied} are supplied")]
+    TooFewIndividualTokens { required: usize, supplied: usize },
+
+    #[error("program divines at least {required} digests, but {supplied} are supplied")]
+    TooFewDigests { required: usize, supplied: usize },
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum AlgebraicExecutionTraceDecodingError {
+    #[error("failed to decode algebraic execution trace: {0}")]
+    DecodingError(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("trace with {0} elements cannot be arranged in rows of width {1}")]
+    TraceShapeMismatch(usize, usize),
+
+    #[error("u32 table entry has invalid instruction: {0}")]
+    InvalidU32TableInstruction(#[from] InstructionError),
 }
 
 #[non_exhaustive]
@@ -214,6 +411,12 @@ pub enum ProvingError {
     #[error("claimed public output does not match actual public output")]
     PublicOutputMismatch,
 
+    #[error("input commitment does not match claimed public input")]
+    InputCommitmentMismatch,
+
+    #[error("execution trace does not end in instruction `halt`")]
+    TraceDoesNotHalt,
+
     #[error("expected row of length {expected_len} but got {actual_len}")]
     TableRowConversionError {
         expected_len: usize,
@@ -242,6 +445,9 @@ pub enum VerificationError {
     #[error("received and computed out-of-domain quotient values don't match")]
     OutOfDomainQuotientValueMismatch,
 
+    #[error("input commitment does not match claimed public input")]
+    InputCommitmentMismatch,
+
     #[error("failed to verify authentication path for base codeword")]
     BaseCodewordAuthenticationFailure,
 
@@ -292,6 +498,37 @@ pub enum OpStackElementError {
     FailedIntegerConversion(#[from] TryFromIntError),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum XFieldElementConversionError {
+    #[error("length {0} is not a multiple of the extension degree {EXTENSION_DEGREE}")]
+    LengthNotMultipleOfExtensionDegree(usize),
+
+    #[error("address {address} of memory region {region} is uninitialized")]
+    UninitializedMemory {
+        region: MemoryRegion,
+        address: BFieldElement,
+    },
+}
+
+/// The reason a memory layout is not [integral][integral].
+///
+/// [integral]: crate::air::memory_layout::IntegralMemoryLayout::is_integral
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum LayoutError {
+    #[error(
+        "memory region {region_name} {region} overlaps with \
+         memory region {other_region_name} {other_region}"
+    )]
+    OverlappingRegions {
+        region_name: MemoryRegionName,
+        region: MemoryRegion,
+        other_region_name: MemoryRegionName,
+        other_region: MemoryRegion,
+    },
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum NumberOfWordsError {
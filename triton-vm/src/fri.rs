@@ -10,6 +10,7 @@ use crate::error::FriProvingError;
 use crate::error::FriSetupError;
 use crate::error::FriValidationError;
 use crate::error::FriValidationError::*;
+use crate::merkle;
 use crate::profiler::profiler;
 use crate::proof_item::FriResponse;
 use crate::proof_item::ProofItem;
@@ -19,6 +20,9 @@ pub(crate) type SetupResult<T> = Result<T, FriSetupError>;
 pub(crate) type ProverResult<T> = Result<T, FriProvingError>;
 pub(crate) type VerifierResult<T> = Result<T, FriValidationError>;
 
+/// Indices into a codeword, together with the codeword's elements at those indices.
+type PartiallyRevealedCodeword = Vec<(usize, XFieldElement)>;
+
 pub type AuthenticationStructure = Vec<Digest>;
 
 #[derive(Debug, Copy, Clone)]
@@ -333,12 +337,13 @@ impl<'stream> FriVerifier<'stream> {
         let leaf_indices = self.collinearity_check_a_indices_for_round(0);
         let indexed_leafs = leaf_indices.into_iter().zip_eq(revealed_digests).collect();
 
-        let inclusion_proof = MerkleTreeInclusionProof {
-            tree_height: round.merkle_tree_height(),
+        let tree_height = round.merkle_tree_height();
+        match merkle::verify_inclusion(
+            round.merkle_root,
+            tree_height,
             indexed_leafs,
             authentication_structure,
-        };
-        match inclusion_proof.verify(round.merkle_root) {
+        ) {
             true => Ok(()),
             false => Err(BadMerkleAuthenticationPath),
         }
@@ -356,12 +361,13 @@ impl<'stream> FriVerifier<'stream> {
         let leaf_indices = self.collinearity_check_b_indices_for_round(round_number);
         let indexed_leafs = leaf_indices.into_iter().zip_eq(revealed_digests).collect();
 
-        let inclusion_proof = MerkleTreeInclusionProof {
-            tree_height: round.merkle_tree_height(),
+        let tree_height = round.merkle_tree_height();
+        match merkle::verify_inclusion(
+            round.merkle_root,
+            tree_height,
             indexed_leafs,
             authentication_structure,
-        };
-        match inclusion_proof.verify(round.merkle_root) {
+        ) {
             true => Ok(()),
             false => Err(BadMerkleAuthenticationPath),
         }
@@ -477,7 +483,7 @@ impl<'stream> FriVerifier<'stream> {
         }
 
         let indeterminate = self.proof_stream.sample_scalars(1)[0];
-        let horner_evaluation = self.last_round_polynomial.evaluate(indeterminate);
+        let horner_evaluation: XFieldElement = self.last_round_polynomial.evaluate(indeterminate);
         let barycentric_evaluation = barycentric_evaluate(&self.last_round_codeword, indeterminate);
         if horner_evaluation != barycentric_evaluation {
             return Err(LastRoundPolynomialEvaluationMismatch);
@@ -557,6 +563,18 @@ impl Fri {
         &self,
         proof_stream: &mut ProofStream,
     ) -> VerifierResult<Vec<(usize, XFieldElement)>> {
+        let (revealed_codeword, _) = self.verify_and_report_query_indices(proof_stream)?;
+        Ok(revealed_codeword)
+    }
+
+    /// Like [`verify`](Self::verify), but additionally returns the indices of the codeword
+    /// the verifier queried in each round, starting with the first round. For every round,
+    /// these are the indices of the collinearity checks' “a” points, exactly as used by the
+    /// verifier. The “b” points lie half the round's domain length further.
+    pub(crate) fn verify_and_report_query_indices(
+        &self,
+        proof_stream: &mut ProofStream,
+    ) -> VerifierResult<(PartiallyRevealedCodeword, Vec<Vec<usize>>)> {
         profiler!(start "init");
         let mut verifier = self.verifier(proof_stream);
         verifier.initialize()?;
@@ -570,7 +588,13 @@ impl Fri {
         verifier.authenticate_last_round_codeword()?;
         profiler!(stop "authenticate last round codeword");
 
-        Ok(verifier.first_round_partially_revealed_codeword())
+        let query_indices = (0..verifier.rounds.len())
+            .map(|round_number| verifier.collinearity_check_a_indices_for_round(round_number))
+            .collect();
+        Ok((
+            verifier.first_round_partially_revealed_codeword(),
+            query_indices,
+        ))
     }
 
     fn verifier<'stream>(&'stream self, proof_stream: &'stream mut ProofStream) -> FriVerifier {
@@ -769,6 +793,27 @@ mod tests {
         prop_assert!(verdict.is_ok());
     }
 
+    #[proptest(cases = 20)]
+    fn verifier_queries_indices_sampled_by_prover_in_every_round(
+        #[strategy(arbitrary_fri_supporting_degree(3))] fri: Fri,
+    ) {
+        let coefficients = [1, 0, 0, 2].map(|c| c.into()).to_vec();
+        let codeword = fri.domain.evaluate(&Polynomial::new(coefficients));
+        let mut proof_stream = ProofStream::new();
+        let prover_indices = fri.prove(&codeword, &mut proof_stream).unwrap();
+
+        let mut proof_stream = prepare_proof_stream_for_verification(proof_stream);
+        let (_, query_indices) = fri.verify_and_report_query_indices(&mut proof_stream)?;
+        prop_assert_eq!(fri.num_rounds() + 1, query_indices.len());
+        prop_assert_eq!(&prover_indices, &query_indices[0]);
+
+        for (round_number, round_indices) in query_indices.iter().enumerate() {
+            let domain_length = fri.domain.length >> round_number;
+            let expected_indices = prover_indices.iter().map(|&i| i % domain_length);
+            prop_assert!(expected_indices.eq(round_indices.iter().copied()));
+        }
+    }
+
     #[proptest(cases = 50)]
     fn prove_and_fail_to_verify_high_degree_polynomial(
         fri: Fri,
@@ -1080,7 +1125,7 @@ mod tests {
         let polynomial = Polynomial::from(&coefficients);
         let codeword = domain.evaluate(&polynomial);
         prop_assert_eq!(
-            polynomial.evaluate(indeterminate),
+            polynomial.evaluate::<_, XFieldElement>(indeterminate),
             barycentric_evaluate(&codeword, indeterminate)
         );
     }
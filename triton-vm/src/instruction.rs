@@ -1,7 +1,11 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::iter::Sum;
+use std::ops::Add;
 use std::result;
 
 use arbitrary::Arbitrary;
@@ -15,6 +19,7 @@ use serde_derive::Serialize;
 use strum::EnumCount;
 use strum::EnumIter;
 use strum::IntoEnumIterator;
+use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 
 use AnInstruction::*;
@@ -24,6 +29,7 @@ use crate::instruction::InstructionBit::*;
 use crate::op_stack::NumberOfWords::*;
 use crate::op_stack::OpStackElement::*;
 use crate::op_stack::*;
+use crate::table::hash_table::PERMUTATION_TRACE_LENGTH;
 
 type Result<T> = result::Result<T, InstructionError>;
 
@@ -465,6 +471,44 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
                 | MerkleStepMem
         )
     }
+
+    /// The number of rows executing the instruction adds to the Processor Table. Every
+    /// instruction, including [`Halt`], occupies exactly one row.
+    pub const fn processor_rows(&self) -> usize {
+        1
+    }
+
+    /// The number of rows executing the instruction adds to the various tables, without running
+    /// the instruction. Useful for estimating the proving cost of a [`Program`](crate::Program)
+    /// through static analysis.
+    ///
+    /// The contributions to the Processor, Op Stack, RAM, and Hash Tables are exact. The
+    /// contribution to the U32 Table is an upper bound: the actual number of rows depends on
+    /// the operands, and identical U32 Table lookups share their rows.
+    pub const fn table_contributions(&self) -> TableContributions {
+        let op_stack = self.op_stack_size_influence().unsigned_abs() as usize;
+        let (ram, hash, u32_lookups) = match self {
+            ReadMem(n) | WriteMem(n) => (n.num_words(), 0, 0),
+            Hash | SpongeAbsorb | SpongeSqueeze => (0, PERMUTATION_TRACE_LENGTH, 0),
+            SpongeInit => (0, 1, 0),
+            SpongeAbsorbMem => (tip5::RATE, PERMUTATION_TRACE_LENGTH, 0),
+            Split | Lt | And | Xor | Log2Floor | Pow | PopCount => (0, 0, 1),
+            DivMod => (0, 0, 2),
+            MerkleStep => (0, PERMUTATION_TRACE_LENGTH, 1),
+            MerkleStepMem => (Digest::LEN, PERMUTATION_TRACE_LENGTH, 1),
+            XxDotStep => (2 * EXTENSION_DEGREE, 0, 0),
+            XbDotStep => (1 + EXTENSION_DEGREE, 0, 0),
+            _ => (0, 0, 0),
+        };
+
+        TableContributions {
+            processor: self.processor_rows(),
+            op_stack,
+            ram,
+            hash,
+            u32: u32_lookups * MAX_U32_TABLE_ROWS_PER_LOOKUP,
+        }
+    }
 }
 
 impl<Dest: Display + PartialEq + Default> Display for AnInstruction<Dest> {
@@ -523,6 +567,24 @@ impl Instruction {
     }
 }
 
+/// Instructions are ordered by their [opcode](AnInstruction::opcode), then by their
+/// [argument](Instruction::arg), if any.
+impl PartialOrd for Instruction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Instruction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |instruction: &Self| {
+            let arg = instruction.arg().map(|arg| arg.value());
+            (instruction.opcode(), arg)
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
 impl TryFrom<u32> for Instruction {
     type Error = InstructionError;
 
@@ -561,6 +623,97 @@ impl TryFrom<BFieldElement> for Instruction {
     }
 }
 
+/// The maximal number of rows a single lookup into the U32 Table can add to that table, which
+/// is the case if the dominant operand has all 32 bits set.
+const MAX_U32_TABLE_ROWS_PER_LOOKUP: usize = u32::BITS as usize + 1;
+
+/// The number of rows an [`Instruction`] adds to the tables relevant for proving the correct
+/// execution of a [`Program`](crate::Program). See
+/// [`Instruction::table_contributions`](AnInstruction::table_contributions).
+///
+/// Contributions can be summed up, for example over all instructions executed by a program.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub struct TableContributions {
+    pub processor: usize,
+    pub op_stack: usize,
+    pub ram: usize,
+    pub hash: usize,
+    pub u32: usize,
+}
+
+impl Add for TableContributions {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            processor: self.processor + rhs.processor,
+            op_stack: self.op_stack + rhs.op_stack,
+            ram: self.ram + rhs.ram,
+            hash: self.hash + rhs.hash,
+            u32: self.u32 + rhs.u32,
+        }
+    }
+}
+
+impl Sum for TableContributions {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// A set of [`Instruction`]s, identified by their opcode. The arguments of instructions are
+/// irrelevant for membership, _i.e._, `push 0` and `push 42` are the same element.
+///
+/// Can be used to restrict which instructions a [`Program`](crate::Program) may use, see
+/// [`Program::check_allowed()`](crate::Program::check_allowed).
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct InstructionSet {
+    opcodes: HashSet<u32>,
+}
+
+impl InstructionSet {
+    /// The empty set of instructions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The set of all instructions in Triton VM's instruction set architecture.
+    pub fn all() -> Self {
+        ALL_INSTRUCTIONS.into_iter().collect()
+    }
+
+    #[must_use]
+    pub fn with(mut self, instruction: Instruction) -> Self {
+        self.insert(instruction);
+        self
+    }
+
+    #[must_use]
+    pub fn without(mut self, instruction: Instruction) -> Self {
+        self.remove(instruction);
+        self
+    }
+
+    pub fn insert(&mut self, instruction: Instruction) {
+        self.opcodes.insert(instruction.opcode());
+    }
+
+    pub fn remove(&mut self, instruction: Instruction) {
+        self.opcodes.remove(&instruction.opcode());
+    }
+
+    pub fn contains(&self, instruction: Instruction) -> bool {
+        self.opcodes.contains(&instruction.opcode())
+    }
+}
+
+impl FromIterator<Instruction> for InstructionSet {
+    fn from_iter<I: IntoIterator<Item = Instruction>>(iter: I) -> Self {
+        let opcodes = iter.into_iter().map(|i| i.opcode()).collect();
+        Self { opcodes }
+    }
+}
+
 /// A list of all instructions with default arguments, if any.
 const fn all_instructions_with_default_args() -> [AnInstruction<BFieldElement>; Instruction::COUNT]
 {
@@ -787,12 +940,18 @@ pub mod tests {
     use strum::VariantNames;
     use twenty_first::prelude::*;
 
+    use crate::example_programs::FIBONACCI_SEQUENCE;
     use crate::instruction::*;
     use crate::op_stack::NUM_OP_STACK_REGISTERS;
     use crate::program::PublicInput;
+    use crate::shared_tests::ProgramAndInput;
+    use crate::table::master_table::TableId;
     use crate::triton_asm;
     use crate::triton_program;
     use crate::vm::tests::test_program_for_call_recurse_return;
+    use crate::vm::tests::test_program_for_div_mod;
+    use crate::vm::tests::test_program_for_many_sponge_instructions;
+    use crate::vm::tests::test_program_for_merkle_step_mem_left_sibling;
     use crate::vm::VMState;
     use crate::NonDeterminism;
     use crate::Program;
@@ -948,6 +1107,18 @@ pub mod tests {
         assert!(Nop.change_arg(bfe!(7)).is_err());
     }
 
+    #[test]
+    fn instructions_are_ordered_by_opcode_then_by_argument() {
+        assert!(Push(bfe!(0)) < Pop(N1));
+        assert!(Push(bfe!(3)) < Push(bfe!(7)));
+        assert!(Push(bfe!(-1)) > Push(bfe!(7)));
+        assert!(Dup(ST3) < Dup(ST14));
+
+        let sorted_instructions = ALL_INSTRUCTIONS.into_iter().sorted().collect_vec();
+        let opcodes = sorted_instructions.iter().map(|i| i.opcode()).collect_vec();
+        assert!(opcodes.is_sorted());
+    }
+
     #[test]
     fn print_various_instructions() {
         println!("instruction_push: {:?}", Instruction::Push(bfe!(7)));
@@ -956,6 +1127,21 @@ pub mod tests {
         println!("instruction_dup: {}", Instruction::Dup(ST14));
     }
 
+    #[test]
+    fn instruction_set_ignores_arguments() {
+        let instruction_set = InstructionSet::new().with(Push(bfe!(42)));
+        assert!(instruction_set.contains(Push(bfe!(0))));
+        assert!(!instruction_set.contains(Pop(N1)));
+    }
+
+    #[test]
+    fn instruction_set_of_all_instructions_contains_every_instruction() {
+        let instruction_set = InstructionSet::all();
+        for instruction in ALL_INSTRUCTIONS {
+            assert!(instruction_set.contains(instruction));
+        }
+    }
+
     #[test]
     fn instruction_size_is_consistent_with_having_arguments() {
         for instruction in Instruction::iter() {
@@ -1063,6 +1249,59 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn instructions_contribute_to_tables_as_indicated() {
+        for test_instruction in all_instructions_with_default_args() {
+            let (program, _) = construct_test_program_for_instruction(test_instruction);
+            let public_input = PublicInput::from(bfe_array![0]);
+            let mock_digests = [Digest::default()];
+            let non_determinism = NonDeterminism::from(bfe_array![0]).with_digests(mock_digests);
+            let program_and_input = ProgramAndInput::new(program)
+                .with_input(public_input)
+                .with_non_determinism(non_determinism);
+            assert_table_contributions_agree_with_execution(&program_and_input);
+        }
+    }
+
+    #[test]
+    fn table_contributions_of_known_programs_agree_with_execution() {
+        let programs = [
+            test_program_for_call_recurse_return(),
+            test_program_for_merkle_step_mem_left_sibling(),
+            test_program_for_many_sponge_instructions(),
+            test_program_for_div_mod(),
+            ProgramAndInput::new(FIBONACCI_SEQUENCE.clone()).with_input(bfe_array![23]),
+        ];
+        for program_and_input in programs {
+            assert_table_contributions_agree_with_execution(&program_and_input);
+        }
+    }
+
+    fn assert_table_contributions_agree_with_execution(program_and_input: &ProgramAndInput) {
+        let program = &program_and_input.program;
+        let public_input = program_and_input.public_input();
+        let non_determinism = program_and_input.non_determinism();
+
+        let mut vm_state = VMState::new(program, public_input.clone(), non_determinism.clone());
+        let mut executed_instructions = vec![];
+        while !vm_state.halting {
+            executed_instructions.push(vm_state.current_instruction().unwrap());
+            let_assert!(Ok(_) = vm_state.step());
+        }
+        let contributions = executed_instructions
+            .iter()
+            .map(|instruction| instruction.table_contributions())
+            .sum::<TableContributions>();
+
+        let_assert!(Ok((aet, _)) = program.trace_execution(public_input, non_determinism));
+        let program_hash_rows = aet.program_hash_trace.nrows();
+        assert!(aet.height_of_table(TableId::Processor) == contributions.processor);
+        assert!(aet.height_of_table(TableId::OpStack) == contributions.op_stack);
+        assert!(aet.height_of_table(TableId::Ram) == contributions.ram);
+        assert!(aet.height_of_table(TableId::Hash) == program_hash_rows + contributions.hash);
+        assert!(aet.height_of_table(TableId::U32) <= contributions.u32);
+    }
+
     #[test]
     fn labelled_instructions_act_on_op_stack_as_indicated() {
         for instruction in all_instructions_with_default_args() {
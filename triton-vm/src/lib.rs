@@ -163,6 +163,9 @@
 
 pub use twenty_first;
 
+// Allows code generated by `#[derive(TritonIO)]` to refer to `::triton_vm` from within this crate.
+extern crate self as triton_vm;
+
 use crate::error::ProvingError;
 use crate::prelude::*;
 
@@ -174,6 +177,7 @@ pub mod error;
 pub mod example_programs;
 pub mod fri;
 pub mod instruction;
+pub mod merkle;
 mod ndarray_helper;
 pub mod op_stack;
 pub mod parser;
@@ -507,7 +511,8 @@ macro_rules! triton_instr {
 ///
 /// The program executed by Triton VM must terminate gracefully, i.e., with instruction `halt`.
 /// If the program crashes, _e.g._, due to an out-of-bounds instruction pointer or a failing
-/// `assert` instruction, proof generation will fail.
+/// `assert` instruction, proof generation will fail. Proof generation also fails if the program
+/// does not halt within [`DEFAULT_MAX_NUM_CYCLES`](stark::DEFAULT_MAX_NUM_CYCLES) clock cycles.
 ///
 /// The default STARK parameters used by Triton VM give a (conjectured) security level of 160 bits.
 pub fn prove_program(
@@ -525,8 +530,13 @@ pub fn prove_program(
     // - if the program does not terminate gracefully, _i.e._, with instruction `halt`,
     // - if any of the two inputs does not conform to the program,
     // - because of a bug in the program, among other things.
-    // If the VM crashes, proof generation will fail.
-    let (aet, public_output) = program.trace_execution(public_input.clone(), non_determinism)?;
+    // If the VM crashes, proof generation will fail. Proof generation also fails if the program
+    // does not halt within a generous but finite number of clock cycles.
+    let (aet, public_output) = program.trace_execution_with_budget(
+        public_input.clone(),
+        non_determinism,
+        stark::DEFAULT_MAX_NUM_CYCLES,
+    )?;
 
     // Set up the claim that is to be proven. The claim contains all public information. The
     // proof is zero-knowledge with respect to everything else.
@@ -538,6 +548,7 @@ pub fn prove_program(
         program_digest: program.hash(),
         input: public_input.individual_tokens,
         output: public_output,
+        version: Claim::current_version(),
     };
 
     // The default parameters give a (conjectured) security level of 160 bits.
@@ -551,6 +562,9 @@ pub fn prove_program(
 
 /// A convenience function for proving a [`Claim`] and the program that claim corresponds to.
 /// Method [`prove_program`] gives a simpler interface with less control.
+///
+/// Proving fails if the program does not halt within the given [`Stark`]'s
+/// [maximum number of clock cycles](Stark::max_num_cycles).
 pub fn prove(
     stark: Stark,
     claim: &Claim,
@@ -561,7 +575,11 @@ pub fn prove(
     if program_digest != claim.program_digest {
         return Err(ProvingError::ProgramDigestMismatch);
     }
-    let (aet, public_output) = program.trace_execution((&claim.input).into(), non_determinism)?;
+    let (aet, public_output) = program.trace_execution_with_budget(
+        (&claim.input).into(),
+        non_determinism,
+        stark.max_num_cycles,
+    )?;
     if public_output != claim.output {
         return Err(ProvingError::PublicOutputMismatch);
     }
@@ -622,6 +640,7 @@ mod tests {
         implements_auto_traits::<Program>();
         implements_auto_traits::<PublicInput>();
         implements_auto_traits::<Claim>();
+        implements_auto_traits::<proof::ClaimBuilder>();
         implements_auto_traits::<Proof>();
         implements_auto_traits::<Stark>();
         implements_auto_traits::<VMState>();
@@ -631,10 +650,16 @@ mod tests {
         implements_auto_traits::<error::InstructionError>();
         implements_auto_traits::<error::ArithmeticDomainError>();
         implements_auto_traits::<error::ProofStreamError>();
+        implements_auto_traits::<error::ClaimDecodingError>();
+        implements_auto_traits::<error::ClaimBuildingError>();
         implements_auto_traits::<error::FriSetupError>();
         implements_auto_traits::<error::FriProvingError>();
         implements_auto_traits::<error::FriValidationError>();
         implements_auto_traits::<error::ProgramDecodingError>();
+        implements_auto_traits::<error::ProgramSizeError>();
+        implements_auto_traits::<error::ProgramFromCodeError>();
+        implements_auto_traits::<error::InstructionReplacementError>();
+        implements_auto_traits::<error::AlgebraicExecutionTraceDecodingError>();
         implements_auto_traits::<error::ProvingError>();
         implements_auto_traits::<error::VerificationError>();
         implements_auto_traits::<error::OpStackElementError>();
@@ -722,6 +747,7 @@ mod tests {
         implements_auto_traits::<proof_item::ProofItem>();
         implements_auto_traits::<proof_stream::ProofStream>();
         implements_auto_traits::<vm::CoProcessorCall>();
+        implements_auto_traits::<vm::StateReport>();
     }
 
     #[proptest]
@@ -813,6 +839,27 @@ mod tests {
         assert!(let ProvingError::PublicOutputMismatch = err);
     }
 
+    #[test]
+    fn lib_prove_non_halting_program_gives_appropriate_error() {
+        let program = triton_program! { call infinite_loop halt infinite_loop: recurse };
+        let claim = Claim::about_program(&program);
+
+        let stark = Stark::default().with_max_num_cycles(100);
+        let_assert!(Err(err) = prove(stark, &claim, &program, [].into()));
+        let_assert!(ProvingError::VMError(err) = err);
+        assert!(let InstructionError::ProgramDidNotHalt(100) = err.source);
+    }
+
+    #[test]
+    fn lib_prove_program_halting_within_cycle_budget() {
+        let program = triton_program!(push 1 assert halt);
+        let claim = Claim::about_program(&program);
+
+        let stark = Stark::default().with_max_num_cycles(3);
+        let proof = prove(stark, &claim, &program, [].into()).unwrap();
+        assert!(verify(stark, &claim, &proof));
+    }
+
     #[test]
     fn nested_triton_asm_interpolation() {
         let double_write = triton_asm![write_io 1; 2];
@@ -0,0 +1,138 @@
+//! Verification of membership in [Merkle trees](MerkleTree) built using [`Tip5`], the same
+//! verification the [STARK](crate::stark::Stark) verifier and [FRI](crate::fri::Fri) perform.
+
+use twenty_first::prelude::*;
+
+/// Whether the given leaf is at the given index of the [Merkle tree](MerkleTree) of the given
+/// height with the given root, as witnessed by the leaf's authentication path. The
+/// authentication path lists the siblings of the nodes on the path from the leaf to the root,
+/// starting with the leaf's sibling. Consequently, its length must equal the tree's height.
+///
+/// Any malformed input, like an index that is out of bounds or an authentication path of the
+/// wrong length, results in `false`.
+pub fn verify_merkle_membership(
+    root: Digest,
+    leaf: Digest,
+    index: usize,
+    auth_path: &[Digest],
+    tree_height: usize,
+) -> bool {
+    if auth_path.len() != tree_height {
+        return false;
+    }
+    verify_inclusion(root, tree_height, vec![(index, leaf)], auth_path.to_vec())
+}
+
+/// Whether the given leafs are at the given indices of the [Merkle tree](MerkleTree) of the
+/// given height with the given root, as witnessed by the de-duplicated
+/// [authentication structure](MerkleTree::authentication_structure).
+pub(crate) fn verify_inclusion(
+    root: Digest,
+    tree_height: usize,
+    indexed_leafs: Vec<(usize, Digest)>,
+    authentication_structure: Vec<Digest>,
+) -> bool {
+    let inclusion_proof = MerkleTreeInclusionProof {
+        tree_height,
+        indexed_leafs,
+        authentication_structure,
+    };
+    inclusion_proof.verify(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[derive(Debug, Clone, test_strategy::Arbitrary)]
+    struct MerkleMembership {
+        #[strategy(0..=8_usize)]
+        tree_height: usize,
+
+        #[strategy(vec(arb(), 1 << #tree_height))]
+        leafs: Vec<Digest>,
+
+        #[strategy(0..#leafs.len())]
+        index: usize,
+
+        #[strategy(Just(MerkleTree::new::<CpuParallel>(&#leafs).unwrap()))]
+        merkle_tree: MerkleTree,
+
+        #[strategy(Just(#merkle_tree.authentication_structure(&[#index]).unwrap()))]
+        auth_path: Vec<Digest>,
+    }
+
+    impl MerkleMembership {
+        fn verify(&self, index: usize, auth_path: &[Digest]) -> bool {
+            let root = self.merkle_tree.root();
+            let leaf = self.leafs[self.index];
+            verify_merkle_membership(root, leaf, index, auth_path, self.tree_height)
+        }
+    }
+
+    #[proptest]
+    fn honest_authentication_path_verifies(membership: MerkleMembership) {
+        prop_assert!(membership.verify(membership.index, &membership.auth_path));
+    }
+
+    #[proptest]
+    fn tampered_authentication_path_does_not_verify(
+        membership: MerkleMembership,
+        #[strategy(0..#membership.auth_path.len().max(1))] tampered_node: usize,
+        #[strategy(arb())] bogus_digest: Digest,
+    ) {
+        prop_assume!(!membership.auth_path.is_empty());
+        let mut auth_path = membership.auth_path.clone();
+        prop_assume!(auth_path[tampered_node] != bogus_digest);
+        auth_path[tampered_node] = bogus_digest;
+        prop_assert!(!membership.verify(membership.index, &auth_path));
+    }
+
+    #[proptest]
+    fn authentication_path_for_wrong_index_does_not_verify(
+        membership: MerkleMembership,
+        #[strategy(0..#membership.leafs.len())] wrong_index: usize,
+    ) {
+        let leaf = membership.leafs[membership.index];
+        prop_assume!(leaf != membership.leafs[wrong_index]);
+        prop_assert!(!membership.verify(wrong_index, &membership.auth_path));
+    }
+
+    #[proptest]
+    fn out_of_bounds_index_does_not_verify(membership: MerkleMembership) {
+        let out_of_bounds_index = membership.leafs.len() + membership.index;
+        prop_assert!(!membership.verify(out_of_bounds_index, &membership.auth_path));
+    }
+
+    #[proptest]
+    fn authentication_path_of_wrong_length_does_not_verify(
+        membership: MerkleMembership,
+        #[strategy(arb())] additional_digest: Digest,
+    ) {
+        let too_long_path = [membership.auth_path.clone(), vec![additional_digest]].concat();
+        prop_assert!(!membership.verify(membership.index, &too_long_path));
+
+        if let Some((_, too_short_path)) = membership.auth_path.split_last() {
+            prop_assert!(!membership.verify(membership.index, too_short_path));
+        }
+    }
+
+    #[test]
+    fn leaf_of_tree_of_height_zero_is_its_root() {
+        let leaf = Tip5::hash_varlen(&[bfe!(42)]);
+        assert!(verify_merkle_membership(leaf, leaf, 0, &[], 0));
+        assert!(!verify_merkle_membership(
+            leaf,
+            Digest::default(),
+            0,
+            &[],
+            0
+        ));
+    }
+}
@@ -34,6 +34,13 @@ pub const NUM_OP_STACK_REGISTERS: usize = OpStackElement::COUNT;
 /// and the op-stack underflow memory. The op-stack registers are the first
 /// [`OpStackElement::COUNT`] elements of the op-stack, and the op-stack underflow memory is the
 /// remaining elements.
+///
+/// The op-stack underflow memory is _not_ backed by random-access memory (RAM). It has its own
+/// address space, indexed by the [op-stack pointer](Self::pointer), and is arithmetized in a
+/// dedicated table. Consequently, no program – adversarial or otherwise – can cause op-stack
+/// underflow memory and RAM to collide, independent of the RAM addresses it writes to. Since the
+/// initial value of the op-stack pointer, [`OpStackElement::COUNT`], is fixed by the AIR, the
+/// location of the op-stack underflow memory is stable and not configurable.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
 // If the op stack is empty, things have gone horribly wrong. Suppressing this lint is preferred
 // to implementing a basically useless `is_empty()` method.
@@ -48,6 +48,29 @@ impl<'a> Display for ParseError<'a> {
 
 impl<'a> Error for ParseError<'a> {}
 
+impl<'a> ParseError<'a> {
+    /// The line and column, both starting at 1, of the earliest position in the input at which
+    /// an error was detected.
+    pub fn line_and_column(&self) -> (usize, usize) {
+        let input_start = self.input.as_ptr() as usize;
+        let offset = self
+            .errors
+            .errors
+            .iter()
+            .map(|(error_input, _)| error_input.as_ptr() as usize)
+            .filter_map(|error_start| error_start.checked_sub(input_start))
+            .filter(|&offset| offset <= self.input.len())
+            .min()
+            .unwrap_or(self.input.len());
+
+        let preceding_input = &self.input[..offset];
+        let line = preceding_input.matches('\n').count() + 1;
+        let line_start = preceding_input.rfind('\n').map_or(0, |i| i + 1);
+        let column = preceding_input[line_start..].chars().count() + 1;
+        (line, column)
+    }
+}
+
 impl<'a> InstructionToken<'a> {
     pub fn token_str(&self) -> &'a str {
         match self {
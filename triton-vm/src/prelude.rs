@@ -24,10 +24,14 @@ pub use crate::instruction::LabelledInstruction;
 pub use crate::program::NonDeterminism;
 pub use crate::program::Program;
 pub use crate::program::PublicInput;
+pub use crate::proof::BatchProof;
 pub use crate::proof::Claim;
 pub use crate::proof::Proof;
+pub use crate::proof::TritonIO;
 pub use crate::stark::Stark;
 pub use crate::triton_asm;
 pub use crate::triton_instr;
 pub use crate::triton_program;
+pub use crate::vm::ExecutionProfile;
+pub use crate::vm::StateDelta;
 pub use crate::vm::VMState;
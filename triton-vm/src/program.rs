@@ -1,14 +1,17 @@
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fs;
 use std::hash::Hash;
 use std::io::Cursor;
 use std::ops::Add;
 use std::ops::AddAssign;
 use std::ops::Sub;
+use std::path::Path;
 
 use arbitrary::Arbitrary;
 use get_size::GetSize;
@@ -18,14 +21,22 @@ use serde_derive::Serialize;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
+use crate::error::InstructionError;
+use crate::error::InstructionReplacementError;
+use crate::error::NondetIssue;
 use crate::error::ProgramDecodingError;
+use crate::error::ProgramFromCodeError;
+use crate::error::ProgramSizeError;
+use crate::error::TasmFileError;
 use crate::error::VMError;
 use crate::instruction::AnInstruction;
 use crate::instruction::Instruction;
+use crate::instruction::InstructionSet;
 use crate::instruction::LabelledInstruction;
 use crate::instruction::TypeHint;
 use crate::parser::parse;
 use crate::parser::to_labelled_instructions;
+use crate::parser::InstructionToken;
 use crate::parser::ParseError;
 use crate::profiler::profiler;
 use crate::table::hash_table::PERMUTATION_TRACE_LENGTH;
@@ -59,6 +70,10 @@ pub struct Program {
     address_to_label: HashMap<u64, String>,
     breakpoints: Vec<bool>,
     type_hints: HashMap<u64, Vec<TypeHint>>,
+
+    // Omitted if absent, keeping serialized programs compatible with versions that predate it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_map: Option<BTreeMap<u64, usize>>,
 }
 
 impl Display for Program {
@@ -98,7 +113,7 @@ impl BFieldCodec for Program {
         while read_idx < program_length {
             let opcode = sequence[read_idx];
             let mut instruction = Instruction::try_from(opcode)
-                .map_err(|err| Self::Error::InvalidInstruction(read_idx, err))?;
+                .map_err(|_| Self::Error::InvalidOpcode(read_idx, opcode))?;
             let instruction_has_arg = instruction.arg().is_some();
             if instruction_has_arg && instructions.len() + instruction.size() > program_length {
                 return Err(Self::Error::MissingArgument(read_idx, instruction));
@@ -126,6 +141,7 @@ impl BFieldCodec for Program {
             address_to_label: HashMap::default(),
             breakpoints: vec![],
             type_hints: HashMap::default(),
+            source_map: None,
         }))
     }
 
@@ -231,6 +247,7 @@ impl Program {
             address_to_label,
             breakpoints,
             type_hints,
+            source_map: None,
         }
     }
 
@@ -318,9 +335,224 @@ impl Program {
 
     /// Create a `Program` by parsing source code.
     pub fn from_code(code: &str) -> std::result::Result<Self, ParseError> {
-        parse(code)
-            .map(|tokens| to_labelled_instructions(&tokens))
-            .map(|instructions| Program::new(&instructions))
+        let tokens = parse(code)?;
+        let mut program = Program::new(&to_labelled_instructions(&tokens));
+        program.source_map = Some(Self::build_source_map(code, &tokens));
+        Ok(program)
+    }
+
+    /// Create a `Program` by reading and parsing the file at the given path, which contains
+    /// Triton assembly. Like [`from_code`](Self::from_code), but reports the location of any
+    /// parse error as line and column in the file.
+    pub fn from_tasm_file(path: impl AsRef<Path>) -> std::result::Result<Self, TasmFileError> {
+        let path = path.as_ref();
+        let code = fs::read_to_string(path).map_err(|source| TasmFileError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Self::from_code(&code).map_err(|err| {
+            let (line, column) = err.line_and_column();
+            TasmFileError::Parse {
+                path: path.to_path_buf(),
+                line,
+                column,
+                message: err.to_string(),
+            }
+        })
+    }
+
+    /// Like [`new`](Self::new), but rejects programs consisting of more than the given number
+    /// of instructions before assembling them. See also
+    /// [`with_max_instructions`](Self::with_max_instructions).
+    pub fn new_with_max_instructions(
+        labelled_instructions: &[LabelledInstruction],
+        max_num_instructions: usize,
+    ) -> std::result::Result<Self, ProgramSizeError> {
+        let num_instructions = labelled_instructions
+            .iter()
+            .filter(|instruction| matches!(instruction, LabelledInstruction::Instruction(_)))
+            .count();
+        Self::check_num_instructions(num_instructions, max_num_instructions)?;
+        Ok(Self::new(labelled_instructions))
+    }
+
+    /// Like [`from_code`](Self::from_code), but rejects programs consisting of more than the
+    /// given number of instructions before assembling them. See also
+    /// [`with_max_instructions`](Self::with_max_instructions).
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// let program = Program::from_code_with_max_instructions("push 1 push 2 add halt", 3);
+    /// assert!(program.is_err());
+    /// ```
+    pub fn from_code_with_max_instructions(
+        code: &str,
+        max_num_instructions: usize,
+    ) -> std::result::Result<Self, ProgramFromCodeError<'_>> {
+        let tokens = parse(code).map_err(ProgramFromCodeError::Parse)?;
+        let labelled_instructions = to_labelled_instructions(&tokens);
+        let mut program =
+            Self::new_with_max_instructions(&labelled_instructions, max_num_instructions)?;
+        program.source_map = Some(Self::build_source_map(code, &tokens));
+        Ok(program)
+    }
+
+    /// Limit the number of instructions the program may consist of. Helps catching mistakes like
+    /// accidentally assembling an absurdly large program early, instead of deep in proving.
+    /// Programs are not limited in size unless this method or one of the checked constructors
+    /// [`new_with_max_instructions`](Self::new_with_max_instructions) and
+    /// [`from_code_with_max_instructions`](Self::from_code_with_max_instructions) is used.
+    ///
+    /// Note that the limit refers to the number of instructions, not the number of
+    /// [words](Self::len_bwords), _i.e._, an instruction with an argument counts only once.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// let program = Program::from_code("push 1 push 2 add halt")
+    ///     .unwrap()
+    ///     .with_max_instructions(3);
+    /// assert!(program.is_err());
+    /// ```
+    pub fn with_max_instructions(
+        self,
+        max_num_instructions: usize,
+    ) -> std::result::Result<Self, ProgramSizeError> {
+        Self::check_num_instructions(self.num_instructions(), max_num_instructions)?;
+        Ok(self)
+    }
+
+    fn check_num_instructions(
+        num_instructions: usize,
+        max_num_instructions: usize,
+    ) -> std::result::Result<(), ProgramSizeError> {
+        if num_instructions > max_num_instructions {
+            let error =
+                ProgramSizeError::TooManyInstructions(num_instructions, max_num_instructions);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// The number of instructions, as opposed to the number of [words](Self::len_bwords).
+    fn num_instructions(&self) -> usize {
+        let mut num_instructions = 0;
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            num_instructions += 1;
+            address += instruction.size();
+        }
+        num_instructions
+    }
+
+    /// A copy of this program with the instruction at the given address replaced by the
+    /// given instruction. The address must point to the start of an instruction, not to
+    /// an instruction's argument.
+    ///
+    /// If the new instruction's size differs from the size of the replaced instruction,
+    /// all subsequent instructions move, and all labels are re-resolved accordingly. The
+    /// call target of a new [`call`](AnInstruction::Call) refers to the addresses of the
+    /// original program and must point to the start of an instruction, or to the end of
+    /// the program.
+    ///
+    /// Labels, breakpoints, and type hints are retained. The [source map](Self::source_map)
+    /// is not.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// # use triton_vm::instruction::Instruction;
+    /// let program = triton_program!(push 1 push 2 add halt);
+    /// let program = program.with_instruction_replaced(4, Instruction::Mul).unwrap();
+    /// assert!(triton_program!(push 1 push 2 mul halt) == program);
+    /// ```
+    pub fn with_instruction_replaced(
+        &self,
+        address: u64,
+        new_instruction: Instruction,
+    ) -> std::result::Result<Self, InstructionReplacementError> {
+        let program_length = self.instructions.len();
+        if address >= program_length as u64 {
+            return Err(InstructionReplacementError::AddressOutOfRange(
+                address,
+                program_length,
+            ));
+        }
+
+        let mut labelled_instructions = self.labelled_instructions();
+        if Self::index_of_instruction_at(&labelled_instructions, address).is_none() {
+            return Err(InstructionReplacementError::AddressNotAtInstructionStart(
+                address,
+            ));
+        }
+
+        if let Instruction::Call(target) = new_instruction {
+            let target = target.value();
+            let label = LabelledInstruction::Label(self.label_for_address(target));
+            if !labelled_instructions.contains(&label) {
+                let label_index =
+                    match Self::index_of_instruction_at(&labelled_instructions, target) {
+                        Some(index) => index,
+                        None if target == program_length as u64 => labelled_instructions.len(),
+                        None => return Err(InstructionReplacementError::InvalidCallTarget(target)),
+                    };
+                labelled_instructions.insert(label_index, label);
+            }
+        }
+
+        let new_instruction =
+            new_instruction.map_call_address(|&target| self.label_for_address(target.value()));
+        let index = Self::index_of_instruction_at(&labelled_instructions, address).unwrap();
+        labelled_instructions[index] = LabelledInstruction::Instruction(new_instruction);
+
+        Ok(Program::new(&labelled_instructions))
+    }
+
+    /// The index of the [`LabelledInstruction`] that is the instruction at the given
+    /// address, if any.
+    fn index_of_instruction_at(
+        labelled_instructions: &[LabelledInstruction],
+        address: u64,
+    ) -> Option<usize> {
+        let mut current_address = 0;
+        for (index, labelled_instruction) in labelled_instructions.iter().enumerate() {
+            let LabelledInstruction::Instruction(instruction) = labelled_instruction else {
+                continue;
+            };
+            if current_address == address {
+                return Some(index);
+            }
+            if current_address > address {
+                return None;
+            }
+            current_address += instruction.size() as u64;
+        }
+        None
+    }
+
+    /// Map the address of every instruction word to the (1-indexed) line of the source code
+    /// the instruction stems from.
+    fn build_source_map(code: &str, tokens: &[InstructionToken]) -> BTreeMap<u64, usize> {
+        let mut source_map = BTreeMap::new();
+        let mut address = 0;
+        let mut line = 1;
+        let mut offset = 0;
+        for token in tokens {
+            let InstructionToken::Instruction(instruction, token_str) = token else {
+                continue;
+            };
+
+            // the token's string is the remainder of the source code, starting at the token
+            let token_offset = code.len() - token_str.len();
+            line += code[offset..token_offset].matches('\n').count();
+            offset = token_offset;
+
+            for _ in 0..instruction.size() {
+                source_map.insert(address, line);
+                address += 1;
+            }
+        }
+
+        source_map
     }
 
     pub fn labelled_instructions(&self) -> Vec<LabelledInstruction> {
@@ -375,6 +607,69 @@ impl Program {
             .collect()
     }
 
+    /// Follow the control flow of the program from its entry point, accumulating the
+    /// non-determinism consumed by all instructions that are certainly executed.
+    ///
+    /// Calls are followed, unless the callee is already on the call stack. An instruction
+    /// preceded by `skiz` might be skipped; its demand is ignored. If that instruction is
+    /// `return`, `recurse`, or `recurse_or_return`, the rest of the current function might not
+    /// be executed, and the analysis continues at the return address.
+    fn non_determinism_demand(&self) -> NonDeterminismDemand {
+        let mut demand = NonDeterminismDemand::default();
+        let mut call_stack: Vec<(u64, u64)> = vec![];
+        let return_to_caller = |call_stack: &mut Vec<(u64, u64)>| {
+            call_stack.pop().map(|(_, return_address)| return_address)
+        };
+
+        let mut instruction_pointer = 0;
+        while let Some(&instruction) = self.instructions.get(instruction_pointer as usize) {
+            let next_instruction_pointer = instruction_pointer + instruction.size() as u64;
+            let maybe_next_instruction_pointer = match instruction {
+                Instruction::Halt => None,
+                Instruction::Divine(n) => {
+                    demand.num_individual_tokens += n.num_words();
+                    Some(next_instruction_pointer)
+                }
+                Instruction::MerkleStep => {
+                    demand.num_digests += 1;
+                    Some(next_instruction_pointer)
+                }
+                Instruction::Call(address) => {
+                    let callee = address.value();
+                    match call_stack.iter().any(|&(function, _)| function == callee) {
+                        true => Some(next_instruction_pointer),
+                        false => {
+                            call_stack.push((callee, next_instruction_pointer));
+                            Some(callee)
+                        }
+                    }
+                }
+                Instruction::Return | Instruction::Recurse | Instruction::RecurseOrReturn => {
+                    return_to_caller(&mut call_stack)
+                }
+                Instruction::Skiz => {
+                    match self.instructions.get(next_instruction_pointer as usize) {
+                        Some(
+                            Instruction::Return
+                            | Instruction::Recurse
+                            | Instruction::RecurseOrReturn,
+                        ) => return_to_caller(&mut call_stack),
+                        Some(skippable) => Some(next_instruction_pointer + skippable.size() as u64),
+                        None => None,
+                    }
+                }
+                _ => Some(next_instruction_pointer),
+            };
+
+            let Some(next_instruction_pointer) = maybe_next_instruction_pointer else {
+                break;
+            };
+            instruction_pointer = next_instruction_pointer;
+        }
+
+        demand
+    }
+
     pub fn is_breakpoint(&self, address: u64) -> bool {
         let address: usize = address.try_into().unwrap();
         self.breakpoints.get(address).unwrap_or(&false).to_owned()
@@ -384,6 +679,42 @@ impl Program {
         self.type_hints.get(&address).cloned().unwrap_or_default()
     }
 
+    /// The (1-indexed) line of source code every instruction word stems from, keyed by the
+    /// word's address. Only available for programs [parsed from source code][from_code];
+    /// `None` otherwise.
+    ///
+    /// [from_code]: Self::from_code
+    pub fn source_map(&self) -> Option<BTreeMap<u64, usize>> {
+        self.source_map.clone()
+    }
+
+    /// Create a `Program` from its [encoding](BFieldCodec::encode), given as a sequence of bytes.
+    /// Every 8 consecutive bytes are interpreted as one [`BFieldElement`] in little-endian
+    /// byte order, which must be canonical, _i.e._, smaller than [`BFieldElement::P`].
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, ProgramDecodingError> {
+        const WORD_SIZE: usize = std::mem::size_of::<u64>();
+        if !bytes.len().is_multiple_of(WORD_SIZE) {
+            return Err(ProgramDecodingError::ByteLengthNotMultipleOfWordSize(
+                bytes.len(),
+            ));
+        }
+
+        let bytes_to_word = |(index, chunk): (usize, &[u8])| {
+            let word = u64::from_le_bytes(chunk.try_into().unwrap());
+            match word < BFieldElement::P {
+                true => Ok(bfe!(word)),
+                false => Err(ProgramDecodingError::NonCanonicalWord(index, word)),
+            }
+        };
+        let words: Vec<_> = bytes
+            .chunks_exact(WORD_SIZE)
+            .enumerate()
+            .map(bytes_to_word)
+            .try_collect()?;
+
+        Ok(*Self::decode(&words)?)
+    }
+
     /// Turn the program into a sequence of `BFieldElement`s. Each instruction is encoded as its
     /// opcode, followed by its argument (if any).
     ///
@@ -420,6 +751,30 @@ impl Program {
         Tip5::hash_varlen(&self.to_bwords())
     }
 
+    /// Statically check that the program only uses instructions from the given
+    /// [`InstructionSet`]. Useful for restricted execution environments, for
+    /// example, to bound proving cost or to forbid non-determinism.
+    ///
+    /// Returns every instruction that is not allowed, together with its address.
+    pub fn check_allowed(
+        &self,
+        allowed: &InstructionSet,
+    ) -> std::result::Result<(), Vec<(u64, Instruction)>> {
+        let mut violations = vec![];
+        let mut address = 0;
+        for instruction in self.clone() {
+            if !allowed.contains(instruction) {
+                violations.push((address, instruction));
+            }
+            address += instruction.size() as u64;
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+
     /// Run Triton VM on the [`Program`] with the given public input and non-determinism.
     /// If an error is encountered, the returned [`VMError`] contains the [`VMState`] at the point
     /// of execution failure.
@@ -454,10 +809,23 @@ impl Program {
         &self,
         public_input: PublicInput,
         non_determinism: NonDeterminism,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        self.trace_execution_with_budget(public_input, non_determinism, u32::MAX)
+    }
+
+    /// Like [`trace_execution`][Self::trace_execution], but aborts execution if the program has
+    /// not halted after `max_num_cycles` clock cycles. In that case, the returned error's source
+    /// is [`InstructionError::ProgramDidNotHalt`].
+    pub fn trace_execution_with_budget(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        max_num_cycles: u32,
     ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
         profiler!(start "trace execution" ("gen"));
         let state = VMState::new(self, public_input, non_determinism);
-        let (aet, terminal_state) = self.trace_execution_of_state(state)?;
+        let (aet, terminal_state) =
+            self.trace_execution_of_state_with_budget(state, max_num_cycles)?;
         profiler!(stop "trace execution");
         Ok((aet, terminal_state.public_output))
     }
@@ -474,14 +842,26 @@ impl Program {
     /// - if the given [`VMState`] is not about to `self`
     /// - if the given [`VMState`] is incorrectly initialized
     pub fn trace_execution_of_state(
+        &self,
+        state: VMState,
+    ) -> Result<(AlgebraicExecutionTrace, VMState)> {
+        self.trace_execution_of_state_with_budget(state, u32::MAX)
+    }
+
+    fn trace_execution_of_state_with_budget(
         &self,
         mut state: VMState,
+        max_num_cycles: u32,
     ) -> Result<(AlgebraicExecutionTrace, VMState)> {
         let mut aet = AlgebraicExecutionTrace::new(self.clone());
         assert_eq!(self.instructions, state.program);
         assert_eq!(self.len_bwords(), aet.instruction_multiplicities.len());
 
         while !state.halting {
+            if state.cycle_count >= max_num_cycles {
+                let err = InstructionError::ProgramDidNotHalt(max_num_cycles);
+                return Err(VMError::new(err, state));
+            }
             if let Err(err) = aet.record_state(&state) {
                 return Err(VMError::new(err, state));
             };
@@ -544,6 +924,19 @@ impl Program {
             .cloned()
             .unwrap_or_else(|| format!("address_{address}"))
     }
+
+    /// All labels of the program and the addresses they resolve to, _i.e._, the address of the
+    /// instruction immediately following the label's declaration. The inverse of
+    /// [`label_for_address`][Self::label_for_address].
+    ///
+    /// If several labels resolve to the same address, only one of them is retained. Programs
+    /// that were [decoded](BFieldCodec::decode) have no labels.
+    pub fn labels(&self) -> BTreeMap<String, u64> {
+        self.address_to_label
+            .iter()
+            .map(|(&address, label)| (label.clone(), address))
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Arbitrary)]
@@ -895,6 +1288,76 @@ impl NonDeterminism {
         self.ram = ram.into();
         self
     }
+
+    /// Like [`with_ram`](Self::with_ram), but builds the initial RAM from (address, value)
+    /// pairs. If an address appears multiple times, the last value wins.
+    #[must_use]
+    pub fn with_ram_from<I>(self, ram: I) -> Self
+    where
+        I: IntoIterator<Item = (BFieldElement, BFieldElement)>,
+    {
+        self.with_ram(ram.into_iter().collect::<HashMap<_, _>>())
+    }
+
+    /// Extend the initial RAM by the given list, starting at `address`. Every element of the
+    /// list occupies [`EXTENSION_DEGREE`][degree] consecutive words, in the order of its
+    /// coefficients. Previously set values at the same addresses are overwritten.
+    ///
+    /// [degree]: twenty_first::math::x_field_element::EXTENSION_DEGREE
+    #[must_use]
+    pub fn with_ram_list_at<X>(
+        mut self,
+        address: BFieldElement,
+        list: impl IntoIterator<Item = X>,
+    ) -> Self
+    where
+        X: Into<XFieldElement>,
+    {
+        let words = list.into_iter().flat_map(|xfe| xfe.into().coefficients);
+        let addresses = (0..).map(|i| address + bfe!(i));
+        self.ram.extend(addresses.zip(words));
+        self
+    }
+
+    /// Check whether this non-determinism likely suffices for running the given program, before
+    /// actually running it. To this end, the program's demands are estimated statically by
+    /// following its control flow. The estimate is a lower bound: conditionally executed code
+    /// as well as repeated iterations of loops are not taken into account. Consequently, passing
+    /// validation does not guarantee that running the program succeeds, but every reported
+    /// issue causes any terminating execution of the program to fail.
+    pub fn validate_for(&self, program: &Program) -> std::result::Result<(), Vec<NondetIssue>> {
+        let demand = program.non_determinism_demand();
+
+        let mut issues = vec![];
+        if demand.num_individual_tokens > self.individual_tokens.len() {
+            issues.push(NondetIssue::TooFewIndividualTokens {
+                required: demand.num_individual_tokens,
+                supplied: self.individual_tokens.len(),
+            });
+        }
+        if demand.num_digests > self.digests.len() {
+            issues.push(NondetIssue::TooFewDigests {
+                required: demand.num_digests,
+                supplied: self.digests.len(),
+            });
+        }
+
+        match issues.is_empty() {
+            true => Ok(()),
+            false => Err(issues),
+        }
+    }
+}
+
+/// A lower bound on the amount of non-determinism any terminating execution of some
+/// [`Program`] consumes.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+struct NonDeterminismDemand {
+    /// The number of elements read using instruction `divine`.
+    num_individual_tokens: usize,
+
+    /// The number of digests read using instruction `merkle_step`.
+    num_digests: usize,
 }
 
 #[cfg(test)]
@@ -909,11 +1372,65 @@ mod tests {
 
     use crate::error::InstructionError;
     use crate::example_programs::CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS;
+    use crate::op_stack::NumberOfWords::N1;
+    use crate::op_stack::NumberOfWords::N2;
     use crate::table::master_table::TableId;
     use crate::triton_program;
 
     use super::*;
 
+    /// Write the given code to a fresh file in the system's temporary directory.
+    fn tasm_file_with_code(file_name: &str, code: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{file_name}", std::process::id()));
+        fs_err::write(&path, code).unwrap();
+        path
+    }
+
+    #[test]
+    fn program_from_tasm_file_is_identical_to_program_from_code() {
+        let code = "push 1\ncall foo\nhalt\nfoo:\n  push 2\n  add\n  return\n";
+        let path = tasm_file_with_code("valid.tasm", code);
+        let_assert!(Ok(program) = Program::from_tasm_file(&path));
+        assert!(Program::from_code(code).unwrap() == program);
+        fs_err::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn syntax_error_in_tasm_file_is_reported_with_line_and_column() {
+        let code = "push 1\npush 2\n  addd\nhalt\n";
+        let path = tasm_file_with_code("syntax_error.tasm", code);
+        let_assert!(Err(err) = Program::from_tasm_file(&path));
+        let_assert!(TasmFileError::Parse { line, column, .. } = &err);
+        assert!(3 == *line);
+        assert!(3 == *column);
+
+        let err_msg = err.to_string();
+        assert!(err_msg.contains("syntax_error.tasm:3:3"), "{err_msg}");
+        fs_err::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn missing_label_in_tasm_file_is_reported_with_line() {
+        let path = tasm_file_with_code("missing_label.tasm", "push 1\ncall bar\nhalt\n");
+        let_assert!(Err(TasmFileError::Parse { line, .. }) = Program::from_tasm_file(&path));
+        assert!(2 == line);
+        fs_err::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reading_missing_tasm_file_fails() {
+        let path = std::env::temp_dir().join("this_file_does_not_exist.tasm");
+        let_assert!(Err(err) = Program::from_tasm_file(&path));
+        let_assert!(
+            TasmFileError::Io {
+                path: err_path,
+                source
+            } = &err
+        );
+        assert!(&path == err_path);
+        assert!(std::io::ErrorKind::NotFound == source.kind());
+    }
+
     #[proptest]
     fn random_program_encode_decode_equivalence(#[strategy(arb())] program: Program) {
         let encoding = program.encode();
@@ -952,6 +1469,39 @@ mod tests {
         let_assert!(ProgramDecodingError::SequenceTooLong = err);
     }
 
+    #[test]
+    fn decode_program_with_invalid_opcode_reports_index_and_opcode() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut encoded = program.encode();
+        encoded[6] = bfe!(5);
+
+        let_assert!(Err(err) = Program::decode(&encoded));
+        let_assert!(ProgramDecodingError::InvalidOpcode(5, opcode) = err);
+        assert!(bfe!(5) == opcode);
+    }
+
+    #[test]
+    fn decode_program_with_out_of_range_opcode_reports_index_and_opcode() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut encoded = program.encode();
+        encoded[3] = bfe!(u64::MAX >> 1);
+
+        let_assert!(Err(err) = Program::decode(&encoded));
+        let_assert!(ProgramDecodingError::InvalidOpcode(2, opcode) = err);
+        assert!(bfe!(u64::MAX >> 1) == opcode);
+    }
+
+    #[test]
+    fn decode_truncated_program_reports_index_of_instruction_missing_its_argument() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let encoded = program.encode();
+        let mut truncated = encoded[..4].to_vec();
+        truncated[0] = bfe!(3);
+
+        let_assert!(Err(err) = Program::decode(&truncated));
+        let_assert!(ProgramDecodingError::MissingArgument(2, _) = err);
+    }
+
     #[test]
     fn decode_program_from_empty_sequence() {
         let encoded = vec![];
@@ -959,6 +1509,212 @@ mod tests {
         let_assert!(ProgramDecodingError::EmptySequence = err);
     }
 
+    fn program_to_bytes(program: &Program) -> Vec<u8> {
+        program
+            .encode()
+            .into_iter()
+            .flat_map(|word| word.value().to_le_bytes())
+            .collect()
+    }
+
+    #[proptest]
+    fn decode_program_from_bytes(#[strategy(arb())] program: Program) {
+        let decoded = Program::from_bytes(&program_to_bytes(&program)).unwrap();
+        prop_assert_eq!(program, decoded);
+    }
+
+    #[test]
+    fn decode_program_from_bytes_of_wrong_length() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut bytes = program_to_bytes(&program);
+        bytes.pop();
+
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramDecodingError::ByteLengthNotMultipleOfWordSize(55) = err);
+    }
+
+    #[test]
+    fn decode_program_from_bytes_containing_non_canonical_field_element() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut bytes = program_to_bytes(&program);
+        bytes[16..24].copy_from_slice(&BFieldElement::P.to_le_bytes());
+
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramDecodingError::NonCanonicalWord(2, BFieldElement::P) = err);
+    }
+
+    #[test]
+    fn program_using_only_allowed_instructions_passes_check() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        let allowed = InstructionSet::all().without(Instruction::Divine(N1));
+        assert!(Ok(()) == program.check_allowed(&allowed));
+    }
+
+    #[test]
+    fn forbidden_instruction_is_reported_with_its_address() {
+        let program = triton_program!(push 1 divine 2 add halt);
+        let allowed = InstructionSet::all().without(Instruction::Divine(N1));
+        let_assert!(Err(violations) = program.check_allowed(&allowed));
+        assert!(vec![(2, Instruction::Divine(N2))] == violations);
+    }
+
+    #[test]
+    fn program_within_instruction_limit_can_be_assembled() {
+        let program = Program::from_code("push 1 push 2 add halt").unwrap();
+        let_assert!(Ok(limited_program) = program.clone().with_max_instructions(4));
+        assert!(program == limited_program);
+    }
+
+    #[test]
+    fn program_exceeding_instruction_limit_gives_err() {
+        let program = Program::from_code("push 1 push 2 add halt").unwrap();
+        let_assert!(Err(err) = program.with_max_instructions(3));
+        assert!(ProgramSizeError::TooManyInstructions(4, 3) == err);
+    }
+
+    #[test]
+    fn checked_constructors_accept_program_within_instruction_limit() {
+        let code = "push 1 call foo halt foo: push 2 return";
+        let program = Program::from_code(code).unwrap();
+
+        let_assert!(Ok(limited_program) = Program::from_code_with_max_instructions(code, 5));
+        assert!(program == limited_program);
+        assert!(program.source_map() == limited_program.source_map());
+
+        let labelled_instructions = program.labelled_instructions();
+        let_assert!(
+            Ok(limited_program) = Program::new_with_max_instructions(&labelled_instructions, 5)
+        );
+        assert!(program == limited_program);
+    }
+
+    #[test]
+    fn checked_constructors_reject_program_exceeding_instruction_limit() {
+        let code = "push 1 call foo halt foo: push 2 return";
+        let expected_err = ProgramSizeError::TooManyInstructions(5, 4);
+
+        let_assert!(Err(err) = Program::from_code_with_max_instructions(code, 4));
+        let_assert!(ProgramFromCodeError::Size(err) = err);
+        assert!(expected_err == err);
+
+        let labelled_instructions = Program::from_code(code).unwrap().labelled_instructions();
+        let_assert!(Err(err) = Program::new_with_max_instructions(&labelled_instructions, 4));
+        assert!(expected_err == err);
+    }
+
+    #[test]
+    fn checked_constructor_reports_parse_errors() {
+        let_assert!(Err(err) = Program::from_code_with_max_instructions("push", 4));
+        let_assert!(ProgramFromCodeError::Parse(_) = err);
+    }
+
+    #[test]
+    fn replacing_instruction_with_instruction_of_same_size_keeps_addresses() {
+        let program = triton_program!(push 1 call foo halt foo: push 2 return);
+        let new_instruction = Instruction::Push(bfe!(3));
+        let_assert!(Ok(patched) = program.with_instruction_replaced(0, new_instruction));
+
+        let expected = triton_program!(push 3 call foo halt foo: push 2 return);
+        assert!(expected == patched);
+        assert!(program.labels() == patched.labels());
+    }
+
+    #[test]
+    fn replacing_instruction_with_instruction_of_different_size_re_resolves_labels() {
+        let program = triton_program!(push 1 call foo halt foo: push 2 return);
+        let_assert!(Ok(patched) = program.with_instruction_replaced(0, Instruction::Nop));
+
+        let expected = triton_program!(nop call foo halt foo: push 2 return);
+        assert!(expected == patched);
+        assert!(Some(&4) == patched.labels().get("foo"));
+        assert!(Instruction::Call(bfe!(4)) == patched.instructions[1]);
+    }
+
+    #[test]
+    fn replacing_instruction_by_call_to_unlabelled_address_introduces_label() {
+        let program = triton_program!(push 1 push 2 pop 2 halt);
+        let new_instruction = Instruction::Call(bfe!(6));
+        let_assert!(Ok(patched) = program.with_instruction_replaced(0, new_instruction));
+
+        let expected = triton_program!(call address_6 push 2 pop 2 address_6: halt);
+        assert!(expected == patched);
+        assert!(Instruction::Call(bfe!(6)) == patched.instructions[0]);
+    }
+
+    #[test]
+    fn replacing_instruction_at_invalid_address_gives_err() {
+        let program = triton_program!(push 1 push 2 add halt);
+
+        let_assert!(Err(err) = program.with_instruction_replaced(6, Instruction::Nop));
+        assert!(InstructionReplacementError::AddressOutOfRange(6, 6) == err);
+
+        let_assert!(Err(err) = program.with_instruction_replaced(1, Instruction::Nop));
+        assert!(InstructionReplacementError::AddressNotAtInstructionStart(1) == err);
+
+        let new_instruction = Instruction::Call(bfe!(3));
+        let_assert!(Err(err) = program.with_instruction_replaced(0, new_instruction));
+        assert!(InstructionReplacementError::InvalidCallTarget(3) == err);
+    }
+
+    #[test]
+    fn source_map_maps_every_instruction_word_to_its_line() {
+        let code = "
+            push 1 // line 2
+            pop 1
+
+            // line 5 is a comment
+            foo: call foo
+            nop nop
+            halt";
+        let program = Program::from_code(code).unwrap();
+        let_assert!(Some(source_map) = program.source_map());
+
+        let expected_lines = [2, 2, 3, 3, 6, 6, 7, 7, 8];
+        let expected: BTreeMap<_, _> = (0..).zip(expected_lines).collect();
+        assert!(expected == source_map);
+    }
+
+    #[test]
+    fn serialization_of_program_without_source_map_omits_it() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let serialized = serde_json::to_value(&program).unwrap();
+        assert!(serialized.get("source_map").is_none());
+
+        let deserialized: Program = serde_json::from_value(serialized).unwrap();
+        assert!(deserialized.source_map().is_none());
+    }
+
+    #[test]
+    fn source_map_survives_serialization_round_trip() {
+        let program = Program::from_code("push 1\npop 1\nhalt").unwrap();
+        let serialized = serde_json::to_string(&program).unwrap();
+        let deserialized: Program = serde_json::from_str(&serialized).unwrap();
+        assert!(program.source_map() == deserialized.source_map());
+    }
+
+    #[test]
+    fn program_not_parsed_from_source_code_has_no_source_map() {
+        let program = triton_program!(push 1 pop 1 halt);
+        assert!(program.source_map().is_none());
+
+        let program = Program::new(&program.labelled_instructions());
+        assert!(program.source_map().is_none());
+    }
+
+    #[test]
+    fn labels_map_to_address_of_following_instruction() {
+        let program = triton_program! {
+            call foo halt
+            foo: push 1
+            bar: pop 1 call baz return
+            baz: nop return
+        };
+        let expected: BTreeMap<_, _> = [("foo", 3), ("bar", 5), ("baz", 10)]
+            .map(|(label, address)| (label.to_string(), address))
+            .into();
+        assert!(expected == program.labels());
+    }
+
     #[test]
     fn hash_simple_program() {
         let program = triton_program!(halt);
@@ -1005,6 +1761,38 @@ mod tests {
         assert!(NonDeterminism::new(vec![]) == [].into());
     }
 
+    #[test]
+    fn later_ram_entries_overwrite_earlier_ones() {
+        let ram = [(bfe!(0), bfe!(1)), (bfe!(1), bfe!(2)), (bfe!(0), bfe!(3))];
+        let non_determinism = NonDeterminism::default().with_ram_from(ram);
+
+        let expected_ram = HashMap::from([(bfe!(0), bfe!(3)), (bfe!(1), bfe!(2))]);
+        assert!(expected_ram == non_determinism.ram);
+    }
+
+    #[test]
+    fn ram_list_is_flattened_into_consecutive_words() {
+        let list = [xfe!([1, 2, 3]), xfe!([4, 5, 6])];
+        let non_determinism = NonDeterminism::default().with_ram_list_at(bfe!(10), list);
+
+        let expected_ram = (10..16).zip(1..7).map(|(a, v)| (bfe!(a), bfe!(v)));
+        assert!(expected_ram.collect::<HashMap<_, _>>() == non_determinism.ram);
+    }
+
+    #[test]
+    fn overlapping_ram_lists_are_resolved_by_last_write() {
+        let non_determinism = NonDeterminism::default()
+            .with_ram_from([(bfe!(2), bfe!(100)), (bfe!(5), bfe!(200))])
+            .with_ram_list_at(bfe!(0), [xfe!([1, 2, 3]), xfe!([4, 5, 6])])
+            .with_ram_list_at(bfe!(4), [xfe!([7, 8, 9])]);
+
+        let expected_ram = (0..7)
+            .zip([1, 2, 3, 4, 7, 8, 9])
+            .map(|(a, v)| (bfe!(a), bfe!(v)))
+            .collect::<HashMap<_, _>>();
+        assert!(expected_ram == non_determinism.ram);
+    }
+
     #[test]
     fn create_program_from_code() {
         let element_3 = thread_rng().gen_range(0_u64..BFieldElement::P);
@@ -1116,4 +1904,59 @@ mod tests {
         let program = Program::decode(&encoding).unwrap();
         println!("{program}");
     }
+
+    #[test]
+    fn non_determinism_with_too_few_digests_is_reported() {
+        let program = triton_program! {
+            push 0 push 0 push 0 push 0 push 0 push 2
+            call step
+            call step
+            halt
+            step: merkle_step return
+        };
+        let non_determinism = NonDeterminism::default().with_digests([Digest::default()]);
+        let_assert!(Err(issues) = non_determinism.validate_for(&program));
+
+        let expected_issue = NondetIssue::TooFewDigests {
+            required: 2,
+            supplied: 1,
+        };
+        assert!(vec![expected_issue] == issues);
+    }
+
+    #[test]
+    fn sufficient_non_determinism_passes_validation() {
+        let program = triton_program! {
+            divine 3
+            call divine_twice
+            halt
+            divine_twice: divine 1 divine 1 return
+        };
+        let non_determinism = NonDeterminism::new(bfe_vec![0; 5]);
+        assert!(let Ok(()) = non_determinism.validate_for(&program));
+
+        let non_determinism = NonDeterminism::new(bfe_vec![0; 4]);
+        let_assert!(Err(issues) = non_determinism.validate_for(&program));
+        let expected_issue = NondetIssue::TooFewIndividualTokens {
+            required: 5,
+            supplied: 4,
+        };
+        assert!(vec![expected_issue] == issues);
+    }
+
+    #[test]
+    fn conditionally_executed_non_determinism_demand_is_ignored() {
+        let program = triton_program! {
+            read_io 1
+            skiz divine 1
+            call loop
+            halt
+            loop:
+                divine 2
+                dup 0 push 0 eq skiz return
+                divine 3
+                recurse
+        };
+        assert!(let Ok(()) = NonDeterminism::new(bfe_vec![0; 2]).validate_for(&program));
+    }
 }
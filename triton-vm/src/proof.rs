@@ -1,16 +1,32 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
 use arbitrary::Arbitrary;
 use get_size::GetSize;
 use itertools::Itertools;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoEnumIterator;
 use twenty_first::prelude::*;
 
+use crate::error::ClaimBuildingError;
+use crate::error::ClaimDecodingError;
+use crate::error::ClaimJsonError;
 use crate::error::ProofStreamError;
+use crate::error::TritonIOError;
 use crate::program::Program;
+use crate::proof_item::ProofItem;
+use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
 
 /// Contains the necessary cryptographic information to verify a computation.
 /// Should be used together with a [`Claim`].
+///
+/// A proof does not contain its claim, not even the [program digest](Claim::program_digest).
+/// The claim is only absorbed into the Fiat-Shamir state, from which it cannot be recovered.
+/// Consequently, the claim needs to be shipped alongside the proof, for example as part of a
+/// [`BatchProof`].
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, GetSize, BFieldCodec, Arbitrary)]
 pub struct Proof(pub Vec<BFieldElement>);
 
@@ -31,10 +47,196 @@ impl Proof {
         if log_2_padded_heights.len() > 1 {
             return Err(ProofStreamError::TooManyLog2PaddedHeights);
         }
-        Ok(1 << log_2_padded_heights[0])
+        padded_height_from_log2(log_2_padded_heights[0])
+    }
+
+    /// The decoded [proof items](ProofItem), in the order in which the prover enqueued them.
+    /// This is the transcript the Fiat-Shamir heuristic is applied to, which allows inspecting
+    /// the sequence of Merkle roots, out-of-domain values, _et cetera_, for example to replay
+    /// the transcript's absorptions without running the verifier. Note that not every item
+    /// [is absorbed](ProofItem::include_in_fiat_shamir_heuristic).
+    pub fn transcript_items(&self) -> Result<Vec<ProofItem>, ProofStreamError> {
+        let proof_stream = ProofStream::try_from(self)?;
+        Ok(proof_stream.items)
+    }
+
+    /// The [padded height](Self::padded_height), if it does not exceed the given `limit`.
+    /// Allows rejecting proofs from untrusted sources that are too expensive to verify before
+    /// spending any effort on their verification.
+    pub fn verify_padded_height_at_most(&self, limit: usize) -> Result<usize, ProofStreamError> {
+        let padded_height = self.padded_height()?;
+        if padded_height > limit {
+            return Err(ProofStreamError::PaddedHeightExceedsLimit {
+                padded_height,
+                limit,
+            });
+        }
+        Ok(padded_height)
+    }
+
+    /// The number of FRI rounds, _i.e._, the number of times the FRI codeword is folded,
+    /// as encoded in the proof. Like the [padded height](Self::padded_height), it is one of
+    /// the main contributing factors to the cost of verification.
+    ///
+    /// FRI reveals one response for the first round's codeword, plus one for each folded
+    /// codeword. Every one of these codewords is committed to using one Merkle root.
+    pub fn num_fri_rounds(&self) -> Result<usize, ProofStreamError> {
+        let proof_stream = ProofStream::try_from(self)?;
+        let num_items_of_variant = |variant| {
+            let items = proof_stream.items.iter();
+            items
+                .filter(|&item| ProofItemVariant::from(item) == variant)
+                .count()
+        };
+
+        let num_fri_responses = num_items_of_variant(ProofItemVariant::FriResponse);
+        if num_fri_responses == 0 {
+            return Err(ProofStreamError::NoFriResponse);
+        }
+
+        let num_merkle_roots = num_items_of_variant(ProofItemVariant::MerkleRoot);
+        let num_fri_merkle_roots = num_merkle_roots.saturating_sub(NUM_NON_FRI_MERKLE_ROOTS);
+        if num_fri_merkle_roots != num_fri_responses {
+            return Err(ProofStreamError::InconsistentFriRounds {
+                num_fri_responses,
+                num_fri_merkle_roots,
+            });
+        }
+
+        Ok(num_fri_responses - 1)
+    }
+
+    /// A human-readable summary of the proof: its padded height, its size, the number of
+    /// [proof items](crate::proof_item::ProofItem) of each kind, and the number of FRI rounds.
+    pub fn summary(&self) -> String {
+        let size = self.0.len();
+        let proof_stream = match ProofStream::try_from(self) {
+            Ok(proof_stream) => proof_stream,
+            Err(err) => return format!("malformed proof of size {size}: {err}"),
+        };
+
+        let padded_height = match self.padded_height() {
+            Ok(padded_height) => padded_height.to_string(),
+            Err(err) => format!("unknown ({err})"),
+        };
+        let num_items_of_variant = |variant| {
+            let items = proof_stream.items.iter();
+            items
+                .filter(|&item| ProofItemVariant::from(item) == variant)
+                .count()
+        };
+
+        let mut summary = format!("padded height: {padded_height}\n");
+        summary.push_str(&format!("size: {size} field elements\n"));
+        summary.push_str("proof items:\n");
+        for variant in ProofItemVariant::iter() {
+            let count = num_items_of_variant(variant);
+            if count > 0 {
+                summary.push_str(&format!("  {variant}: {count}\n"));
+            }
+        }
+
+        let num_fri_rounds = match self.num_fri_rounds() {
+            Ok(num_fri_rounds) => num_fri_rounds.to_string(),
+            Err(err) => format!("unknown ({err})"),
+        };
+        summary.push_str(&format!("FRI rounds: {num_fri_rounds}"));
+        summary
+    }
+
+    /// Write the proof's [encoding](BFieldCodec::encode) to the given writer. Every
+    /// [`BFieldElement`] is written as 8 bytes in little-endian byte order. In contrast to
+    /// encoding the proof first, no byte buffer the size of the entire proof is allocated.
+    ///
+    /// The inverse of [`read_from`](Self::read_from).
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let num_elements = self.0.len() as u64;
+        let encoding_length = num_elements + 1;
+        writer.write_all(&encoding_length.to_le_bytes())?;
+        writer.write_all(&num_elements.to_le_bytes())?;
+
+        let mut buffer = Vec::with_capacity(PROOF_IO_CHUNK_SIZE * WORD_SIZE);
+        for chunk in self.0.chunks(PROOF_IO_CHUNK_SIZE) {
+            buffer.clear();
+            buffer.extend(chunk.iter().flat_map(|word| word.value().to_le_bytes()));
+            writer.write_all(&buffer)?;
+        }
+        Ok(())
+    }
+
+    /// Read a proof written by [`write_to`](Self::write_to) from the given reader. The read
+    /// bytes are identical to the proof's [encoding](BFieldCodec::encode), with every
+    /// [`BFieldElement`] given as 8 bytes in little-endian byte order, which must be
+    /// canonical, _i.e._, smaller than [`BFieldElement::P`].
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let mut read_word = || -> io::Result<u64> {
+            let mut bytes = [0; WORD_SIZE];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let encoding_length = read_word()?;
+        let num_elements = read_word()?;
+        if encoding_length != num_elements.wrapping_add(1) {
+            return Err(invalid_data("inconsistent length indicators"));
+        }
+        let num_elements = usize::try_from(num_elements)
+            .map_err(|_| invalid_data("proof is too long for this platform"))?;
+
+        // The length indicator is untrusted; don't allocate more than one chunk up front.
+        let mut elements = Vec::with_capacity(num_elements.min(PROOF_IO_CHUNK_SIZE));
+        let mut buffer = vec![0; PROOF_IO_CHUNK_SIZE * WORD_SIZE];
+        while elements.len() < num_elements {
+            let chunk_size = (num_elements - elements.len()).min(PROOF_IO_CHUNK_SIZE);
+            let chunk = &mut buffer[..chunk_size * WORD_SIZE];
+            reader.read_exact(chunk)?;
+            for bytes in chunk.chunks_exact(WORD_SIZE) {
+                let word = u64::from_le_bytes(bytes.try_into().unwrap());
+                if word >= BFieldElement::P {
+                    return Err(invalid_data("non-canonical field element"));
+                }
+                elements.push(bfe!(word));
+            }
+        }
+        Ok(Proof(elements))
+    }
+}
+
+/// The number of bytes in the serialization of one [`BFieldElement`].
+const WORD_SIZE: usize = std::mem::size_of::<u64>();
+
+/// The number of [`BFieldElement`]s [`Proof::write_to`] and [`Proof::read_from`] process at a
+/// time.
+const PROOF_IO_CHUNK_SIZE: usize = 1 << 12;
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// The number of Merkle roots in a [`Proof`] that are not part of FRI: the commitments to the
+/// master base table, the master extension table, and the quotient segments.
+const NUM_NON_FRI_MERKLE_ROOTS: usize = 3;
+
+/// The largest base-2 logarithm of the padded height a [`Proof`] can declare. No trace of this
+/// length can actually be proven, since the corresponding [FRI domain][fri] would exceed the
+/// maximal length of any [`ArithmeticDomain`][domain]. Guards against arithmetic overflows when
+/// verifying malicious proofs.
+///
+/// [fri]: crate::fri::Fri
+/// [domain]: crate::arithmetic_domain::ArithmeticDomain
+pub const MAX_LOG2_PADDED_HEIGHT: u32 = 32;
+
+pub(crate) fn padded_height_from_log2(log_2_padded_height: u32) -> Result<usize, ProofStreamError> {
+    let too_large = ProofStreamError::Log2PaddedHeightTooLarge(log_2_padded_height);
+    if log_2_padded_height > MAX_LOG2_PADDED_HEIGHT {
+        return Err(too_large);
     }
+    1_usize.checked_shl(log_2_padded_height).ok_or(too_large)
 }
 
+/// The version of [Triton VM](crate)'s constraint system, as recorded in every new [`Claim`].
+/// Changes whenever a change to the constraint system invalidates previously generated proofs.
+const CLAIM_VERSION: u32 = 0;
+
 /// Contains the public information of a verifiably correct computation.
 /// A corresponding [`Proof`] is needed to verify the computation.
 /// One additional piece of public information not explicitly listed in the [`Claim`] is the
@@ -52,6 +254,11 @@ pub struct Claim {
 
     /// The public output of the computation.
     pub output: Vec<BFieldElement>,
+
+    /// The version of the constraint system the claim is about. Part of the Fiat-Shamir
+    /// transcript, which means that a proof only verifies against a claim of the version it
+    /// was generated for. Defaults to the [current version](Self::current_version).
+    pub version: u32,
 }
 
 impl Claim {
@@ -60,14 +267,47 @@ impl Claim {
             program_digest,
             input: vec![],
             output: vec![],
+            version: Self::current_version(),
         }
     }
 
+    /// Start building a [`Claim`] from public input and output given as plain integers. See
+    /// [`ClaimBuilder`].
+    pub fn builder() -> ClaimBuilder {
+        ClaimBuilder::default()
+    }
+
+    /// The version of the constraint system implemented by this version of
+    /// [Triton VM](crate).
+    pub const fn current_version() -> u32 {
+        CLAIM_VERSION
+    }
+
     #[must_use]
     pub fn about_program(program: &Program) -> Self {
         Self::new(program.hash())
     }
 
+    /// A claim about the given program with the given public input and output. The program
+    /// digest is [computed](Program::hash) from the program, which rules out putting a wrong
+    /// digest into the claim.
+    #[must_use]
+    pub fn for_program(
+        program: &Program,
+        input: Vec<BFieldElement>,
+        output: Vec<BFieldElement>,
+    ) -> Self {
+        Self::about_program(program)
+            .with_input(input)
+            .with_output(output)
+    }
+
+    /// Whether the claim is about the given program, _i.e._, whether the claim's program
+    /// digest equals the program's [Tip5](twenty_first::prelude::Tip5) [hash](Program::hash).
+    pub fn matches_program(&self, program: &Program) -> bool {
+        self.program_digest == program.hash()
+    }
+
     #[must_use]
     pub fn with_input(mut self, input: Vec<BFieldElement>) -> Self {
         self.input = input;
@@ -79,20 +319,470 @@ impl Claim {
         self.output = output;
         self
     }
+
+    /// Like [`with_input`](Self::with_input), but takes any type implementing
+    /// [`BFieldCodec`], for example a struct deriving it, and uses its encoding as the public
+    /// input. Recover the typed input using [`decode_input`](Self::decode_input).
+    #[must_use]
+    pub fn with_encoded_input<T: BFieldCodec>(self, input: &T) -> Self {
+        self.with_input(input.encode())
+    }
+
+    /// Like [`with_output`](Self::with_output), but takes any type implementing
+    /// [`BFieldCodec`]. See also [`with_encoded_input`](Self::with_encoded_input).
+    #[must_use]
+    pub fn with_encoded_output<T: BFieldCodec>(self, output: &T) -> Self {
+        self.with_output(output.encode())
+    }
+
+    /// Decode the public input as the given type. See also
+    /// [`with_encoded_input`](Self::with_encoded_input).
+    pub fn decode_input<T: BFieldCodec>(&self) -> Result<Box<T>, T::Error> {
+        T::decode(&self.input)
+    }
+
+    /// Decode the public output as the given type, for example, after the program was run.
+    /// See also [`with_encoded_output`](Self::with_encoded_output).
+    pub fn decode_output<T: BFieldCodec>(&self) -> Result<Box<T>, T::Error> {
+        T::decode(&self.output)
+    }
+
+    /// Like [`with_input`](Self::with_input), but takes any type implementing [`TritonIO`],
+    /// for example, a struct deriving it. Unlike [`with_encoded_input`][encoded], the fields
+    /// end up in the public input in the order of their declaration, ready to be read by the
+    /// program. Recover the typed input using [`typed_input`](Self::typed_input).
+    ///
+    /// [encoded]: Self::with_encoded_input
+    #[must_use]
+    pub fn with_typed_input<T: TritonIO>(self, input: &T) -> Self {
+        self.with_input(input.encode_io())
+    }
+
+    /// Like [`with_output`](Self::with_output), but takes any type implementing [`TritonIO`].
+    /// See also [`with_typed_input`](Self::with_typed_input).
+    #[must_use]
+    pub fn with_typed_output<T: TritonIO>(self, output: &T) -> Self {
+        self.with_output(output.encode_io())
+    }
+
+    /// Decode the public input as the given type. See also
+    /// [`with_typed_input`](Self::with_typed_input).
+    pub fn typed_input<T: TritonIO>(&self) -> Result<T, TritonIOError> {
+        T::decode_io(&self.input)
+    }
+
+    /// Decode the public output as the given type, for example, after the program was run.
+    /// See also [`with_typed_output`](Self::with_typed_output).
+    pub fn typed_output<T: TritonIO>(&self) -> Result<T, TritonIOError> {
+        T::decode_io(&self.output)
+    }
+
+    #[must_use]
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// A human-readable summary of the claim: the program digest in hexadecimal, and the
+    /// lengths and first few elements of the public input and output.
+    pub fn summary(&self) -> String {
+        const NUM_ELEMENTS_TO_SHOW: usize = 5;
+        let summarize = |list: &[BFieldElement]| {
+            let first_elements = list.iter().take(NUM_ELEMENTS_TO_SHOW).join(", ");
+            let ellipsis = if list.len() > NUM_ELEMENTS_TO_SHOW {
+                ", …"
+            } else {
+                ""
+            };
+            format!("{} elements: [{first_elements}{ellipsis}]", list.len())
+        };
+
+        format!(
+            "program digest: {:x}\ninput: {}\noutput: {}",
+            self.program_digest,
+            summarize(&self.input),
+            summarize(&self.output),
+        )
+    }
+
+    /// A commitment to the public input, namely its Tip5 hash. Used to check a claim against a
+    /// public input that was committed to elsewhere; see
+    /// [`Stark::prove_with_input_commitment`](crate::stark::Stark::prove_with_input_commitment).
+    pub fn input_commitment(&self) -> Digest {
+        Tip5::hash_varlen(&self.input)
+    }
+
+    /// The claim's canonical JSON representation. The program digest is given in hexadecimal,
+    /// the public input and output as arrays of decimal strings, one per canonical field
+    /// element. Unlike the [`Serialize`] implementation, whose layout follows the struct's
+    /// derive, this format is stable and meant for logging and cross-language tooling:
+    ///
+    /// ```json
+    /// {"program_digest":"…","input":["1","2"],"output":["3"],"version":0}
+    /// ```
+    ///
+    /// The inverse of [`from_json`](Self::from_json).
+    pub fn to_json(&self) -> String {
+        let to_strings =
+            |list: &[BFieldElement]| list.iter().map(|e| e.value().to_string()).collect();
+        let claim = ClaimJson {
+            program_digest: self.program_digest.to_hex(),
+            input: to_strings(&self.input),
+            output: to_strings(&self.output),
+            version: self.version,
+        };
+
+        serde_json::to_string(&claim).unwrap()
+    }
+
+    /// Parse a claim from its canonical JSON representation; see [`to_json`](Self::to_json).
+    /// Rejects public input or output elements that are not canonical field elements, as well
+    /// as unknown fields.
+    pub fn from_json(json: &str) -> Result<Self, ClaimJsonError> {
+        let claim: ClaimJson = serde_json::from_str(json)?;
+        let parse = |list: Vec<String>, to_err: fn(usize, _) -> ClaimJsonError| {
+            list.iter()
+                .enumerate()
+                .map(|(i, element)| element.parse().map_err(|err| to_err(i, err)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            program_digest: Digest::try_from_hex(claim.program_digest)?,
+            input: parse(claim.input, ClaimJsonError::InvalidInputElement)?,
+            output: parse(claim.output, ClaimJsonError::InvalidOutputElement)?,
+            version: claim.version,
+        })
+    }
+
+    /// Like [`decode`](BFieldCodec::decode), but rejects claims whose public input or public
+    /// output is longer than `max_io_len`. The declared lengths are checked before any list is
+    /// decoded, which makes this the method of choice for claims from untrusted sources.
+    pub fn decode_bounded(
+        sequence: &[BFieldElement],
+        max_io_len: usize,
+    ) -> Result<Box<Self>, ClaimDecodingError> {
+        // The version is encoded first, followed by the output and the input. Each list is
+        // prefixed by the length of its encoding, which in turn starts with the number of
+        // elements in the list.
+        let output_start = 1;
+        let Some((output_len, input_start)) = Self::declared_list_length(sequence, output_start)
+        else {
+            return Ok(Self::decode(sequence)?);
+        };
+        if output_len > max_io_len as u64 {
+            return Err(ClaimDecodingError::OutputTooLong(output_len, max_io_len));
+        }
+
+        let Some((input_len, _)) = Self::declared_list_length(sequence, input_start) else {
+            return Ok(Self::decode(sequence)?);
+        };
+        if input_len > max_io_len as u64 {
+            return Err(ClaimDecodingError::InputTooLong(input_len, max_io_len));
+        }
+
+        Ok(Self::decode(sequence)?)
+    }
+
+    /// The number of elements of the list whose encoding starts at the given index, as well as
+    /// the index directly after the list's encoding. `None` if the sequence is too short.
+    fn declared_list_length(sequence: &[BFieldElement], start: usize) -> Option<(u64, usize)> {
+        let encoding_len = usize::try_from(sequence.get(start)?.value()).ok()?;
+        let list_len = sequence.get(start.checked_add(1)?)?.value();
+        let next_start = start.checked_add(1)?.checked_add(encoding_len)?;
+        Some((list_len, next_start))
+    }
+}
+
+/// A type that can be passed to a program as public input, or be returned by it as public
+/// output. Usually [derived](macro@TritonIO) for structs all fields of which implement
+/// [`BFieldCodec`]: the fields are then encoded in the order of their declaration, allowing
+/// the program to `read_io` them one after the other. A field of statically known length is
+/// encoded as is; any other field is prefixed with the length of its encoding.
+///
+/// See also [`Claim::with_typed_input`] and [`Claim::typed_output`].
+///
+/// ```
+/// # use triton_vm::prelude::*;
+/// #[derive(Debug, PartialEq, TritonIO)]
+/// struct Sum {
+///     sum: BFieldElement,
+/// }
+///
+/// let program = triton_program!(read_io 2 add write_io 1 halt);
+/// let output = program.run(bfe_vec![3, 4].into(), [].into()).unwrap();
+/// let claim = Claim::about_program(&program).with_output(output);
+/// assert_eq!(Sum { sum: bfe!(7) }, claim.typed_output().unwrap());
+/// ```
+pub trait TritonIO: Sized {
+    /// The sequence of field elements representing `self` in public input or output.
+    fn encode_io(&self) -> Vec<BFieldElement>;
+
+    /// Decode `Self` from the start of the given sequence. Also returns the rest of the
+    /// sequence.
+    fn decode_io_prefix(
+        sequence: &[BFieldElement],
+    ) -> Result<(Self, &[BFieldElement]), TritonIOError>;
+
+    /// Decode `Self` from the given sequence, which must not contain any further elements.
+    fn decode_io(sequence: &[BFieldElement]) -> Result<Self, TritonIOError> {
+        let (decoded, rest) = Self::decode_io_prefix(sequence)?;
+        if !rest.is_empty() {
+            return Err(TritonIOError::TrailingElements(rest.len()));
+        }
+        Ok(decoded)
+    }
+}
+
+pub use triton_io_derive::TritonIO;
+
+/// Append the encoding of one field of a struct [deriving](macro@TritonIO) [`TritonIO`] to the
+/// given sequence. Not part of the public API; only used by the derive macro.
+#[doc(hidden)]
+pub fn encode_io_field<T: BFieldCodec>(field: &T, sequence: &mut Vec<BFieldElement>) {
+    let encoding = field.encode();
+    if T::static_length().is_none() {
+        sequence.push(bfe!(encoding.len() as u64));
+    }
+    sequence.extend(encoding);
+}
+
+/// Decode one field of a struct [deriving](macro@TritonIO) [`TritonIO`] from the start of the
+/// given sequence, returning the rest of the sequence. Not part of the public API; only used by
+/// the derive macro.
+#[doc(hidden)]
+pub fn decode_io_field<'s, T: BFieldCodec>(
+    sequence: &'s [BFieldElement],
+    field_name: &'static str,
+) -> Result<(T, &'s [BFieldElement]), TritonIOError> {
+    let (length, sequence) = match T::static_length() {
+        Some(length) => (length, sequence),
+        None => {
+            let (length, sequence) = sequence
+                .split_first()
+                .ok_or(TritonIOError::MissingField(field_name))?;
+            let length = usize::try_from(length.value())
+                .map_err(|_| TritonIOError::FieldTooLong(field_name))?;
+            (length, sequence)
+        }
+    };
+    if sequence.len() < length {
+        return match sequence.is_empty() {
+            true => Err(TritonIOError::MissingField(field_name)),
+            false => Err(TritonIOError::FieldTooLong(field_name)),
+        };
+    }
+
+    let (encoding, sequence) = sequence.split_at(length);
+    let field = T::decode(encoding).map_err(|_| TritonIOError::InvalidField(field_name))?;
+    Ok((*field, sequence))
+}
+
+/// The layout of a [`Claim`]'s [canonical JSON representation](Claim::to_json).
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClaimJson {
+    program_digest: String,
+    input: Vec<String>,
+    output: Vec<String>,
+    version: u32,
+}
+
+/// Builds a [`Claim`] from a program digest and public input and output, converting the latter
+/// from any type that converts losslessly into a [`u64`], like [`u32`], [`u64`], or
+/// [`BFieldElement`].
+///
+/// Unlike the conversion from [`u64`] into [`BFieldElement`], which silently reduces modulo the
+/// field's prime, [building](Self::build) rejects values that are not canonical field elements.
+///
+/// ```
+/// # use triton_vm::prelude::*;
+/// let program = triton_program!(read_io 1 write_io 1 halt);
+/// let claim = Claim::builder()
+///     .with_program_digest(program.hash())
+///     .with_input([42_u32])
+///     .with_output([42_u64])
+///     .build()
+///     .unwrap();
+/// assert_eq!(bfe_vec![42], claim.output);
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ClaimBuilder {
+    program_digest: Option<Digest>,
+    input: Vec<u64>,
+    output: Vec<u64>,
+}
+
+impl ClaimBuilder {
+    #[must_use]
+    pub fn with_program_digest(mut self, program_digest: Digest) -> Self {
+        self.program_digest = Some(program_digest);
+        self
+    }
+
+    #[must_use]
+    pub fn with_input(mut self, input: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+        self.input = input.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_output(mut self, output: impl IntoIterator<Item = impl Into<u64>>) -> Self {
+        self.output = output.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Errors if the program digest was not set, or if any element of the public input or
+    /// output is not smaller than the field's [prime](BFieldElement::P).
+    pub fn build(self) -> Result<Claim, ClaimBuildingError> {
+        let program_digest = self
+            .program_digest
+            .ok_or(ClaimBuildingError::MissingProgramDigest)?;
+        let input = Self::canonical_elements(&self.input)
+            .map_err(|(i, v)| ClaimBuildingError::NonCanonicalInputElement(i, v))?;
+        let output = Self::canonical_elements(&self.output)
+            .map_err(|(i, v)| ClaimBuildingError::NonCanonicalOutputElement(i, v))?;
+
+        Ok(Claim::new(program_digest)
+            .with_input(input)
+            .with_output(output))
+    }
+
+    /// The given values as field elements, or the index and value of the first value that is
+    /// not a canonical field element.
+    fn canonical_elements(values: &[u64]) -> Result<Vec<BFieldElement>, (usize, u64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| match value < BFieldElement::P {
+                true => Ok(BFieldElement::new(value)),
+                false => Err((i, value)),
+            })
+            .collect()
+    }
+}
+
+/// Proofs of several independent executions of the same program. The digest of the shared
+/// program is stored only once, making a [`BatchProof`] smaller than the corresponding
+/// collection of [`Claim`]s and [`Proof`]s.
+///
+/// Generated by [`Stark::prove_shared_program`][prove] and verified by
+/// [`Stark::verify_batch`][verify].
+///
+/// [prove]: crate::stark::Stark::prove_shared_program
+/// [verify]: crate::stark::Stark::verify_batch
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, GetSize, BFieldCodec, Arbitrary)]
+pub struct BatchProof {
+    /// The hash digest of the program that was executed in every instance.
+    pub program_digest: Digest,
+
+    /// The proven executions, in the order they were given to the prover.
+    pub instances: Vec<BatchedInstance>,
+}
+
+/// One execution of the program shared by all instances of a [`BatchProof`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, GetSize, BFieldCodec, Arbitrary)]
+pub struct BatchedInstance {
+    /// The public input to the execution.
+    pub input: Vec<BFieldElement>,
+
+    /// The public output of the execution.
+    pub output: Vec<BFieldElement>,
+
+    /// The proof for the execution's [claim](BatchProof::claims).
+    pub proof: Proof,
+}
+
+impl BatchProof {
+    /// The [`Claim`] of each instance, in the order of the instances.
+    pub fn claims(&self) -> Vec<Claim> {
+        self.instances
+            .iter()
+            .map(|instance| {
+                Claim::new(self.program_digest)
+                    .with_input(instance.input.clone())
+                    .with_output(instance.output.clone())
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
-    use crate::proof_item::ProofItem;
+    use crate::proof_item::FriResponse;
+    use crate::shared_tests::low_security_stark;
+    use crate::shared_tests::DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS;
+    use crate::triton_program;
 
     use super::*;
 
+    fn encoding_as_bytes(proof: &Proof) -> Vec<u8> {
+        let encoding = proof.encode().into_iter();
+        encoding
+            .flat_map(|word| word.value().to_le_bytes())
+            .collect()
+    }
+
+    #[proptest]
+    fn streamed_proof_is_byte_for_byte_identical_to_its_encoding(#[strategy(arb())] proof: Proof) {
+        let mut bytes = vec![];
+        proof.write_to(&mut bytes).unwrap();
+        prop_assert_eq!(encoding_as_bytes(&proof), bytes);
+    }
+
+    #[proptest]
+    fn streaming_proof_through_cursor_is_identity(
+        #[strategy(vec(arb(), 0..3 * PROOF_IO_CHUNK_SIZE))] elements: Vec<BFieldElement>,
+    ) {
+        let proof = Proof(elements);
+        let mut cursor = io::Cursor::new(vec![]);
+        proof.write_to(&mut cursor).unwrap();
+
+        cursor.set_position(0);
+        let read_proof = Proof::read_from(&mut cursor).unwrap();
+        prop_assert_eq!(proof, read_proof);
+    }
+
+    #[proptest]
+    fn truncated_proof_cannot_be_read(
+        #[strategy(arb())] proof: Proof,
+        #[strategy(1..=#proof.0.len() * WORD_SIZE + 2 * WORD_SIZE)] num_missing_bytes: usize,
+    ) {
+        let bytes = encoding_as_bytes(&proof);
+        let truncated_bytes = &bytes[..bytes.len() - num_missing_bytes];
+        let_assert!(Err(err) = Proof::read_from(truncated_bytes));
+        prop_assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn proof_with_non_canonical_element_cannot_be_read() {
+        let bytes = [2, 1, BFieldElement::P]
+            .into_iter()
+            .flat_map(u64::to_le_bytes)
+            .collect_vec();
+        let_assert!(Err(err) = Proof::read_from(bytes.as_slice()));
+        assert!(io::ErrorKind::InvalidData == err.kind());
+    }
+
+    #[test]
+    fn proof_with_inconsistent_length_indicators_cannot_be_read() {
+        let bytes = [3, 1, 42]
+            .into_iter()
+            .flat_map(u64::to_le_bytes)
+            .collect_vec();
+        let_assert!(Err(err) = Proof::read_from(bytes.as_slice()));
+        assert!(io::ErrorKind::InvalidData == err.kind());
+    }
+
     impl Default for Claim {
         /// For testing purposes only.
         fn default() -> Self {
@@ -100,6 +790,364 @@ mod tests {
         }
     }
 
+    #[proptest]
+    fn bounded_decoding_of_claim_within_bounds_is_identical_to_decoding(
+        #[strategy(arb())] claim: Claim,
+    ) {
+        let max_io_len = claim.input.len().max(claim.output.len());
+        let decoded = Claim::decode_bounded(&claim.encode(), max_io_len).unwrap();
+        prop_assert_eq!(claim, *decoded);
+    }
+
+    #[test]
+    fn bounded_decoding_of_claim_with_oversized_declared_input_length_fails() {
+        let claim = Claim::new(Digest::default()).with_input(bfe_vec![1, 2, 3]);
+        let mut encoding = claim.encode();
+
+        // the version is encoded first, followed by the output, which is empty and occupies
+        // only 2 elements
+        let input_len_index = 4;
+        assert!(bfe!(3) == encoding[input_len_index]);
+        encoding[input_len_index] = bfe!(1_u64 << 60);
+
+        let_assert!(Err(err) = Claim::decode_bounded(&encoding, 100));
+        let_assert!(ClaimDecodingError::InputTooLong(input_len, 100) = err);
+        assert!(1 << 60 == input_len);
+    }
+
+    #[test]
+    fn bounded_decoding_of_claim_with_too_long_output_fails() {
+        let claim = Claim::new(Digest::default()).with_output(bfe_vec![1, 2, 3]);
+        let_assert!(Err(err) = Claim::decode_bounded(&claim.encode(), 2));
+        let_assert!(ClaimDecodingError::OutputTooLong(3, 2) = err);
+    }
+
+    #[proptest]
+    fn claim_of_any_version_survives_round_trip_through_encoding(
+        #[strategy(arb())] program_digest: Digest,
+        #[strategy(vec(arb(), 0..10))] input: Vec<BFieldElement>,
+        #[strategy(vec(arb(), 0..10))] output: Vec<BFieldElement>,
+        version: u32,
+    ) {
+        let claim = Claim::new(program_digest)
+            .with_input(input)
+            .with_output(output)
+            .with_version(version);
+        let decoded = Claim::decode(&claim.encode()).unwrap();
+        prop_assert_eq!(&claim, &*decoded);
+
+        let decoded = Claim::decode_bounded(&claim.encode(), 10).unwrap();
+        prop_assert_eq!(claim, *decoded);
+    }
+
+    #[proptest]
+    fn claim_survives_round_trip_through_json(#[strategy(arb())] claim: Claim) {
+        let json = claim.to_json();
+        prop_assert_eq!(claim, Claim::from_json(&json).unwrap());
+    }
+
+    #[proptest]
+    fn json_of_claim_lists_canonical_values_of_io_as_decimal_strings(
+        #[strategy(arb())] claim: Claim,
+    ) {
+        let json: serde_json::Value = serde_json::from_str(&claim.to_json()).unwrap();
+        let as_strings =
+            |list: &[BFieldElement]| list.iter().map(|e| e.value().to_string()).collect_vec();
+        let expected_input = as_strings(&claim.input);
+        let expected_output = as_strings(&claim.output);
+        prop_assert_eq!(serde_json::json!(expected_input), json["input"].clone());
+        prop_assert_eq!(serde_json::json!(expected_output), json["output"].clone());
+        prop_assert_eq!(
+            serde_json::json!(claim.program_digest.to_hex()),
+            json["program_digest"].clone()
+        );
+    }
+
+    #[test]
+    fn hand_written_json_parses_into_expected_claim() {
+        let program_digest = Digest::new(bfe_array![1, 2, 3, 4, 5]);
+        let json = format!(
+            r#"{{
+                "program_digest": "{}",
+                "input": ["0", "42", "18446744069414584320"],
+                "output": [],
+                "version": 3
+            }}"#,
+            program_digest.to_hex()
+        );
+
+        let expected = Claim::new(program_digest)
+            .with_input(bfe_vec![0, 42, BFieldElement::MAX])
+            .with_version(3);
+        assert!(expected == Claim::from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn json_with_non_canonical_output_element_is_rejected() {
+        let claim = Claim::new(Digest::default()).with_output(bfe_vec![1, 2]);
+        let json = claim
+            .to_json()
+            .replace(r#""2""#, r#""18446744069414584321""#);
+        let_assert!(Err(err) = Claim::from_json(&json));
+        let_assert!(ClaimJsonError::InvalidOutputElement(1, _) = err);
+    }
+
+    #[test]
+    fn json_with_malformed_program_digest_is_rejected() {
+        let json = r#"{"program_digest":"cafe","input":[],"output":[],"version":0}"#;
+        let_assert!(Err(ClaimJsonError::InvalidProgramDigest(_)) = Claim::from_json(json));
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, BFieldCodec, Arbitrary)]
+    struct TestIO {
+        counter: u64,
+        digest: Digest,
+        values: Vec<XFieldElement>,
+    }
+
+    #[proptest]
+    fn typed_io_survives_round_trip_through_claim(
+        #[strategy(arb())] input: TestIO,
+        #[strategy(arb())] output: TestIO,
+    ) {
+        let claim = Claim::new(Digest::default())
+            .with_encoded_input(&input)
+            .with_encoded_output(&output);
+        let claim = Claim::decode(&claim.encode()).unwrap();
+
+        let decoded_input = claim.decode_input::<TestIO>().unwrap();
+        let decoded_output = claim.decode_output::<TestIO>().unwrap();
+        prop_assert_eq!(input, *decoded_input);
+        prop_assert_eq!(output, *decoded_output);
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, TritonIO, Arbitrary)]
+    struct TypedIO {
+        counter: u64,
+        values: Vec<XFieldElement>,
+        digest: Digest,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, TritonIO)]
+    struct TupleIO(BFieldElement, Vec<BFieldElement>);
+
+    #[derive(Debug, Clone, Eq, PartialEq, TritonIO)]
+    struct SumIO {
+        sum: BFieldElement,
+    }
+
+    #[proptest]
+    fn derived_typed_io_survives_round_trip_through_claim(
+        #[strategy(arb())] input: TypedIO,
+        #[strategy(arb())] output: TypedIO,
+    ) {
+        let claim = Claim::new(Digest::default())
+            .with_typed_input(&input)
+            .with_typed_output(&output);
+        let claim = Claim::decode(&claim.encode()).unwrap();
+
+        let decoded_input = claim.typed_input::<TypedIO>().unwrap();
+        prop_assert_eq!(input.counter, decoded_input.counter);
+        prop_assert_eq!(input.values, decoded_input.values);
+        prop_assert_eq!(input.digest, decoded_input.digest);
+
+        let decoded_output = claim.typed_output::<TypedIO>().unwrap();
+        prop_assert_eq!(output.counter, decoded_output.counter);
+        prop_assert_eq!(output.values, decoded_output.values);
+        prop_assert_eq!(output.digest, decoded_output.digest);
+    }
+
+    #[test]
+    fn derived_typed_io_encodes_fields_in_order_of_declaration() {
+        let io = TupleIO(bfe!(42), bfe_vec![1, 2, 3]);
+        let encoding = bfe_vec![42, 4, 3, 1, 2, 3];
+        assert!(encoding == io.encode_io());
+        assert!(Ok(io) == TupleIO::decode_io(&encoding));
+    }
+
+    #[test]
+    fn program_can_read_derived_typed_input() {
+        let program = triton_program!(
+            read_io 1       // _ first
+            read_io 2       // _ first len num_values
+            pop 2 read_io 2 // _ first [values]
+            add add         // _ sum
+            write_io 1 halt
+        );
+        let input = TupleIO(bfe!(1), bfe_vec![20, 300]);
+        let claim = Claim::about_program(&program).with_typed_input(&input);
+        let output = program.run(claim.input.clone().into(), [].into()).unwrap();
+        let claim = claim.with_output(output);
+        let sum = SumIO { sum: bfe!(321) };
+        assert!(Ok(sum) == claim.typed_output());
+    }
+
+    #[test]
+    fn decoding_derived_typed_io_from_malformed_sequence_fails() {
+        let_assert!(Err(err) = TupleIO::decode_io(&[]));
+        assert!(TritonIOError::MissingField("0") == err);
+        let_assert!(Err(err) = TupleIO::decode_io(&bfe_array![42, 4, 3, 1, 2]));
+        assert!(TritonIOError::FieldTooLong("1") == err);
+        let_assert!(Err(err) = TupleIO::decode_io(&bfe_array![42, 2, 5, 7]));
+        assert!(TritonIOError::InvalidField("1") == err);
+        let_assert!(Err(err) = TupleIO::decode_io(&bfe_array![42, 1, 0, 7]));
+        assert!(TritonIOError::TrailingElements(1) == err);
+    }
+
+    #[test]
+    fn decoding_output_of_wrong_type_fails() {
+        let claim = Claim::new(Digest::default()).with_encoded_output(&bfe!(42));
+        let_assert!(Err(_) = claim.decode_output::<TestIO>());
+    }
+
+    #[proptest]
+    fn claim_builder_accepts_u32_values(
+        #[strategy(arb())] program_digest: Digest,
+        input: Vec<u32>,
+        output: Vec<u32>,
+    ) {
+        let claim = Claim::builder()
+            .with_program_digest(program_digest)
+            .with_input(input.clone())
+            .with_output(output.clone())
+            .build()?;
+        prop_assert_eq!(
+            input.into_iter().map(BFieldElement::from).collect_vec(),
+            claim.input
+        );
+        prop_assert_eq!(
+            output.into_iter().map(BFieldElement::from).collect_vec(),
+            claim.output
+        );
+    }
+
+    #[proptest]
+    fn claim_builder_accepts_canonical_u64_values(
+        #[strategy(arb())] program_digest: Digest,
+        #[strategy(vec(0..BFieldElement::P, 0..10))] input: Vec<u64>,
+        #[strategy(vec(0..BFieldElement::P, 0..10))] output: Vec<u64>,
+    ) {
+        let claim = Claim::builder()
+            .with_program_digest(program_digest)
+            .with_input(input.clone())
+            .with_output(output.clone())
+            .build()?;
+        prop_assert_eq!(input, claim.input.iter().map(|e| e.value()).collect_vec());
+        prop_assert_eq!(output, claim.output.iter().map(|e| e.value()).collect_vec());
+    }
+
+    #[proptest]
+    fn claim_builder_accepts_field_elements(
+        #[strategy(arb())] program_digest: Digest,
+        #[strategy(arb())] input: Vec<BFieldElement>,
+        #[strategy(arb())] output: Vec<BFieldElement>,
+    ) {
+        let claim = Claim::builder()
+            .with_program_digest(program_digest)
+            .with_input(input.clone())
+            .with_output(output.clone())
+            .build()?;
+        let expected = Claim::new(program_digest)
+            .with_input(input)
+            .with_output(output);
+        prop_assert_eq!(expected, claim);
+    }
+
+    #[test]
+    fn claim_builder_rejects_out_of_range_u64() {
+        let builder = Claim::builder()
+            .with_program_digest(Digest::default())
+            .with_input([1_u64, 2, BFieldElement::P, 4]);
+        let_assert!(Err(err) = builder.build());
+        assert!(ClaimBuildingError::NonCanonicalInputElement(2, BFieldElement::P) == err);
+
+        let builder = Claim::builder()
+            .with_program_digest(Digest::default())
+            .with_output([u64::MAX]);
+        let_assert!(Err(err) = builder.build());
+        assert!(ClaimBuildingError::NonCanonicalOutputElement(0, u64::MAX) == err);
+    }
+
+    #[test]
+    fn claim_builder_without_program_digest_fails() {
+        let_assert!(Err(err) = Claim::builder().with_input([42_u32]).build());
+        assert!(ClaimBuildingError::MissingProgramDigest == err);
+    }
+
+    #[test]
+    fn new_claims_are_of_current_version() {
+        assert!(Claim::current_version() == Claim::default().version);
+    }
+
+    #[test]
+    fn proof_for_claim_of_one_version_does_not_verify_for_claim_of_other_version() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let (aet, output) = program
+            .trace_execution(bfe_vec![21].into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+
+        let other_version = Claim::current_version() + 1;
+        let claim_of_other_version = claim.with_version(other_version);
+        assert!(let Err(_) = stark.verify(&claim_of_other_version, &proof));
+    }
+
+    #[test]
+    fn summaries_of_proof_and_claim_contain_padded_height_and_program_digest() {
+        let program = triton_program!(read_io 3 add add write_io 1 halt);
+        let input = bfe_vec![1, 2, 3];
+        let (aet, output) = program
+            .trace_execution(input.clone().into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(input)
+            .with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let padded_height = proof.padded_height().unwrap();
+        let proof_summary = proof.summary();
+        assert!(proof_summary.contains(&format!("padded height: {padded_height}")));
+        assert!(proof_summary.contains("FriResponse"));
+
+        let claim_summary = claim.summary();
+        assert!(claim_summary.contains(&program.hash().to_hex()));
+        assert!(claim_summary.contains("input: 3 elements: [1, 2, 3]"));
+        assert!(claim_summary.contains("output: 1 elements: [6]"));
+    }
+
+    #[test]
+    fn claim_for_program_matches_that_program() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::for_program(&program, bfe_vec![21], bfe_vec![42]);
+        assert!(claim.matches_program(&program));
+        assert!(program.hash() == claim.program_digest);
+        assert!(bfe_vec![21] == claim.input);
+        assert!(bfe_vec![42] == claim.output);
+    }
+
+    #[test]
+    fn claim_does_not_match_other_program() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let other_program = triton_program!(read_io 1 push 3 mul write_io 1 halt);
+        let claim = Claim::for_program(&program, bfe_vec![21], bfe_vec![42]);
+        assert!(!claim.matches_program(&other_program));
+        assert!(!Claim::default().matches_program(&program));
+    }
+
+    #[test]
+    fn summary_of_claim_with_long_input_shows_only_first_few_elements() {
+        let claim = Claim::default().with_input(bfe_vec![10, 11, 12, 13, 14, 15, 16]);
+        let summary = claim.summary();
+        assert!(summary.contains("input: 7 elements: [10, 11, 12, 13, 14, …]"));
+        assert!(!summary.contains("15"));
+    }
+
     #[proptest]
     fn decode_proof(#[strategy(arb())] proof: Proof) {
         let encoded = proof.encode();
@@ -134,6 +1182,193 @@ mod tests {
         assert!(maybe_padded_height.is_err());
     }
 
+    fn proof_with_fri_rounds(
+        num_fri_merkle_roots: usize,
+        num_fri_responses: usize,
+        root: Digest,
+    ) -> Proof {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(8));
+        for _ in 0..NUM_NON_FRI_MERKLE_ROOTS + num_fri_merkle_roots {
+            proof_stream.enqueue(ProofItem::MerkleRoot(root));
+        }
+        proof_stream.enqueue(ProofItem::FriCodeword(vec![]));
+        proof_stream.enqueue(ProofItem::FriPolynomial(Polynomial::new(vec![])));
+        for _ in 0..num_fri_responses {
+            let fri_response = FriResponse {
+                auth_structure: vec![],
+                revealed_leaves: vec![],
+            };
+            proof_stream.enqueue(ProofItem::FriResponse(fri_response));
+        }
+        proof_stream.into()
+    }
+
+    #[proptest(cases = 10)]
+    fn num_fri_rounds_can_be_derived_from_proof_stream(
+        #[strategy(0_usize..20)] num_fri_rounds: usize,
+        #[strategy(arb())] root: Digest,
+    ) {
+        let num_rounds_including_first = num_fri_rounds + 1;
+        let proof =
+            proof_with_fri_rounds(num_rounds_including_first, num_rounds_including_first, root);
+        prop_assert_eq!(num_fri_rounds, proof.num_fri_rounds()?);
+    }
+
+    #[proptest(cases = 10)]
+    fn proof_without_fri_response_gives_err(
+        #[strategy(0_usize..5)] num_fri_merkle_roots: usize,
+        #[strategy(arb())] root: Digest,
+    ) {
+        let proof = proof_with_fri_rounds(num_fri_merkle_roots, 0, root);
+        let_assert!(Err(err) = proof.num_fri_rounds());
+        assert!(let ProofStreamError::NoFriResponse = err);
+    }
+
+    #[proptest(cases = 10)]
+    fn proof_with_inconsistent_fri_rounds_gives_err(
+        #[strategy(0_usize..20)] num_fri_merkle_roots: usize,
+        #[strategy(1_usize..20)]
+        #[filter(#num_fri_merkle_roots != #num_fri_responses)]
+        num_fri_responses: usize,
+        #[strategy(arb())] root: Digest,
+    ) {
+        let proof = proof_with_fri_rounds(num_fri_merkle_roots, num_fri_responses, root);
+        let_assert!(Err(err) = proof.num_fri_rounds());
+        let_assert!(ProofStreamError::InconsistentFriRounds { .. } = err);
+    }
+
+    #[test]
+    fn num_fri_rounds_of_actual_proof_matches_fri_parameters() {
+        let program = triton_program!(push 2 push 3 mul pop 1 halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let fri = stark.derive_fri(proof.padded_height().unwrap()).unwrap();
+        assert!(fri.num_rounds() == proof.num_fri_rounds().unwrap());
+    }
+
+    #[proptest]
+    fn transcript_items_are_items_of_proof_stream(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let proof: Proof = ProofStream::from_items(items.clone()).into();
+        let_assert!(Ok(transcript_items) = proof.transcript_items());
+        assert!(items == transcript_items);
+    }
+
+    #[test]
+    fn transcript_of_actual_proof_is_in_proving_order() {
+        let program = triton_program!(push 2 push 3 mul pop 1 halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let items = proof.transcript_items().unwrap();
+        let variants = items.iter().map(ProofItemVariant::from).collect_vec();
+        let_assert!([first, second, third, fourth, ..] = variants.as_slice());
+        assert!(ProofItemVariant::Log2PaddedHeight == *first);
+        assert!(ProofItemVariant::MerkleRoot == *second);
+        assert!(ProofItemVariant::MerkleRoot == *third);
+        assert!(ProofItemVariant::MerkleRoot == *fourth);
+
+        let position_of = |variant| variants.iter().position(|&v| v == variant).unwrap();
+        let first_ood_base_row = position_of(ProofItemVariant::OutOfDomainBaseRow);
+        let first_fri_response = position_of(ProofItemVariant::FriResponse);
+        let first_revealed_rows = position_of(ProofItemVariant::MasterBaseTableRows);
+        assert!(first_ood_base_row < first_fri_response);
+        assert!(first_fri_response < first_revealed_rows);
+        assert!(Some(&ProofItemVariant::AuthenticationStructure) == variants.last());
+    }
+
+    #[test]
+    fn transcript_of_malformed_proof_cannot_be_obtained() {
+        let proof = Proof(bfe_vec![1, 2, 3]);
+        assert!(let Err(_) = proof.transcript_items());
+    }
+
+    #[test]
+    fn proof_with_too_large_padded_height_gives_err() {
+        for log_2_padded_height in [MAX_LOG2_PADDED_HEIGHT + 1, 63, 64, u32::MAX] {
+            let mut proof_stream = ProofStream::new();
+            proof_stream.enqueue(ProofItem::Log2PaddedHeight(log_2_padded_height));
+            let proof: Proof = proof_stream.into();
+            let_assert!(Err(err) = proof.padded_height());
+            assert!(let ProofStreamError::Log2PaddedHeightTooLarge(_) = err);
+        }
+    }
+
+    fn proof_with_log2_padded_height(log_2_padded_height: u32) -> Proof {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(log_2_padded_height));
+        proof_stream.into()
+    }
+
+    #[test]
+    fn padded_height_at_or_below_limit_is_accepted() {
+        let proof = proof_with_log2_padded_height(10);
+        let_assert!(Ok(1024) = proof.verify_padded_height_at_most(1024));
+        let_assert!(Ok(1024) = proof.verify_padded_height_at_most(1025));
+        let_assert!(Ok(1024) = proof.verify_padded_height_at_most(usize::MAX));
+    }
+
+    #[test]
+    fn padded_height_above_limit_is_rejected() {
+        let proof = proof_with_log2_padded_height(10);
+        let_assert!(Err(err) = proof.verify_padded_height_at_most(1023));
+        let_assert!(
+            ProofStreamError::PaddedHeightExceedsLimit {
+                padded_height: 1024,
+                limit: 1023
+            } = &err
+        );
+
+        let err_msg = err.to_string();
+        assert!(err_msg.contains("1024"), "{err_msg}");
+        assert!(err_msg.contains("1023"), "{err_msg}");
+    }
+
+    #[test]
+    fn padded_height_limit_cannot_be_checked_for_proof_without_padded_height() {
+        let proof: Proof = ProofStream::new().into();
+        let_assert!(
+            Err(ProofStreamError::NoLog2PaddedHeight) = proof.verify_padded_height_at_most(1)
+        );
+    }
+
+    #[test]
+    fn batch_proof_stores_shared_program_digest_and_claim_version_only_once() {
+        let instance = |input: u64, proof_len: usize| BatchedInstance {
+            input: bfe_vec![input],
+            output: bfe_vec![2 * input],
+            proof: Proof(bfe_vec![input; proof_len]),
+        };
+        let batch_proof = BatchProof {
+            program_digest: Digest::default(),
+            instances: vec![instance(3, 10), instance(5, 20), instance(7, 30)],
+        };
+        let independent_proofs = batch_proof
+            .claims()
+            .into_iter()
+            .zip(batch_proof.instances.iter().map(|i| i.proof.clone()))
+            .collect_vec();
+
+        // Every independent claim encodes the program digest, the claim's version, and the
+        // length of the claim itself. The batch proof encodes the program digest once, and the
+        // length of its list of instances.
+        let num_words_per_claim = Digest::LEN + 2;
+        let num_words_shared = Digest::LEN + 1;
+        let num_words_saved = batch_proof.instances.len() * num_words_per_claim - num_words_shared;
+        assert!(15 == num_words_saved);
+
+        let batch_proof_size = batch_proof.encode().len();
+        let independent_proofs_size = independent_proofs.encode().len();
+        assert!(independent_proofs_size == batch_proof_size + num_words_saved);
+    }
+
     #[proptest]
     fn decoding_arbitrary_proof_data_does_not_panic(
         #[strategy(vec(arb(), 0..1_000))] proof_data: Vec<BFieldElement>,
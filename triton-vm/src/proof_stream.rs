@@ -25,12 +25,42 @@ impl ProofStream {
         }
     }
 
+    /// A proof stream carrying the given items, in the given order. Like a proof stream
+    /// obtained from a [`Proof`], the Fiat-Shamir sponge is in its initial state and
+    /// [dequeuing](Self::dequeue) starts at the first item.
+    pub fn from_items(items: Vec<ProofItem>) -> Self {
+        Self {
+            items,
+            ..Self::new()
+        }
+    }
+
+    /// Move all items of the `other` proof stream to the end of this one, preserving their
+    /// order. The Fiat-Shamir sponge of neither stream is involved: the appended items are
+    /// absorbed once [dequeued](Self::dequeue) from the combined stream, in the order of
+    /// the combined sequence. The bookkeeping of `other` is discarded.
+    pub fn append(&mut self, other: ProofStream) {
+        self.items.extend(other.items);
+    }
+
     /// The number of field elements required to encode the proof.
     pub fn transcript_length(&self) -> usize {
         let Proof(b_field_elements) = self.into();
         b_field_elements.len()
     }
 
+    /// Whether the two proof streams carry the same transcript, _i.e._, the same
+    /// [`ProofItem`]s in the same order. In contrast to `==`, the bookkeeping that is not
+    /// part of the transcript – the position of the next item to [dequeue](Self::dequeue)
+    /// and the state of the Fiat-Shamir sponge – is ignored.
+    ///
+    /// Every proof item is part of the transcript, including those items that are not
+    /// [absorbed by the sponge](ProofItem::include_in_fiat_shamir_heuristic): the verifier
+    /// relies on them all.
+    pub fn transcript_eq(&self, other: &ProofStream) -> bool {
+        self.items == other.items
+    }
+
     /// Alters the Fiat-Shamir's sponge state with the encoding of the given item.
     /// Does _not_ record the given item in the proof stream.
     /// This is useful for items that are not sent to the verifier, _e.g._, the
@@ -72,6 +102,46 @@ impl ProofStream {
         Ok(item)
     }
 
+    /// The position of the next item to [dequeue](Self::dequeue), in number of
+    /// [`BFieldElement`]s. Equals the total length of the encodings of all items dequeued so
+    /// far, which makes it independent of the in-memory representation of the stream.
+    ///
+    /// See also [`Self::seek()`].
+    pub fn position(&self) -> usize {
+        self.items[..self.items_index]
+            .iter()
+            .map(|item| item.encode().len())
+            .sum()
+    }
+
+    /// Continue [dequeuing](Self::dequeue) from the given [position](Self::position). Allows
+    /// restoring a partially consumed proof stream, for example after reconstructing it from
+    /// its [`Proof`].
+    ///
+    /// Only the position is restored. The state of the Fiat-Shamir sponge is left untouched:
+    /// it also depends on data that is not part of the stream, like the
+    /// [`Claim`](crate::proof::Claim) and any sampled randomness. Restore it separately.
+    ///
+    /// Returns an error if the position does not fall on the boundary of an item, or if it
+    /// lies beyond the end of the stream.
+    pub fn seek(&mut self, position: usize) -> Result<(), ProofStreamError> {
+        let mut item_boundary = 0;
+        let mut items_index = 0;
+        while item_boundary < position {
+            let Some(item) = self.items.get(items_index) else {
+                break;
+            };
+            item_boundary += item.encode().len();
+            items_index += 1;
+        }
+
+        if item_boundary != position {
+            return Err(ProofStreamError::NotAnItemBoundary(position));
+        }
+        self.items_index = items_index;
+        Ok(())
+    }
+
     /// Given an `upper_bound` that is a power of 2, produce `num_indices` uniform random numbers
     /// in the interval `[0; upper_bound)`.
     ///
@@ -217,6 +287,62 @@ mod tests {
         assert!(0 == sponge_states.len());
     }
 
+    #[proptest]
+    fn seeking_recorded_position_continues_dequeuing_identically(
+        #[strategy(vec(arb(), 2..20))] items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        for item in items.clone() {
+            proof_stream.enqueue(item);
+        }
+        let proof = Proof::from(&proof_stream);
+
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        for _ in 0..items.len() / 2 {
+            let_assert!(Ok(_) = proof_stream.dequeue());
+        }
+        let position = proof_stream.position();
+        let sponge = proof_stream.sponge.clone();
+
+        let mut restored_proof_stream = ProofStream::try_from(&proof).unwrap();
+        restored_proof_stream.sponge = sponge;
+        let_assert!(Ok(()) = restored_proof_stream.seek(position));
+        assert!(position == restored_proof_stream.position());
+
+        for _ in items.len() / 2..items.len() {
+            let_assert!(Ok(item) = proof_stream.dequeue());
+            let_assert!(Ok(restored_item) = restored_proof_stream.dequeue());
+            assert!(item == restored_item);
+            assert!(proof_stream.sponge.state == restored_proof_stream.sponge.state);
+        }
+        let_assert!(Err(_) = restored_proof_stream.dequeue());
+    }
+
+    #[proptest]
+    fn seeking_position_within_an_item_fails(
+        #[strategy(vec(arb(), 1..20))] items: Vec<ProofItem>,
+        #[strategy(0..#items.len())] item_index: usize,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        for item in items {
+            proof_stream.enqueue(item);
+        }
+        proof_stream.items_index = item_index;
+        let position_within_item = proof_stream.position() + 1;
+        let_assert!(Err(err) = proof_stream.seek(position_within_item));
+        let_assert!(ProofStreamError::NotAnItemBoundary(position) = err);
+        assert!(position_within_item == position);
+    }
+
+    #[test]
+    fn seeking_beyond_end_of_proof_stream_fails() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        let end = proof_stream.transcript_length();
+        let_assert!(Err(_) = proof_stream.seek(end + 100));
+        let_assert!(Ok(()) = proof_stream.seek(0));
+    }
+
     #[test]
     fn enqueue_dequeue_verify_partial_authentication_structure() {
         let tree_height = 8;
@@ -279,6 +405,84 @@ mod tests {
         let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.dequeue());
     }
 
+    #[test]
+    fn proof_streams_differing_only_in_bookkeeping_have_equal_transcripts() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(7));
+
+        let mut partially_read_proof_stream = proof_stream.clone();
+        let_assert!(Ok(_) = partially_read_proof_stream.dequeue());
+
+        assert!(proof_stream != partially_read_proof_stream);
+        assert!(proof_stream.transcript_eq(&partially_read_proof_stream));
+    }
+
+    #[test]
+    fn proof_streams_with_reordered_items_have_different_transcripts() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(7));
+
+        let mut reordered_proof_stream = ProofStream::new();
+        reordered_proof_stream.items = proof_stream.items.iter().rev().cloned().collect();
+
+        assert!(!proof_stream.transcript_eq(&reordered_proof_stream));
+    }
+
+    #[proptest]
+    fn appending_proof_stream_preserves_order_of_items(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+        #[strategy(vec(arb(), 0..10))] other_items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::from_items(items.clone());
+        proof_stream.append(ProofStream::from_items(other_items.clone()));
+
+        let expected_items = items.into_iter().chain(other_items).collect_vec();
+        assert!(expected_items == proof_stream.items);
+        for expected_item in expected_items {
+            let_assert!(Ok(item) = proof_stream.dequeue());
+            assert!(expected_item == item);
+        }
+        let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.dequeue());
+    }
+
+    #[proptest]
+    fn merged_proof_stream_survives_round_trip_through_proof(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+        #[strategy(vec(arb(), 0..10))] other_items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::from_items(items);
+        proof_stream.append(ProofStream::from_items(other_items));
+
+        let proof = Proof::from(&proof_stream);
+        let_assert!(Ok(decoded_proof_stream) = ProofStream::try_from(&proof));
+        assert!(proof_stream.transcript_eq(&decoded_proof_stream));
+    }
+
+    #[test]
+    fn proof_stream_from_items_dequeues_like_proof_stream_with_enqueued_items() {
+        let items = vec![
+            ProofItem::MerkleRoot(Digest::default()),
+            ProofItem::Log2PaddedHeight(7),
+            ProofItem::FriCodeword(vec![]),
+        ];
+        let mut enqueued_proof_stream = ProofStream::new();
+        for item in items.clone() {
+            enqueued_proof_stream.enqueue(item);
+        }
+        let mut proof_stream = ProofStream::from_items(items);
+
+        // only dequeuing absorbs the items into the sponge, in both cases
+        let mut receiving_proof_stream = ProofStream::from_items(enqueued_proof_stream.items);
+        for _ in 0..3 {
+            let_assert!(Ok(expected_item) = receiving_proof_stream.dequeue());
+            let_assert!(Ok(item) = proof_stream.dequeue());
+            assert!(expected_item == item);
+        }
+        assert!(receiving_proof_stream == proof_stream);
+    }
+
     #[test]
     fn encoded_length_of_prove_stream_is_not_known_at_compile_time() {
         assert!(ProofStream::static_length().is_none());
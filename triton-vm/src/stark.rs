@@ -1,5 +1,7 @@
 use std::ops::Mul;
 use std::ops::MulAssign;
+use std::time::Duration;
+use std::time::Instant;
 
 use arbitrary::Arbitrary;
 use arbitrary::Unstructured;
@@ -22,10 +24,20 @@ use crate::error::ProvingError;
 use crate::error::VerificationError;
 use crate::fri;
 use crate::fri::Fri;
+use crate::instruction::Instruction;
+use crate::merkle;
 use crate::profiler::profiler;
+use crate::program::NonDeterminism;
+use crate::program::Program;
+use crate::program::PublicInput;
+use crate::proof::padded_height_from_log2;
+use crate::proof::BatchProof;
+use crate::proof::BatchedInstance;
 use crate::proof::Claim;
 use crate::proof::Proof;
+use crate::proof_item::FriResponse;
 use crate::proof_item::ProofItem;
+use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
 use crate::table::challenges::Challenges;
 use crate::table::extension_table::Evaluable;
@@ -37,6 +49,8 @@ use crate::table::master_table::MasterBaseTable;
 use crate::table::master_table::MasterExtTable;
 use crate::table::master_table::MasterTable;
 use crate::table::master_table::AIR_TARGET_DEGREE;
+use crate::table::table_column::MasterBaseTableColumn;
+use crate::table::table_column::ProcessorBaseTableColumn;
 use crate::table::QuotientSegments;
 use crate::table::NUM_BASE_COLUMNS;
 use crate::table::NUM_EXT_COLUMNS;
@@ -51,6 +65,10 @@ pub const NUM_RANDOMIZER_POLYNOMIALS: usize = 1;
 
 const NUM_DEEP_CODEWORD_COMPONENTS: usize = 3;
 
+/// The default for [`Stark::max_num_cycles`]. Generous enough for any program whose execution
+/// trace can be proven in practice.
+pub const DEFAULT_MAX_NUM_CYCLES: u32 = 1 << 28;
+
 /// The Zero-Knowledge [Scalable Transparent ARgument of Knowledge (STARK)][stark] for Triton VM.
 ///
 /// [stark]: https://www.iacr.org/archive/crypto2019/116940201/116940201.pdf
@@ -72,6 +90,22 @@ pub struct Stark {
 
     /// The number of collinearity checks to perform in FRI.
     pub num_collinearity_checks: usize,
+
+    /// The maximum number of clock cycles a program may take when it is executed in the course
+    /// of [proving](crate::prove). A program that has not halted within this budget causes
+    /// proving to fail instead of running indefinitely.
+    /// Has no influence on soundness. Defaults to [`DEFAULT_MAX_NUM_CYCLES`], also when
+    /// deserializing a `Stark` that was serialized before this field existed.
+    ///
+    /// Like all other fields, this one takes part in equality and hashing, since it changes the
+    /// behavior of [proving](Self::prove). Use [`compatible_with`](Self::compatible_with) to find
+    /// out whether two `Stark`s produce and accept the same proofs.
+    #[serde(default = "default_max_num_cycles")]
+    pub max_num_cycles: u32,
+}
+
+fn default_max_num_cycles() -> u32 {
+    DEFAULT_MAX_NUM_CYCLES
 }
 
 impl Stark {
@@ -97,18 +131,160 @@ impl Stark {
             fri_expansion_factor,
             num_trace_randomizers,
             num_collinearity_checks,
+            max_num_cycles: DEFAULT_MAX_NUM_CYCLES,
         }
     }
 
+    /// The cheapest parameters that still allow proving and verifying, prioritizing speed over
+    /// security: a FRI expansion factor of 2 and a single collinearity check.
+    ///
+    /// **For testing purposes only.** The resulting proofs provide no meaningful soundness.
+    pub fn minimal_for_testing() -> Self {
+        let security_level = 1;
+        let log2_of_fri_expansion_factor = 1;
+        Self::new(security_level, log2_of_fri_expansion_factor)
+    }
+
+    /// Set the [maximum number of clock cycles](Self::max_num_cycles) a program may take when
+    /// it is executed in the course of [proving](crate::prove).
+    #[must_use]
+    pub fn with_max_num_cycles(mut self, max_num_cycles: u32) -> Self {
+        self.max_num_cycles = max_num_cycles;
+        self
+    }
+
+    /// Whether proofs generated with `self` can be verified with `other`, and vice versa.
+    ///
+    /// Only the parameters influencing the shape of the proof and the verifier's checks are
+    /// compared: the FRI expansion factor, the number of trace randomizers, and the number of
+    /// collinearity checks. Parameters without influence on soundness, like the
+    /// [maximum number of clock cycles](Self::max_num_cycles), are ignored. The
+    /// [security level](Self::security_level) is ignored, too – it is only used to derive the
+    /// other parameters.
+    pub fn compatible_with(&self, other: &Self) -> bool {
+        self.fri_expansion_factor == other.fri_expansion_factor
+            && self.num_trace_randomizers == other.num_trace_randomizers
+            && self.num_collinearity_checks == other.num_collinearity_checks
+    }
+
     pub fn prove(
         &self,
         claim: &Claim,
         aet: &AlgebraicExecutionTrace,
     ) -> Result<Proof, ProvingError> {
+        let mut timings = ProvingTimings::default();
+        self.prove_measuring_timings(claim, aet, &mut timings)
+    }
+
+    /// Like [`prove`](Self::prove), but additionally measures the wall-clock time spent in
+    /// the prover's major phases. See [`ProvingTimings`].
+    pub fn prove_timed(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<(Proof, ProvingTimings), ProvingError> {
+        let mut timings = ProvingTimings::default();
+        let proof = self.prove_measuring_timings(claim, aet, &mut timings)?;
+        Ok((proof, timings))
+    }
+
+    /// Like [`prove`](Self::prove), but intended for [traces](AlgebraicExecutionTrace) that
+    /// were generated elsewhere, for example, [decoded](BFieldCodec::decode) after having been
+    /// shipped from the machine that executed the program. Performs basic sanity checks on the
+    /// trace before proving:
+    /// - the trace's program must match the claim's program digest, and
+    /// - the traced execution must have terminated gracefully, _i.e._, with instruction `halt`.
+    pub fn prove_from_trace(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        if aet.program.hash() != claim.program_digest {
+            return Err(ProvingError::ProgramDigestMismatch);
+        }
+
+        let ci = ProcessorBaseTableColumn::CI.base_table_index();
+        let last_row = aet.processor_trace.rows().into_iter().next_back();
+        let last_instruction = last_row.map(|row| row[ci]);
+        if last_instruction != Some(Instruction::Halt.opcode_b()) {
+            return Err(ProvingError::TraceDoesNotHalt);
+        }
+
+        self.prove(claim, aet)
+    }
+
+    /// Like [`prove`](Self::prove), but first makes sure that the claim's public input
+    /// matches the given commitment, for example, one that was published elsewhere. Fails if
+    /// the commitment is not the [claim's input commitment](Claim::input_commitment).
+    ///
+    /// The commitment is not added to the proof's transcript: the entire claim, including the
+    /// public input, already is. A proof for the claim is thus bound to the committed input,
+    /// and the resulting proof is an ordinary one. It can be verified with
+    /// [`verify`](Self::verify) or, to also check the commitment on the verifier's side, with
+    /// [`verify_with_input_commitment`](Self::verify_with_input_commitment).
+    pub fn prove_with_input_commitment(
+        &self,
+        input_commitment: Digest,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        if input_commitment != claim.input_commitment() {
+            return Err(ProvingError::InputCommitmentMismatch);
+        }
+        self.prove(claim, aet)
+    }
+
+    /// Prove several independent executions of the same program, one per given instance of
+    /// public input and non-determinism. The program is executed for every instance, within
+    /// the [budget of clock cycles](Self::max_num_cycles).
+    ///
+    /// Each execution is proven as if by [`prove`](Self::prove); the resulting
+    /// [`BatchProof`] only stores the program's digest once. Use
+    /// [`verify_batch`](Self::verify_batch) to verify it.
+    pub fn prove_shared_program(
+        &self,
+        program: &Program,
+        instances: &[(PublicInput, NonDeterminism)],
+    ) -> Result<BatchProof, ProvingError> {
+        let program_digest = program.hash();
+        let mut batched_instances = Vec::with_capacity(instances.len());
+        for (public_input, non_determinism) in instances {
+            let (aet, output) = program.trace_execution_with_budget(
+                public_input.clone(),
+                non_determinism.clone(),
+                self.max_num_cycles,
+            )?;
+            let input = public_input.individual_tokens.clone();
+            let claim = Claim::new(program_digest)
+                .with_input(input.clone())
+                .with_output(output.clone());
+            let proof = self.prove(&claim, &aet)?;
+            batched_instances.push(BatchedInstance {
+                input,
+                output,
+                proof,
+            });
+        }
+
+        Ok(BatchProof {
+            program_digest,
+            instances: batched_instances,
+        })
+    }
+
+    fn prove_measuring_timings(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        timings: &mut ProvingTimings,
+    ) -> Result<Proof, ProvingError> {
+        let mut clock = PhaseClock::start();
+
         profiler!(start "Fiat-Shamir: claim" ("hash"));
         let mut proof_stream = ProofStream::new();
         proof_stream.alter_fiat_shamir_state_with(claim);
         profiler!(stop "Fiat-Shamir: claim");
+        clock.charge(&mut timings.fiat_shamir);
 
         profiler!(start "derive additional parameters");
         let padded_height = aet.padded_height();
@@ -117,6 +293,7 @@ impl Stark {
         let quotient_domain = Self::quotient_domain(fri.domain, max_degree)?;
         proof_stream.enqueue(ProofItem::Log2PaddedHeight(padded_height.ilog2()));
         profiler!(stop "derive additional parameters");
+        clock.charge(&mut timings.parameter_derivation);
 
         profiler!(start "base tables");
         profiler!(start "create" ("gen"));
@@ -131,20 +308,24 @@ impl Stark {
         profiler!(start "randomize trace" ("gen"));
         master_base_table.randomize_trace();
         profiler!(stop "randomize trace");
+        clock.charge(&mut timings.trace_generation);
 
         profiler!(start "LDE" ("LDE"));
         master_base_table.low_degree_extend_all_columns();
         profiler!(stop "LDE");
+        clock.charge(&mut timings.low_degree_extension);
 
         profiler!(start "Merkle tree" ("hash"));
         let base_merkle_tree = master_base_table.merkle_tree();
         profiler!(stop "Merkle tree");
+        clock.charge(&mut timings.merkle_commitment);
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(base_merkle_tree.root()));
         let challenges = proof_stream.sample_scalars(Challenges::SAMPLE_COUNT);
         let challenges = Challenges::new(challenges, claim);
         profiler!(stop "Fiat-Shamir");
+        clock.charge(&mut timings.fiat_shamir);
 
         profiler!(start "extend" ("gen"));
         let mut master_ext_table = master_base_table.extend(&challenges);
@@ -155,14 +336,17 @@ impl Stark {
         profiler!(start "randomize trace" ("gen"));
         master_ext_table.randomize_trace();
         profiler!(stop "randomize trace");
+        clock.charge(&mut timings.trace_generation);
 
         profiler!(start "LDE" ("LDE"));
         master_ext_table.low_degree_extend_all_columns();
         profiler!(stop "LDE");
+        clock.charge(&mut timings.low_degree_extension);
 
         profiler!(start "Merkle tree" ("hash"));
         let ext_merkle_tree = master_ext_table.merkle_tree();
         profiler!(stop "Merkle tree");
+        clock.charge(&mut timings.merkle_commitment);
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(ext_merkle_tree.root()));
@@ -172,6 +356,7 @@ impl Stark {
             proof_stream.sample_scalars(MasterExtTable::NUM_CONSTRAINTS);
         profiler!(stop "Fiat-Shamir");
         profiler!(stop "ext tables");
+        clock.charge(&mut timings.fiat_shamir);
 
         let (fri_domain_quotient_segment_codewords, quotient_segment_polynomials) =
             Self::compute_quotient_segments(
@@ -182,6 +367,7 @@ impl Stark {
                 &challenges,
                 &quotient_combination_weights,
             );
+        clock.charge(&mut timings.quotient_computation);
 
         profiler!(start "hash rows of quotient segments" ("hash"));
         let interpret_xfe_as_bfes = |xfe: &XFieldElement| xfe.coefficients.to_vec();
@@ -201,6 +387,7 @@ impl Stark {
         let quot_merkle_tree_root = quot_merkle_tree.root();
         proof_stream.enqueue(ProofItem::MerkleRoot(quot_merkle_tree_root));
         profiler!(stop "Merkle tree");
+        clock.charge(&mut timings.merkle_commitment);
 
         debug_assert_eq!(fri.domain.length, quot_merkle_tree.num_leafs());
 
@@ -236,10 +423,12 @@ impl Stark {
             out_of_domain_curr_row_quot_segments,
         ));
         profiler!(stop "out-of-domain rows");
+        clock.charge(&mut timings.deep_composition);
 
         profiler!(start "Fiat-Shamir" ("hash"));
         let weights = LinearCombinationWeights::sample(&mut proof_stream);
         profiler!(stop "Fiat-Shamir");
+        clock.charge(&mut timings.fiat_shamir);
 
         let fri_domain_is_short_domain = fri.domain.length <= quotient_domain.length;
         let short_domain = if fri_domain_is_short_domain {
@@ -338,6 +527,7 @@ impl Stark {
         );
 
         profiler!(stop "sum");
+
         let fri_combination_codeword = if fri_domain_is_short_domain {
             deep_codeword
         } else {
@@ -348,6 +538,7 @@ impl Stark {
         };
         assert_eq!(fri.domain.length, fri_combination_codeword.len());
         profiler!(stop "combined DEEP polynomial");
+        clock.charge(&mut timings.deep_composition);
 
         profiler!(start "FRI");
         let revealed_current_row_indices =
@@ -357,6 +548,7 @@ impl Stark {
             revealed_current_row_indices.len()
         );
         profiler!(stop "FRI");
+        clock.charge(&mut timings.fri);
 
         profiler!(start "open trace leafs");
         // Open leafs of zipped codewords at indicated positions
@@ -412,7 +604,10 @@ impl Stark {
         ));
         profiler!(stop "open trace leafs");
 
-        Ok(proof_stream.into())
+        let proof = proof_stream.into();
+        clock.charge(&mut timings.merkle_commitment);
+        timings.total = clock.total();
+        Ok(proof)
     }
 
     fn compute_quotient_segments(
@@ -550,10 +745,7 @@ impl Stark {
     /// trace and the number of trace randomizers.
     /// The degree of the quotients depends on the constraints, _i.e._, the AIR.
     pub fn derive_max_degree(&self, padded_height: usize) -> isize {
-        let interpolant_degree = interpolant_degree(padded_height, self.num_trace_randomizers);
-        let max_constraint_degree_with_origin =
-            max_degree_with_origin(interpolant_degree, padded_height);
-        let max_constraint_degree = max_constraint_degree_with_origin.degree as u64;
+        let max_constraint_degree = self.quotient_degree_bound(padded_height) as u64;
         let min_arithmetic_domain_length_supporting_max_constraint_degree =
             max_constraint_degree.next_power_of_two();
         let max_degree_supported_by_that_smallest_arithmetic_domain =
@@ -562,6 +754,28 @@ impl Stark {
         max_degree_supported_by_that_smallest_arithmetic_domain as isize
     }
 
+    /// The exact upper bound on the degree of any quotient polynomial, given the padded height of
+    /// the trace.
+    ///
+    /// A constraint of degree `d` applied to interpolants of degree `D` results in a polynomial
+    /// of degree at most `d·D`. Dividing out the constraint's zerofier of degree `z` gives a
+    /// quotient of degree at most `d·D - z`. The bound returned here is the maximum of this value
+    /// over all constraints, where `D` is the degree of the [randomized trace's][interpolant]
+    /// interpolants.
+    ///
+    /// The [combined quotient](all_quotients_combined) is split into [`NUM_QUOTIENT_SEGMENTS`]
+    /// segments, each of which has degree at most
+    /// `quotient_degree_bound / NUM_QUOTIENT_SEGMENTS`. The [maximum degree][max_degree] rounds
+    /// the bound up to match the length of the next arithmetic domain.
+    ///
+    /// [interpolant]: interpolant_degree
+    /// [max_degree]: Self::derive_max_degree
+    pub fn quotient_degree_bound(&self, padded_height: usize) -> usize {
+        let interpolant_degree = interpolant_degree(padded_height, self.num_trace_randomizers);
+        let max_degree_with_origin = max_degree_with_origin(interpolant_degree, padded_height);
+        usize::try_from(max_degree_with_origin.degree).expect("AIR should constrain the VM")
+    }
+
     /// Compute the parameters for FRI. The length of the FRI domain, _i.e._, the number of
     /// elements in the FRI domain, has a major influence on proving time. It is influenced by the
     /// length of the execution trace and the FRI expansion factor, a security parameter.
@@ -583,6 +797,46 @@ impl Stark {
         )
     }
 
+    /// The kinds of [proof items](ProofItem), in order, that a [`Proof`] for an execution trace
+    /// of the given padded height consists of. Every valid proof has exactly this structure,
+    /// which makes it useful for implementing alternative verifiers.
+    pub fn expected_proof_schema(
+        &self,
+        padded_height: usize,
+    ) -> fri::SetupResult<Vec<ProofItemVariant>> {
+        let fri = self.derive_fri(padded_height)?;
+        let num_fri_merkle_roots = fri.num_rounds() + 1;
+
+        // FRI reveals the “a” indices of the first round and the “b” indices of every round
+        // that has a next round.
+        let num_fri_responses = fri.num_rounds() + 1;
+
+        let mut schema = vec![ProofItemVariant::Log2PaddedHeight];
+        schema.extend([ProofItemVariant::MerkleRoot; 3]);
+        schema.extend([
+            ProofItemVariant::OutOfDomainBaseRow,
+            ProofItemVariant::OutOfDomainExtRow,
+            ProofItemVariant::OutOfDomainBaseRow,
+            ProofItemVariant::OutOfDomainExtRow,
+            ProofItemVariant::OutOfDomainQuotientSegments,
+        ]);
+        schema.extend(vec![ProofItemVariant::MerkleRoot; num_fri_merkle_roots]);
+        schema.extend([
+            ProofItemVariant::FriCodeword,
+            ProofItemVariant::FriPolynomial,
+        ]);
+        schema.extend(vec![ProofItemVariant::FriResponse; num_fri_responses]);
+        schema.extend([
+            ProofItemVariant::MasterBaseTableRows,
+            ProofItemVariant::AuthenticationStructure,
+            ProofItemVariant::MasterExtTableRows,
+            ProofItemVariant::AuthenticationStructure,
+            ProofItemVariant::QuotientSegmentsElements,
+            ProofItemVariant::AuthenticationStructure,
+        ]);
+        Ok(schema)
+    }
+
     /// Read the indicated rows from the cached table. The indices come from FRI.
     fn read_revealed_rows<const N: usize, FF: FiniteField>(
         fri_domain_table: ArrayView2<FF>,
@@ -722,7 +976,94 @@ impl Stark {
         segments.try_into().unwrap()
     }
 
+    /// Verify a [`Proof`] for the given [`Claim`].
+    ///
+    /// The proof is bound to the claim in its entirety. All of the claim's parts – the program
+    /// digest, the public input, and the public output – are absorbed into the Fiat-Shamir state
+    /// before any other part of the proof is looked at. Additionally, the public input and output
+    /// determine the terminal values of the corresponding evaluation arguments. Consequently,
+    /// it is not possible to check a proof against only a part of a claim, for example the input
+    /// without the output.
+    ///
+    /// The claim's program digest is taken at face value: the verifier never has access to the
+    /// program itself and does not re-derive the digest. A caller who has validated the digest
+    /// out of band therefore does no redundant work by calling this method. Conversely, any
+    /// program digest other than the one the proof was generated for leads to a transcript
+    /// mismatch and is rejected.
     pub fn verify(&self, claim: &Claim, proof: &Proof) -> Result<(), VerificationError> {
+        self.verify_returning_fri_query_indices(claim, proof)?;
+        Ok(())
+    }
+
+    /// Like [`verify`](Self::verify), but additionally reports the parts of the [`Claim`] that
+    /// the proof was found to be bound to. See [`VerificationAudit`].
+    pub fn verify_with_audit(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+    ) -> Result<VerificationAudit, VerificationError> {
+        self.verify_returning_fri_query_indices(claim, proof)?;
+        Ok(VerificationAudit {
+            program_digest: claim.program_digest,
+            input_len: claim.input.len(),
+            output_len: claim.output.len(),
+        })
+    }
+
+    /// The indices of the FRI codewords the verifier queries in each round of FRI when
+    /// [verifying](Self::verify) the given [`Proof`] for the given [`Claim`], starting with
+    /// the first round. Useful for auditing FRI verification.
+    ///
+    /// The proof is verified in the process; indices are only returned for valid proofs.
+    ///
+    /// In each round, the verifier checks collinearity of points at the listed indices
+    /// (“a indices”) and the indices half the round's domain length further (“b indices”).
+    /// Since the domain shrinks with every round, the indices of later rounds are the
+    /// first round's indices reduced modulo the round's domain length. Consequently, indices
+    /// within one round can collide; they are listed once per collinearity check regardless.
+    pub fn fri_query_indices(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+    ) -> Result<Vec<Vec<usize>>, VerificationError> {
+        self.verify_returning_fri_query_indices(claim, proof)
+    }
+
+    /// Like [`verify`](Self::verify), but first makes sure that the claim's public input
+    /// matches the given commitment. Fails if the commitment is not the
+    /// [claim's input commitment](Claim::input_commitment). See also
+    /// [`prove_with_input_commitment`](Self::prove_with_input_commitment).
+    pub fn verify_with_input_commitment(
+        &self,
+        input_commitment: Digest,
+        claim: &Claim,
+        proof: &Proof,
+    ) -> Result<(), VerificationError> {
+        if input_commitment != claim.input_commitment() {
+            return Err(VerificationError::InputCommitmentMismatch);
+        }
+        self.verify(claim, proof)
+    }
+
+    /// Verify a [`BatchProof`] generated by
+    /// [`prove_shared_program`](Self::prove_shared_program). Succeeds if and only if the proof
+    /// of every instance verifies for the instance's [claim](BatchProof::claims); the first
+    /// failure is returned. In particular, a batch without instances is accepted.
+    pub fn verify_batch(&self, batch_proof: &BatchProof) -> Result<(), VerificationError> {
+        let claims = batch_proof.claims();
+        for (claim, instance) in claims.iter().zip(&batch_proof.instances) {
+            self.verify(claim, &instance.proof)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the indices of the FRI codewords that were queried, one list per round. See
+    /// [`fri_query_indices`](Self::fri_query_indices).
+    fn verify_returning_fri_query_indices(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+    ) -> Result<Vec<Vec<usize>>, VerificationError> {
         profiler!(start "deserialize");
         let mut proof_stream = ProofStream::try_from(proof)?;
         profiler!(stop "deserialize");
@@ -733,7 +1074,7 @@ impl Stark {
 
         profiler!(start "derive additional parameters");
         let log_2_padded_height = proof_stream.dequeue()?.try_into_log2_padded_height()?;
-        let padded_height = 1 << log_2_padded_height;
+        let padded_height = padded_height_from_log2(log_2_padded_height)?;
         let fri = self.derive_fri(padded_height)?;
         let merkle_tree_height = fri.domain.length.ilog2() as usize;
         profiler!(stop "derive additional parameters");
@@ -868,7 +1209,8 @@ impl Stark {
 
         // verify low degree of combination polynomial with FRI
         profiler!(start "FRI");
-        let revealed_fri_indices_and_elements = fri.verify(&mut proof_stream)?;
+        let (revealed_fri_indices_and_elements, fri_query_indices) =
+            fri.verify_and_report_query_indices(&mut proof_stream)?;
         let (revealed_current_row_indices, revealed_fri_values): (Vec<_>, Vec<_>) =
             revealed_fri_indices_and_elements.into_iter().unzip();
         profiler!(stop "FRI");
@@ -890,12 +1232,12 @@ impl Stark {
             index_iter.zip_eq(leaves).collect()
         };
         profiler!(start "Merkle verify (base tree)" ("hash"));
-        let base_merkle_tree_inclusion_proof = MerkleTreeInclusionProof {
-            tree_height: merkle_tree_height,
-            indexed_leafs: index_leaves(leaf_digests_base),
-            authentication_structure: base_authentication_structure,
-        };
-        if !base_merkle_tree_inclusion_proof.verify(base_merkle_tree_root) {
+        if !merkle::verify_inclusion(
+            base_merkle_tree_root,
+            merkle_tree_height,
+            index_leaves(leaf_digests_base),
+            base_authentication_structure,
+        ) {
             return Err(VerificationError::BaseCodewordAuthenticationFailure);
         }
         profiler!(stop "Merkle verify (base tree)");
@@ -915,12 +1257,12 @@ impl Stark {
         profiler!(stop "dequeue extension elements");
 
         profiler!(start "Merkle verify (extension tree)" ("hash"));
-        let ext_merkle_tree_inclusion_proof = MerkleTreeInclusionProof {
-            tree_height: merkle_tree_height,
-            indexed_leafs: index_leaves(leaf_digests_ext),
-            authentication_structure: ext_authentication_structure,
-        };
-        if !ext_merkle_tree_inclusion_proof.verify(extension_tree_merkle_root) {
+        if !merkle::verify_inclusion(
+            extension_tree_merkle_root,
+            merkle_tree_height,
+            index_leaves(leaf_digests_ext),
+            ext_authentication_structure,
+        ) {
             return Err(VerificationError::ExtensionCodewordAuthenticationFailure);
         }
         profiler!(stop "Merkle verify (extension tree)");
@@ -936,12 +1278,12 @@ impl Stark {
         profiler!(stop "dequeue quotient segments' elements");
 
         profiler!(start "Merkle verify (combined quotient)" ("hash"));
-        let quot_merkle_tree_inclusion_proof = MerkleTreeInclusionProof {
-            tree_height: merkle_tree_height,
-            indexed_leafs: index_leaves(revealed_quotient_segments_digests),
-            authentication_structure: revealed_quotient_authentication_structure,
-        };
-        if !quot_merkle_tree_inclusion_proof.verify(quotient_codeword_merkle_root) {
+        if !merkle::verify_inclusion(
+            quotient_codeword_merkle_root,
+            merkle_tree_height,
+            index_leaves(revealed_quotient_segments_digests),
+            revealed_quotient_authentication_structure,
+        ) {
             return Err(VerificationError::QuotientCodewordAuthenticationFailure);
         }
         profiler!(stop "Merkle verify (combined quotient)");
@@ -965,7 +1307,7 @@ impl Stark {
         };
 
         for (row_idx, base_row, ext_row, quotient_segments_elements, fri_value) in izip!(
-            revealed_current_row_indices,
+            revealed_current_row_indices.iter().copied(),
             base_table_rows,
             ext_table_rows,
             revealed_quotient_segments_elements,
@@ -1019,7 +1361,47 @@ impl Stark {
             profiler!(stop "combination codeword equality");
         }
         profiler!(stop "linear combination");
-        Ok(())
+        Ok(fri_query_indices)
+    }
+
+    /// An a-priori estimate of the work required to [verify](Self::verify) the given
+    /// [`Proof`]. The estimate is derived from the proof's padded height and the
+    /// parameters of the STARK only; the proof is not otherwise inspected.
+    ///
+    /// The cost model counts
+    /// - the number of hash invocations needed to authenticate all Merkle paths, ignoring
+    ///   potential savings from shared authentication path nodes,
+    /// - the number of colinearity checks performed in [FRI](Fri), and
+    /// - the number of constraints evaluated in the out-of-domain point.
+    pub fn estimated_verification_cost(
+        &self,
+        proof: &Proof,
+    ) -> Result<VerificationCost, VerificationError> {
+        let padded_height = proof.padded_height()?;
+        let fri = self.derive_fri(padded_height)?;
+        let num_fri_rounds = fri.num_rounds();
+        let merkle_tree_height = fri.domain.length.ilog2() as usize;
+
+        let num_main_merkle_trees = 3;
+        let main_merkle_path_hashes =
+            num_main_merkle_trees * self.num_collinearity_checks * merkle_tree_height;
+
+        // FRI authenticates the “a” indices of the first round and the “b” indices of every
+        // round that has a next round. Each round halves the height of the Merkle tree.
+        let fri_first_round_merkle_path_hashes = self.num_collinearity_checks * merkle_tree_height;
+        let fri_b_indices_merkle_path_hashes = (0..num_fri_rounds)
+            .map(|round| self.num_collinearity_checks * (merkle_tree_height - round))
+            .sum::<usize>();
+
+        let num_merkle_path_hashes = main_merkle_path_hashes
+            + fri_first_round_merkle_path_hashes
+            + fri_b_indices_merkle_path_hashes;
+
+        Ok(VerificationCost {
+            num_merkle_path_hashes,
+            num_fri_colinearity_checks: self.num_collinearity_checks * num_fri_rounds,
+            num_constraint_evaluations: MasterExtTable::NUM_CONSTRAINTS,
+        })
     }
 
     fn hash_quotient_segment_elements(quotient_segment_rows: &[QuotientSegments]) -> Vec<Digest> {
@@ -1264,6 +1646,225 @@ impl<'a> Arbitrary<'a> for Stark {
     }
 }
 
+/// An a-priori estimate of the length of a [`Proof`], _i.e._, the number of [`BFieldElement`]s
+/// it consists of, for an execution trace of the given padded height. The remaining
+/// parameters of the [`Stark`] are the [default](Stark::default) ones for the given FRI
+/// expansion factor. The estimate does not depend on the [`Claim`]: a proof does not contain
+/// its claim.
+///
+/// All parts of a proof have a length that is known in advance, except for the Merkle
+/// trees' authentication structures: their length depends on the revealed indices, which are
+/// sampled during proving. For those, the expected length given uniformly random indices is
+/// used. For real proofs, the estimate is within 1% of the actual length.
+///
+/// # Panics
+///
+/// Panics if
+/// - the FRI expansion factor is not a power of two greater than one, or
+/// - the padded height results in a FRI domain that is too large.
+pub fn estimated_proof_length(padded_height: usize, fri_expansion_factor: usize) -> usize {
+    assert!(fri_expansion_factor.is_power_of_two());
+    let log2_of_fri_expansion_factor = fri_expansion_factor.ilog2() as usize;
+    let stark = Stark::new(
+        Stark::default().security_level,
+        log2_of_fri_expansion_factor,
+    );
+    let fri = stark.derive_fri(padded_height).unwrap();
+    let num_fri_rounds = fri.num_rounds();
+    let num_indices = stark.num_collinearity_checks;
+    let merkle_tree_height = fri.domain.length.ilog2() as usize;
+    let auth_structure = |tree_height| {
+        let len = expected_authentication_structure_len(tree_height, num_indices);
+        vec![Digest::default(); len]
+    };
+
+    let mut proof_stream = ProofStream::new();
+    proof_stream.enqueue(ProofItem::Log2PaddedHeight(padded_height.ilog2()));
+    for _ in 0..3 {
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+    }
+    for _ in 0..2 {
+        let ood_base_row = Box::new([XFieldElement::zero(); NUM_BASE_COLUMNS]);
+        let ood_ext_row = Box::new([XFieldElement::zero(); NUM_EXT_COLUMNS]);
+        proof_stream.enqueue(ProofItem::OutOfDomainBaseRow(ood_base_row));
+        proof_stream.enqueue(ProofItem::OutOfDomainExtRow(ood_ext_row));
+    }
+    let ood_quotient_segments = [XFieldElement::zero(); NUM_QUOTIENT_SEGMENTS];
+    proof_stream.enqueue(ProofItem::OutOfDomainQuotientSegments(
+        ood_quotient_segments,
+    ));
+
+    for _ in 0..=num_fri_rounds {
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+    }
+    let last_codeword_len = fri.domain.length >> num_fri_rounds;
+    let last_codeword = vec![XFieldElement::zero(); last_codeword_len];
+    let last_polynomial = Polynomial::new(last_codeword.clone());
+    proof_stream.enqueue(ProofItem::FriCodeword(last_codeword));
+    proof_stream.enqueue(ProofItem::FriPolynomial(last_polynomial));
+
+    // FRI reveals the “a” indices of the first round and the “b” indices of every round
+    // that has a next round. Each round halves the height of the Merkle tree.
+    let fri_response_tree_heights = [merkle_tree_height]
+        .into_iter()
+        .chain((0..num_fri_rounds).map(|round| merkle_tree_height - round));
+    for tree_height in fri_response_tree_heights {
+        let fri_response = FriResponse {
+            auth_structure: auth_structure(tree_height),
+            revealed_leaves: vec![XFieldElement::zero(); num_indices],
+        };
+        proof_stream.enqueue(ProofItem::FriResponse(fri_response));
+    }
+
+    let base_rows = vec![[BFieldElement::zero(); NUM_BASE_COLUMNS]; num_indices];
+    let ext_rows = vec![[XFieldElement::zero(); NUM_EXT_COLUMNS]; num_indices];
+    let quotient_segments_rows = vec![[XFieldElement::zero(); NUM_QUOTIENT_SEGMENTS]; num_indices];
+    proof_stream.enqueue(ProofItem::MasterBaseTableRows(base_rows));
+    proof_stream.enqueue(ProofItem::AuthenticationStructure(auth_structure(
+        merkle_tree_height,
+    )));
+    proof_stream.enqueue(ProofItem::MasterExtTableRows(ext_rows));
+    proof_stream.enqueue(ProofItem::AuthenticationStructure(auth_structure(
+        merkle_tree_height,
+    )));
+    proof_stream.enqueue(ProofItem::QuotientSegmentsElements(quotient_segments_rows));
+    proof_stream.enqueue(ProofItem::AuthenticationStructure(auth_structure(
+        merkle_tree_height,
+    )));
+
+    Proof::from(proof_stream).0.len()
+}
+
+/// The expected number of [`Digest`]s in the authentication structure of a Merkle tree of the
+/// given height for the given number of leaf indices, each sampled uniformly at random.
+///
+/// A node on some layer is part of the authentication structure if and only if no index
+/// falls into the node's subtree, but at least one index falls into its sibling's subtree.
+fn expected_authentication_structure_len(tree_height: usize, num_indices: usize) -> usize {
+    let num_indices = num_indices as f64;
+    let expected_len = (1..=tree_height)
+        .map(|layer_height| {
+            let num_nodes_in_layer = (1_u64 << layer_height) as f64;
+            let prob_node_is_missed = (1.0 - 1.0 / num_nodes_in_layer).powf(num_indices);
+            let prob_siblings_are_missed = (1.0 - 2.0 / num_nodes_in_layer).powf(num_indices);
+            num_nodes_in_layer * (prob_node_is_missed - prob_siblings_are_missed)
+        })
+        .sum::<f64>();
+
+    expected_len.round() as usize
+}
+
+/// An a-priori estimate of the work required to verify a [`Proof`].
+/// See [`Stark::estimated_verification_cost`] for details on the cost model.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct VerificationCost {
+    /// The number of hash invocations required to authenticate all Merkle paths.
+    pub num_merkle_path_hashes: usize,
+
+    /// The number of colinearity checks performed in [FRI](Fri).
+    pub num_fri_colinearity_checks: usize,
+
+    /// The number of AIR constraints evaluated by the verifier.
+    pub num_constraint_evaluations: usize,
+}
+
+/// The parts of a [`Claim`] that a successful [verification](Stark::verify_with_audit)
+/// confirmed against the proof's transcript.
+///
+/// All fields are cryptographically bound: the entire claim is absorbed into the Fiat-Shamir
+/// state, and the public input and output additionally determine the terminal values of the
+/// corresponding evaluation arguments. No part of the claim is trusted without verification.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct VerificationAudit {
+    /// The digest of the program whose execution was proven.
+    pub program_digest: Digest,
+
+    /// The number of public input elements.
+    pub input_len: usize,
+
+    /// The number of public output elements.
+    pub output_len: usize,
+}
+
+/// The wall-clock time spent in the major phases of [proving](Stark::prove_timed).
+///
+/// Phases that are entered several times, for example one Merkle tree commitment per
+/// table, accumulate. Every step of proving is charged to exactly one phase, such that the
+/// phases add up to the [total](Self::total).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ProvingTimings {
+    /// Deriving the padded height, the [FRI](Fri) parameters, and the quotient domain.
+    pub parameter_derivation: Duration,
+
+    /// Absorbing the claim and Merkle roots, and sampling challenges and weights.
+    pub fiat_shamir: Duration,
+
+    /// Creating, padding, randomizing, and extending the master tables.
+    pub trace_generation: Duration,
+
+    /// Low-degree extending the master tables.
+    pub low_degree_extension: Duration,
+
+    /// Hashing rows, building Merkle trees, computing authentication structures, and
+    /// assembling the proof.
+    pub merkle_commitment: Duration,
+
+    /// Evaluating the AIR constraints and computing the quotient segments.
+    pub quotient_computation: Duration,
+
+    /// Evaluating out-of-domain rows, linear combination, the DEEP update, and extending the
+    /// combined DEEP codeword to the [FRI](Fri) domain.
+    pub deep_composition: Duration,
+
+    /// Proving low degree of the combined DEEP codeword using [FRI](Fri).
+    pub fri: Duration,
+
+    /// The entire proving process.
+    pub total: Duration,
+}
+
+impl ProvingTimings {
+    /// The sum of all individually measured phases. Equals the [total](Self::total).
+    pub fn sum_of_phases(&self) -> Duration {
+        self.parameter_derivation
+            + self.fiat_shamir
+            + self.trace_generation
+            + self.low_degree_extension
+            + self.merkle_commitment
+            + self.quotient_computation
+            + self.deep_composition
+            + self.fri
+    }
+}
+
+/// Charges the wall-clock time between consecutive checkpoints to the phase ending at the
+/// later checkpoint. Since no time passes between two phases, the phases partition the
+/// [total](Self::total).
+struct PhaseClock {
+    start: Instant,
+    last_checkpoint: Instant,
+}
+
+impl PhaseClock {
+    fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last_checkpoint: now,
+        }
+    }
+
+    fn charge(&mut self, phase: &mut Duration) {
+        let now = Instant::now();
+        *phase += now - self.last_checkpoint;
+        self.last_checkpoint = now;
+    }
+
+    fn total(&self) -> Duration {
+        self.last_checkpoint - self.start
+    }
+}
+
 /// Fiat-Shamir-sampled challenges to compress a row into a single
 /// [extension field element][XFieldElement].
 struct LinearCombinationWeights {
@@ -1330,6 +1931,7 @@ pub(crate) mod tests {
     use crate::instruction::Instruction;
     use crate::op_stack::OpStackElement;
     use crate::program::NonDeterminism;
+    use crate::program::Program;
     use crate::shared_tests::*;
     use crate::table::cascade_table::ExtCascadeTable;
     use crate::table::challenges::ChallengeId::StandardInputIndeterminate;
@@ -1357,8 +1959,10 @@ pub(crate) mod tests {
     use crate::table::table_column::ProcessorBaseTableColumn;
     use crate::table::table_column::ProcessorExtTableColumn::InputTableEvalArg;
     use crate::table::table_column::ProcessorExtTableColumn::OutputTableEvalArg;
+    use crate::table::table_column::ProgramBaseTableColumn;
     use crate::table::table_column::RamBaseTableColumn;
     use crate::table::u32_table::ExtU32Table;
+    use crate::table::ConstraintType;
     use crate::triton_program;
     use crate::vm::tests::*;
     use crate::PublicInput;
@@ -1735,6 +2339,24 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn quotient_degree_bound_is_determined_by_air_target_degree() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        for log_2_padded_height in 1..=12 {
+            let padded_height = 1 << log_2_padded_height;
+            let interpolant_degree =
+                interpolant_degree(padded_height, stark.num_trace_randomizers) as usize;
+
+            // Initial and terminal constraints have the zerofier of the lowest degree, 1.
+            let expected_bound = AIR_TARGET_DEGREE as usize * interpolant_degree - 1;
+            let quotient_degree_bound = stark.quotient_degree_bound(padded_height);
+            assert!(expected_bound == quotient_degree_bound);
+
+            let max_degree = stark.derive_max_degree(padded_height) as usize;
+            assert!(quotient_degree_bound <= max_degree);
+        }
+    }
+
     #[test]
     fn number_of_quotient_degree_bounds_match_number_of_constraints() {
         let base_row = Array1::<BFieldElement>::zeros(NUM_BASE_COLUMNS);
@@ -2212,6 +2834,120 @@ pub(crate) mod tests {
         check_cross_table_constraints(mbt, met, &challenges);
     }
 
+    /// All constraints, identified by type and index, that do not evaluate to zero on the
+    /// given trace.
+    fn constraint_violations(
+        master_base_trace_table: ArrayView2<BFieldElement>,
+        master_ext_trace_table: ArrayView2<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<(ConstraintType, usize)> {
+        let mbt = master_base_trace_table;
+        let met = master_ext_trace_table;
+        let last_row_idx = mbt.nrows() - 1;
+        let violations = |constraint_type, evaluations: Vec<XFieldElement>| {
+            evaluations
+                .into_iter()
+                .positions(|evaluation| !evaluation.is_zero())
+                .map(move |constraint_idx| (constraint_type, constraint_idx))
+                .collect_vec()
+        };
+
+        let initial =
+            MasterExtTable::evaluate_initial_constraints(mbt.row(0), met.row(0), challenges);
+        let consistency = (0..=last_row_idx).flat_map(|row_idx| {
+            let (base_row, ext_row) = (mbt.row(row_idx), met.row(row_idx));
+            let evaluations =
+                MasterExtTable::evaluate_consistency_constraints(base_row, ext_row, challenges);
+            violations(ConstraintType::Consistency, evaluations)
+        });
+        let transition = (0..last_row_idx).flat_map(|row_idx| {
+            let evaluations = MasterExtTable::evaluate_transition_constraints(
+                mbt.row(row_idx),
+                met.row(row_idx),
+                mbt.row(row_idx + 1),
+                met.row(row_idx + 1),
+                challenges,
+            );
+            violations(ConstraintType::Transition, evaluations)
+        });
+        let (last_base_row, last_ext_row) = (mbt.row(last_row_idx), met.row(last_row_idx));
+        let terminal =
+            MasterExtTable::evaluate_terminal_constraints(last_base_row, last_ext_row, challenges);
+
+        violations(ConstraintType::Initial, initial)
+            .into_iter()
+            .chain(consistency)
+            .chain(transition)
+            .chain(violations(ConstraintType::Terminal, terminal))
+            .unique()
+            .collect()
+    }
+
+    /// Mutate individual cells of a valid execution trace and check that every mutation is
+    /// detected by at least one constraint. The auxiliary columns are re-computed from the
+    /// mutated trace, like a cheating prover would do. An undetected mutation indicates an
+    /// under-constrained column, _i.e._, a soundness gap.
+    #[test]
+    fn every_mutation_of_execution_trace_violates_some_constraint() {
+        let program = triton_program!(
+            push 3 push 4 mul
+            push 100 write_mem 1 pop 1
+            push 100 read_mem 1 pop 1
+            write_io 1 halt
+        );
+        let (_, claim, mut master_base_table) =
+            master_base_table_for_low_security_level(ProgramAndInput::new(program));
+        let challenges = Challenges::placeholder(&claim);
+        master_base_table.pad();
+
+        let master_ext_table = master_base_table.extend(&challenges);
+        let violations = constraint_violations(
+            master_base_table.trace_table(),
+            master_ext_table.trace_table(),
+            &challenges,
+        );
+        assert!(violations.is_empty());
+
+        let mutations = [
+            (ProcessorBaseTableColumn::CLK.master_base_table_index(), 3),
+            (ProcessorBaseTableColumn::IP.master_base_table_index(), 1),
+            (ProcessorBaseTableColumn::IB1.master_base_table_index(), 2),
+            (ProcessorBaseTableColumn::NIA.master_base_table_index(), 4),
+            (ProcessorBaseTableColumn::ST0.master_base_table_index(), 3),
+            (ProcessorBaseTableColumn::ST1.master_base_table_index(), 5),
+            (
+                OpStackBaseTableColumn::StackPointer.master_base_table_index(),
+                1,
+            ),
+            (RamBaseTableColumn::RamPointer.master_base_table_index(), 0),
+            (RamBaseTableColumn::RamValue.master_base_table_index(), 1),
+            (
+                ProgramBaseTableColumn::Instruction.master_base_table_index(),
+                2,
+            ),
+        ];
+
+        let mut undetected_mutations = vec![];
+        for (column_idx, row_idx) in mutations {
+            let mut mutated_base_table = master_base_table.clone();
+            mutated_base_table.trace_table_mut()[[row_idx, column_idx]] += bfe!(1);
+            let mutated_ext_table = mutated_base_table.extend(&challenges);
+            let violations = constraint_violations(
+                mutated_base_table.trace_table(),
+                mutated_ext_table.trace_table(),
+                &challenges,
+            );
+            if violations.is_empty() {
+                undetected_mutations.push((column_idx, row_idx));
+            }
+        }
+
+        assert!(
+            undetected_mutations.is_empty(),
+            "Undetected mutations (column, row): {undetected_mutations:?}"
+        );
+    }
+
     #[test]
     fn derived_constraints_evaluate_to_zero_on_halt() {
         derived_constraints_evaluate_to_zero(test_program_for_halt());
@@ -2289,6 +3025,42 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn proof_binds_to_input_commitment() {
+        let program = triton_program!(read_io 2 add write_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![3, 4]);
+        let (aet, output) = program
+            .trace_execution(public_input.clone(), NonDeterminism::default())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(public_input.individual_tokens)
+            .with_output(output);
+        let input_commitment = claim.input_commitment();
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark
+            .prove_with_input_commitment(input_commitment, &claim, &aet)
+            .unwrap();
+        let_assert!(Ok(()) = stark.verify_with_input_commitment(input_commitment, &claim, &proof));
+
+        // the claim, including its input, is part of the transcript
+        let_assert!(Ok(()) = stark.verify(&claim, &proof));
+        let other_claim = claim.clone().with_input(bfe_vec![3, 5]);
+        let_assert!(Err(_) = stark.verify(&other_claim, &proof));
+
+        let bogus_commitment = Claim::new(Digest::default())
+            .with_input(bfe_vec![3, 5])
+            .input_commitment();
+        let_assert!(
+            Err(VerificationError::InputCommitmentMismatch) =
+                stark.verify_with_input_commitment(bogus_commitment, &claim, &proof)
+        );
+        let_assert!(
+            Err(ProvingError::InputCommitmentMismatch) =
+                stark.prove_with_input_commitment(bogus_commitment, &claim, &aet)
+        );
+    }
+
     #[test]
     fn prove_and_verify_simple_program() {
         prove_and_verify(
@@ -2315,6 +3087,30 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn proofs_for_every_padded_height_of_the_verification_benchmark_verify() {
+        // The same Fibonacci indices as in benchmark `verify_padded_heights`.
+        let fibonacci_indices = [40, 80, 160, 320, 640];
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = FIBONACCI_SEQUENCE.clone();
+
+        let mut padded_heights = vec![];
+        for index in fibonacci_indices {
+            let (aet, output) = program
+                .trace_execution(bfe_vec![index].into(), [].into())
+                .unwrap();
+            let claim = Claim::about_program(&program)
+                .with_input(bfe_vec![index])
+                .with_output(output);
+            let proof = stark.prove(&claim, &aet).unwrap();
+            let_assert!(Ok(()) = stark.verify(&claim, &proof));
+            padded_heights.push(proof.padded_height().unwrap());
+        }
+
+        // one proof per padded height
+        assert!(padded_heights.iter().all_unique(), "{padded_heights:?}");
+    }
+
     #[test]
     fn constraints_evaluate_to_zero_on_many_u32_operations() {
         let many_u32_instructions =
@@ -2330,6 +3126,40 @@ pub(crate) mod tests {
         );
     }
 
+    fn prove_three_executions_of_doubling_program() -> BatchProof {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let instances = [3, 5, 7].map(|input| {
+            (
+                PublicInput::from(bfe_array![input]),
+                NonDeterminism::default(),
+            )
+        });
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        stark.prove_shared_program(&program, &instances).unwrap()
+    }
+
+    #[test]
+    fn batch_proof_of_executions_sharing_a_program_verifies() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let batch_proof = prove_three_executions_of_doubling_program();
+        assert!(3 == batch_proof.instances.len());
+        assert!(let Ok(()) = stark.verify_batch(&batch_proof));
+
+        for (claim, instance) in batch_proof.claims().iter().zip(&batch_proof.instances) {
+            assert!(vec![bfe!(2) * claim.input[0]] == claim.output);
+            assert!(let Ok(()) = stark.verify(claim, &instance.proof));
+        }
+    }
+
+    #[test]
+    fn batch_proof_with_tampered_instance_does_not_verify() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let mut batch_proof = prove_three_executions_of_doubling_program();
+        batch_proof.instances[1].output = bfe_vec![11];
+        assert!(let Err(_) = stark.verify_batch(&batch_proof));
+    }
+
     #[proptest]
     fn verifying_arbitrary_proof_does_not_panic(
         #[strategy(arb())] stark: Stark,
@@ -2339,6 +3169,283 @@ pub(crate) mod tests {
         let _verdict = stark.verify(&claim, &proof);
     }
 
+    #[test]
+    fn proof_is_bound_to_entire_claim() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(bfe_vec![42]);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (aet, _) = program
+            .trace_execution(
+                PublicInput::new(claim.input.clone()),
+                NonDeterminism::default(),
+            )
+            .unwrap();
+        let proof = stark.prove(&claim, &aet).unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+
+        let claim_with_wrong_input = claim.clone().with_input(bfe_vec![20]);
+        assert!(let Err(_) = stark.verify(&claim_with_wrong_input, &proof));
+
+        let claim_with_wrong_output = claim.clone().with_output(bfe_vec![40]);
+        assert!(let Err(_) = stark.verify(&claim_with_wrong_output, &proof));
+
+        let other_program = triton_program!(read_io 1 push 3 mul write_io 1 halt);
+        let claim_with_wrong_digest = Claim {
+            program_digest: other_program.hash(),
+            ..claim
+        };
+        assert!(let Err(_) = stark.verify(&claim_with_wrong_digest, &proof));
+    }
+
+    #[test]
+    fn proving_from_decoded_trace_gives_verifiable_proof() {
+        let program = triton_program!(read_io 1 push 5 lt push 7 split write_io 3 halt);
+        let (aet, output) = program
+            .trace_execution(bfe_vec![3].into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![3])
+            .with_output(output);
+
+        let decoded_aet = *AlgebraicExecutionTrace::decode(&aet.encode()).unwrap();
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove_from_trace(&claim, &decoded_aet).unwrap();
+        let_assert!(Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn proving_from_trace_of_non_halting_execution_fails() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let (mut aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        aet.processor_trace = aet.processor_trace.slice(s![..-1, ..]).to_owned();
+
+        let claim = Claim::about_program(&program);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Err(ProvingError::TraceDoesNotHalt) = stark.prove_from_trace(&claim, &aet));
+    }
+
+    #[test]
+    fn proving_from_trace_of_other_program_fails() {
+        let program = triton_program!(halt);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+
+        let other_program = triton_program!(nop halt);
+        let claim = Claim::about_program(&other_program);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(
+            Err(ProvingError::ProgramDigestMismatch) = stark.prove_from_trace(&claim, &aet)
+        );
+    }
+
+    #[test]
+    fn fri_query_indices_of_real_proof_lie_within_each_round_s_domain() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, claim, proof) = prove_with_low_security_level(&program);
+        let_assert!(Ok(indices) = stark.fri_query_indices(&claim, &proof));
+
+        let fri = stark.derive_fri(proof.padded_height().unwrap()).unwrap();
+        assert!(fri.num_rounds() + 1 == indices.len());
+
+        let mut domain_length = fri.domain.length;
+        for round_indices in &indices {
+            assert!(stark.num_collinearity_checks == round_indices.len());
+            assert!(round_indices.iter().all(|&i| i < domain_length));
+            domain_length /= 2;
+        }
+
+        // indices of consecutive rounds are congruent modulo the smaller round's domain length
+        let mut domain_length = fri.domain.length;
+        for (round_indices, next_round_indices) in indices.iter().tuple_windows() {
+            domain_length /= 2;
+            for (&index, &next_index) in round_indices.iter().zip_eq(next_round_indices) {
+                assert!(index % domain_length == next_index);
+            }
+        }
+    }
+
+    #[test]
+    fn fri_query_indices_of_invalid_proof_cannot_be_derived() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, claim, proof) = prove_with_low_security_level(&program);
+        let other_claim = claim.with_output(bfe_vec![13]);
+        let_assert!(Err(_) = stark.fri_query_indices(&other_claim, &proof));
+    }
+
+    #[test]
+    fn real_proof_has_the_expected_proof_schema() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, _, proof) = prove_with_low_security_level(&program);
+        let_assert!(Ok(schema) = stark.expected_proof_schema(proof.padded_height().unwrap()));
+
+        let proof_stream = ProofStream::try_from(&proof).unwrap();
+        let item_variants = proof_stream
+            .items
+            .iter()
+            .map(ProofItemVariant::from)
+            .collect_vec();
+        assert!(schema == item_variants);
+    }
+
+    fn prove_with_low_security_level(program: &Program) -> (Stark, Claim, Proof) {
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(program).with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+        (stark, claim, proof)
+    }
+
+    #[test]
+    fn minimal_stark_for_testing_can_prove_and_verify() {
+        let stark = Stark::minimal_for_testing();
+        assert!(1 == stark.num_collinearity_checks);
+        assert!(2 == stark.fri_expansion_factor);
+
+        let program = triton_program!(push 1 push 2 add write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+        let proof = stark.prove(&claim, &aet).unwrap();
+        let_assert!(Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn starks_differing_only_in_cycle_budget_are_compatible() {
+        let stark = Stark::default();
+        let other_stark = stark.with_max_num_cycles(42);
+        assert!(stark.compatible_with(&other_stark));
+        assert!(other_stark.compatible_with(&stark));
+    }
+
+    #[test]
+    fn stark_serialized_without_cycle_budget_deserializes_with_default_budget() {
+        let stark = Stark::default().with_max_num_cycles(42);
+        let mut json = serde_json::to_value(stark).unwrap();
+        json.as_object_mut().unwrap().remove("max_num_cycles");
+
+        let stark: Stark = serde_json::from_value(json).unwrap();
+        assert!(DEFAULT_MAX_NUM_CYCLES == stark.max_num_cycles);
+        assert!(Stark::default() == stark);
+    }
+
+    #[test]
+    fn starks_differing_in_fri_expansion_factor_are_incompatible() {
+        let stark = Stark::new(160, 2);
+        let other_stark = Stark::new(160, 3);
+        assert!(!stark.compatible_with(&other_stark));
+        assert!(!other_stark.compatible_with(&stark));
+    }
+
+    #[test]
+    fn verifying_proof_with_too_large_padded_height_gives_err() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        for log_2_padded_height in [62, 63, 64, u32::MAX] {
+            let proof = proof_with_log_2_padded_height(log_2_padded_height);
+            assert!(let Err(_) = stark.verify(&Claim::default(), &proof));
+            assert!(let Err(_) = stark.estimated_verification_cost(&proof));
+        }
+    }
+
+    fn proof_with_log_2_padded_height(log_2_padded_height: u32) -> Proof {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(log_2_padded_height));
+        proof_stream.into()
+    }
+
+    #[test]
+    fn estimated_proof_length_is_close_to_actual_proof_length() {
+        let program = triton_program!(push 1 push 2 add write_io 1 halt);
+        let (aet, output) = program.trace_execution([].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+        let stark = Stark::default();
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let padded_height = proof.padded_height().unwrap();
+        let estimate = estimated_proof_length(padded_height, stark.fri_expansion_factor);
+        let actual = proof.0.len();
+        let tolerance = estimate / 100;
+        assert!(
+            estimate.abs_diff(actual) <= tolerance,
+            "{estimate} vs {actual}"
+        );
+    }
+
+    #[test]
+    fn estimated_verification_cost_grows_with_padded_height() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let small_proof = proof_with_log_2_padded_height(8);
+        let large_proof = proof_with_log_2_padded_height(16);
+
+        let small_cost = stark.estimated_verification_cost(&small_proof).unwrap();
+        let large_cost = stark.estimated_verification_cost(&large_proof).unwrap();
+
+        assert!(small_cost.num_merkle_path_hashes < large_cost.num_merkle_path_hashes);
+        assert!(small_cost.num_fri_colinearity_checks < large_cost.num_fri_colinearity_checks);
+        assert!(small_cost.num_constraint_evaluations == large_cost.num_constraint_evaluations);
+    }
+
+    #[test]
+    fn estimating_verification_cost_of_proof_without_padded_height_fails() {
+        let proof: Proof = ProofStream::new().into();
+        let_assert!(Err(err) = Stark::default().estimated_verification_cost(&proof));
+        let_assert!(VerificationError::ProofStreamError(_) = err);
+    }
+
+    fn timed_proof_of_fibonacci_sequence() -> (Claim, Proof, ProvingTimings) {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let (aet, output) = program
+            .trace_execution(bfe_vec![100].into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![100])
+            .with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (proof, timings) = stark.prove_timed(&claim, &aet).unwrap();
+        (claim, proof, timings)
+    }
+
+    #[test]
+    fn timed_proof_verifies_and_phase_timings_add_up_to_the_total() {
+        let (claim, proof, timings) = timed_proof_of_fibonacci_sequence();
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Ok(()) = stark.verify(&claim, &proof));
+
+        assert!(Duration::ZERO < timings.fiat_shamir);
+        assert!(Duration::ZERO < timings.trace_generation);
+        assert!(Duration::ZERO < timings.low_degree_extension);
+        assert!(Duration::ZERO < timings.merkle_commitment);
+        assert!(Duration::ZERO < timings.quotient_computation);
+        assert!(Duration::ZERO < timings.deep_composition);
+        assert!(Duration::ZERO < timings.fri);
+        assert!(timings.total == timings.sum_of_phases());
+    }
+
+    #[test]
+    fn audit_of_valid_proof_reflects_claimed_program_digest_and_io_lengths() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let (aet, output) = program
+            .trace_execution(bfe_vec![100].into(), [].into())
+            .unwrap();
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![100])
+            .with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let_assert!(Ok(audit) = stark.verify_with_audit(&claim, &proof));
+        assert!(program.hash() == audit.program_digest);
+        assert!(1 == audit.input_len);
+        assert!(1 == audit.output_len);
+    }
+
+    #[test]
+    fn audit_of_proof_for_different_claim_cannot_be_obtained() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (stark, claim, proof) = prove_with_low_security_level(&program);
+        let other_claim = claim.with_output(bfe_vec![13]);
+        let_assert!(Err(_) = stark.verify_with_audit(&other_claim, &proof));
+    }
+
     #[proptest]
     fn negative_log_2_floor(
         #[strategy(arb())]
@@ -2400,11 +3507,11 @@ pub(crate) mod tests {
     ) {
         let x_pow_n = x.mod_pow_u32(N as u32);
         let evaluate_segment = |(segment_idx, segment): (_, &Polynomial<_>)| {
-            segment.evaluate(x_pow_n) * x.mod_pow_u32(segment_idx as u32)
+            segment.evaluate::<_, FF>(x_pow_n) * x.mod_pow_u32(segment_idx as u32)
         };
         let evaluated_segments = segments.iter().enumerate().map(evaluate_segment);
         let sum_of_evaluated_segments = evaluated_segments.fold(FF::zero(), |acc, x| acc + x);
-        assert!(f.evaluate(x) == sum_of_evaluated_segments);
+        assert!(f.evaluate::<_, FF>(x) == sum_of_evaluated_segments);
     }
 
     fn assert_segments_degrees_are_small_enough<const N: usize, FF: FiniteField>(
@@ -2598,10 +3705,14 @@ pub(crate) mod tests {
             .map(|(segment_index, segment_polynomial)| {
                 let point_to_the_seg_idx = random_point.mod_pow_u32(segment_index);
                 let point_to_the_num_seg = random_point.mod_pow_u32(num_segments as u32);
-                point_to_the_seg_idx * segment_polynomial.evaluate(point_to_the_num_seg)
+                point_to_the_seg_idx
+                    * segment_polynomial.evaluate::<_, XFieldElement>(point_to_the_num_seg)
             })
             .sum::<XFieldElement>();
-        prop_assert_eq!(segments_evaluated, polynomial.evaluate(random_point));
+        prop_assert_eq!(
+            segments_evaluated,
+            polynomial.evaluate::<_, XFieldElement>(random_point)
+        );
 
         let segments_codewords = segment_polynomials
             .iter()
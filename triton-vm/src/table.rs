@@ -1,10 +1,13 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::ops::BitOr;
+use std::ops::BitOrAssign;
 
 use arbitrary::Arbitrary;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
 use twenty_first::prelude::XFieldElement;
 
 pub use crate::stark::NUM_QUOTIENT_SEGMENTS;
@@ -57,6 +60,126 @@ impl Display for ConstraintType {
     }
 }
 
+/// Identifies one AIR constraint through its [type](ConstraintType) and its index among all
+/// constraints of that type. The index matches the position of the constraint's value in the
+/// output of, for example, [`evaluate_initial_constraints`][init].
+///
+/// [init]: extension_table::Evaluable::evaluate_initial_constraints
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ConstraintName {
+    pub constraint_type: ConstraintType,
+    pub index: usize,
+}
+
+impl Display for ConstraintName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} constraint {}", self.constraint_type, self.index)
+    }
+}
+
+impl ConstraintName {
+    /// The names of all AIR constraints, in the order of
+    /// [`evaluate_all_constraints_named`][named].
+    ///
+    /// [named]: master_table::MasterExtTable::evaluate_all_constraints_named
+    pub fn all() -> Vec<Self> {
+        use extension_table::Quotientable;
+        use master_table::MasterExtTable;
+
+        let names = |constraint_type, num_constraints| {
+            (0..num_constraints).map(move |index| Self {
+                constraint_type,
+                index,
+            })
+        };
+        names(
+            ConstraintType::Initial,
+            MasterExtTable::NUM_INITIAL_CONSTRAINTS,
+        )
+        .chain(names(
+            ConstraintType::Consistency,
+            MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS,
+        ))
+        .chain(names(
+            ConstraintType::Transition,
+            MasterExtTable::NUM_TRANSITION_CONSTRAINTS,
+        ))
+        .chain(names(
+            ConstraintType::Terminal,
+            MasterExtTable::NUM_TERMINAL_CONSTRAINTS,
+        ))
+        .collect()
+    }
+}
+
+/// A set of [constraint types](ConstraintType), for example, to select which constraints to
+/// evaluate.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ConstraintCategorySet(u8);
+
+impl ConstraintCategorySet {
+    pub const EMPTY: Self = Self(0);
+    pub const INITIAL: Self = Self::single(ConstraintType::Initial);
+    pub const CONSISTENCY: Self = Self::single(ConstraintType::Consistency);
+    pub const TRANSITION: Self = Self::single(ConstraintType::Transition);
+    pub const TERMINAL: Self = Self::single(ConstraintType::Terminal);
+    pub const ALL: Self =
+        Self(Self::INITIAL.0 | Self::CONSISTENCY.0 | Self::TRANSITION.0 | Self::TERMINAL.0);
+
+    const fn single(constraint_type: ConstraintType) -> Self {
+        Self(1 << constraint_type as u8)
+    }
+
+    pub const fn contains(self, constraint_type: ConstraintType) -> bool {
+        self.0 & Self::single(constraint_type).0 != 0
+    }
+
+    /// The total number of AIR constraints of all types in this set.
+    pub fn num_constraints(self) -> usize {
+        use extension_table::Quotientable;
+        use master_table::MasterExtTable;
+
+        let num_constraints = |constraint_type| match constraint_type {
+            ConstraintType::Initial => MasterExtTable::NUM_INITIAL_CONSTRAINTS,
+            ConstraintType::Consistency => MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS,
+            ConstraintType::Transition => MasterExtTable::NUM_TRANSITION_CONSTRAINTS,
+            ConstraintType::Terminal => MasterExtTable::NUM_TERMINAL_CONSTRAINTS,
+        };
+        ConstraintType::iter()
+            .filter(|&constraint_type| self.contains(constraint_type))
+            .map(num_constraints)
+            .sum()
+    }
+}
+
+impl From<ConstraintType> for ConstraintCategorySet {
+    fn from(constraint_type: ConstraintType) -> Self {
+        Self::single(constraint_type)
+    }
+}
+
+impl FromIterator<ConstraintType> for ConstraintCategorySet {
+    fn from_iter<I: IntoIterator<Item = ConstraintType>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(Self::from)
+            .fold(Self::EMPTY, BitOr::bitor)
+    }
+}
+
+impl BitOr for ConstraintCategorySet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ConstraintCategorySet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
 /// A single row of a [`MasterBaseTable`][table].
 ///
 /// Usually, the elements in the table are [`BFieldElement`][bfe]s. For out-of-domain rows, which is
@@ -239,7 +239,8 @@ mod tests {
         #[strategy(arb())] initial: XFieldElement,
         #[strategy(arb())] challenge: BFieldElement,
     ) {
-        let poly_evaluation = initial * Polynomial::zerofier(&roots).evaluate(challenge);
+        let poly_evaluation =
+            initial * Polynomial::zerofier(&roots).evaluate::<_, XFieldElement>(challenge);
         let perm_arg_terminal = PermArg::compute_terminal(&roots, initial, challenge.lift());
         prop_assert_eq!(poly_evaluation, perm_arg_terminal);
     }
@@ -249,7 +250,7 @@ mod tests {
         #[strategy(arb())] polynomial: Polynomial<BFieldElement>,
         #[strategy(arb())] challenge: BFieldElement,
     ) {
-        let poly_evaluation = polynomial.evaluate(challenge).lift();
+        let poly_evaluation = polynomial.evaluate::<_, BFieldElement>(challenge).lift();
 
         let mut polynomial = polynomial;
         polynomial.normalize(); // remove leading zeros
@@ -272,7 +273,8 @@ mod tests {
     ) {
         let polynomial = Polynomial::zerofier(&roots);
         let derivative = polynomial.formal_derivative();
-        let poly_evaluation = derivative.evaluate(challenge) / polynomial.evaluate(challenge);
+        let poly_evaluation = derivative.evaluate::<_, BFieldElement>(challenge)
+            / polynomial.evaluate::<_, BFieldElement>(challenge);
         let lookup_arg_terminal = LookupArg::compute_terminal(&roots, initial, challenge.lift());
         prop_assert_eq!(initial + poly_evaluation, lookup_arg_terminal);
     }
@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::mem::MaybeUninit;
 use std::ops::Mul;
 use std::ops::MulAssign;
@@ -535,7 +536,7 @@ pub struct MasterExtTable {
     pub randomized_trace_domain: ArithmeticDomain,
     pub quotient_domain: ArithmeticDomain,
     pub fri_domain: ArithmeticDomain,
- 
+
     pub randomized_trace_table: Array2<XFieldElement>,
     pub low_degree_extended_table: Option<Array2<XFieldElement>>,
     pub interpolation_polynomials: Option<Array1<Polynomial<XFieldElement>>>,
@@ -1066,6 +1067,36 @@ impl MasterExtTable {
             .slice_mut(s![..; unit_distance, column_indices])
     }
 
+    /// Evaluate all AIR constraints on the given pair of consecutive rows, and pair each value
+    /// with the [name](ConstraintName) of the constraint it stems from. The initial,
+    /// consistency, and terminal constraints are evaluated on the current rows only.
+    ///
+    /// Helps finding the culprit(s) of a failing proof or verification.
+    pub fn evaluate_all_constraints_named(
+        current_base_row: ArrayView1<BFieldElement>,
+        current_ext_row: ArrayView1<XFieldElement>,
+        next_base_row: ArrayView1<BFieldElement>,
+        next_ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<(ConstraintName, XFieldElement)> {
+        let init =
+            Self::evaluate_initial_constraints(current_base_row, current_ext_row, challenges);
+        let cons =
+            Self::evaluate_consistency_constraints(current_base_row, current_ext_row, challenges);
+        let tran = Self::evaluate_transition_constraints(
+            current_base_row,
+            current_ext_row,
+            next_base_row,
+            next_ext_row,
+            challenges,
+        );
+        let term =
+            Self::evaluate_terminal_constraints(current_base_row, current_ext_row, challenges);
+
+        let values = [init, cons, tran, term].concat();
+        ConstraintName::all().into_iter().zip_eq(values).collect()
+    }
+
     pub(crate) fn try_to_ext_row(row: Array1<XFieldElement>) -> Result<ExtensionRow, ProvingError> {
         let err = || ProvingError::TableRowConversionError {
             expected_len: NUM_EXT_COLUMNS,
@@ -1175,87 +1206,238 @@ pub fn all_quotients_combined(
     );
     assert_eq!(MasterExtTable::NUM_CONSTRAINTS, quotient_weights.len());
 
-    let init_section_end = MasterExtTable::NUM_INITIAL_CONSTRAINTS;
-    let cons_section_end = init_section_end + MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS;
-    let tran_section_end = cons_section_end + MasterExtTable::NUM_TRANSITION_CONSTRAINTS;
-
     profiler!(start "zerofier inverse");
-    let initial_zerofier_inverse = initial_quotient_zerofier_inverse(quotient_domain);
-    let consistency_zerofier_inverse =
-        consistency_quotient_zerofier_inverse(trace_domain, quotient_domain);
-    let transition_zerofier_inverse =
-        transition_quotient_zerofier_inverse(trace_domain, quotient_domain);
-    let terminal_zerofier_inverse =
-        terminal_quotient_zerofier_inverse(trace_domain, quotient_domain);
+    let zerofier_inverses = QuotientZerofierInverses::new(trace_domain, quotient_domain);
     profiler!(stop "zerofier inverse");
 
     profiler!(start "evaluate AIR, compute quotient codeword");
-    let dot_product = |partial_row: Vec<_>, weights: &[_]| -> XFieldElement {
-        let pairs = partial_row.into_iter().zip_eq(weights.iter());
-        pairs.map(|(v, &w)| v * w).sum()
-    };
-
     let quotient_codeword = (0..quotient_domain.length)
         .into_par_iter()
         .map(|row_index| {
-            let unit_distance = quotient_domain.length / trace_domain.length;
-            let next_row_index = (row_index + unit_distance) % quotient_domain.length;
-            let current_row_main = quotient_domain_master_base_table.row(row_index);
-            let current_row_aux = quotient_domain_master_ext_table.row(row_index);
-            let next_row_main = quotient_domain_master_base_table.row(next_row_index);
-            let next_row_aux = quotient_domain_master_ext_table.row(next_row_index);
-
-            let initial_constraint_values = MasterExtTable::evaluate_initial_constraints(
-                current_row_main,
-                current_row_aux,
+            let [current_row_index, next_row_index] =
+                row_index_pair(trace_domain, quotient_domain, row_index);
+            let section_inner_products = weighted_constraint_sections(
+                quotient_domain_master_base_table.row(current_row_index),
+                quotient_domain_master_ext_table.row(current_row_index),
+                quotient_domain_master_base_table.row(next_row_index),
+                quotient_domain_master_ext_table.row(next_row_index),
                 challenges,
+                quotient_weights,
             );
-            let initial_inner_product = dot_product(
-                initial_constraint_values,
-                &quotient_weights[..init_section_end],
-            );
-            let mut quotient_value = initial_inner_product * initial_zerofier_inverse[row_index];
+            zerofier_inverses.combine(section_inner_products, row_index)
+        })
+        .collect();
+    profiler!(stop "evaluate AIR, compute quotient codeword");
 
-            let consistency_constraint_values = MasterExtTable::evaluate_consistency_constraints(
-                current_row_main,
-                current_row_aux,
-                challenges,
-            );
-            let consistency_inner_product = dot_product(
-                consistency_constraint_values,
-                &quotient_weights[init_section_end..cons_section_end],
-            );
-            quotient_value += consistency_inner_product * consistency_zerofier_inverse[row_index];
+    quotient_codeword
+}
 
-            let transition_constraint_values = MasterExtTable::evaluate_transition_constraints(
+/// The result of [`all_quotients_combined_memoized`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MemoizedQuotients {
+    /// The quotient codeword, identical to the one computed by [`all_quotients_combined`].
+    pub quotient_codeword: Vec<XFieldElement>,
+
+    /// The number of rows for which the constraints were not evaluated because an identical
+    /// (current row, next row) pair had been evaluated before.
+    pub num_cache_hits: usize,
+}
+
+/// Like [`all_quotients_combined`], but evaluates the AIR constraints only once per distinct
+/// (current row, next row) pair. The (weighted) constraint evaluations of repeated pairs, for
+/// example as they occur in heavily padded traces, are looked up instead of re-computed.
+///
+/// The challenges and quotient weights are fixed for the entire invocation, which is why they
+/// are not part of the cache key. The cache does not outlive a single invocation.
+pub fn all_quotients_combined_memoized(
+    quotient_domain_master_base_table: ArrayView2<BFieldElement>,
+    quotient_domain_master_ext_table: ArrayView2<XFieldElement>,
+    trace_domain: ArithmeticDomain,
+    quotient_domain: ArithmeticDomain,
+    challenges: &Challenges,
+    quotient_weights: &[XFieldElement],
+) -> MemoizedQuotients {
+    assert_eq!(
+        quotient_domain.length,
+        quotient_domain_master_base_table.nrows(),
+    );
+    assert_eq!(
+        quotient_domain.length,
+        quotient_domain_master_ext_table.nrows()
+    );
+    assert_eq!(MasterExtTable::NUM_CONSTRAINTS, quotient_weights.len());
+
+    profiler!(start "zerofier inverse");
+    let zerofier_inverses = QuotientZerofierInverses::new(trace_domain, quotient_domain);
+    profiler!(stop "zerofier inverse");
+
+    profiler!(start "deduplicate rows");
+    let row_pair = |row_index| {
+        let [current_row_index, next_row_index] =
+            row_index_pair(trace_domain, quotient_domain, row_index);
+        (
+            quotient_domain_master_base_table.row(current_row_index),
+            quotient_domain_master_ext_table.row(current_row_index),
+            quotient_domain_master_base_table.row(next_row_index),
+            quotient_domain_master_ext_table.row(next_row_index),
+        )
+    };
+    let mut first_occurrence = HashMap::new();
+    let mut cache_slots = Vec::with_capacity(quotient_domain.length);
+    let mut distinct_row_indices = vec![];
+    for row_index in 0..quotient_domain.length {
+        let num_distinct_rows = distinct_row_indices.len();
+        let cache_slot = *first_occurrence
+            .entry(row_pair(row_index))
+            .or_insert(num_distinct_rows);
+        if cache_slot == num_distinct_rows {
+            distinct_row_indices.push(row_index);
+        }
+        cache_slots.push(cache_slot);
+    }
+    let num_cache_hits = quotient_domain.length - distinct_row_indices.len();
+    profiler!(stop "deduplicate rows");
+
+    profiler!(start "evaluate AIR, compute quotient codeword");
+    let cache = distinct_row_indices
+        .into_par_iter()
+        .map(|row_index| {
+            let (current_row_main, current_row_aux, next_row_main, next_row_aux) =
+                row_pair(row_index);
+            weighted_constraint_sections(
                 current_row_main,
                 current_row_aux,
                 next_row_main,
                 next_row_aux,
                 challenges,
-            );
-            let transition_inner_product = dot_product(
-                transition_constraint_values,
-                &quotient_weights[cons_section_end..tran_section_end],
-            );
-            quotient_value += transition_inner_product * transition_zerofier_inverse[row_index];
-
-            let terminal_constraint_values = MasterExtTable::evaluate_terminal_constraints(
-                current_row_main,
-                current_row_aux,
-                challenges,
-            );
-            let terminal_inner_product = dot_product(
-                terminal_constraint_values,
-                &quotient_weights[tran_section_end..],
-            );
-            quotient_value += terminal_inner_product * terminal_zerofier_inverse[row_index];
-            quotient_value
+                quotient_weights,
+            )
         })
+        .collect::<Vec<_>>();
+    let quotient_codeword = cache_slots
+        .into_par_iter()
+        .enumerate()
+        .map(|(row_index, slot)| zerofier_inverses.combine(cache[slot], row_index))
         .collect();
     profiler!(stop "evaluate AIR, compute quotient codeword");
 
-    quotient_codeword
+    MemoizedQuotients {
+        quotient_codeword,
+        num_cache_hits,
+    }
+}
+
+/// The indices of the “current” and “next” row in the quotient domain. Consecutive rows of the
+/// trace domain are `quotient_domain.length / trace_domain.length` rows apart.
+fn row_index_pair(
+    trace_domain: ArithmeticDomain,
+    quotient_domain: ArithmeticDomain,
+    row_index: usize,
+) -> [usize; 2] {
+    let unit_distance = quotient_domain.length / trace_domain.length;
+    let next_row_index = (row_index + unit_distance) % quotient_domain.length;
+    [row_index, next_row_index]
+}
+
+/// Evaluates all AIR constraints on the given rows and returns the inner products of the
+/// initial, consistency, transition, and terminal constraint values with their respective
+/// quotient weights, in that order.
+fn weighted_constraint_sections(
+    current_row_main: ArrayView1<BFieldElement>,
+    current_row_aux: ArrayView1<XFieldElement>,
+    next_row_main: ArrayView1<BFieldElement>,
+    next_row_aux: ArrayView1<XFieldElement>,
+    challenges: &Challenges,
+    quotient_weights: &[XFieldElement],
+) -> [XFieldElement; 4] {
+    let init_section_end = MasterExtTable::NUM_INITIAL_CONSTRAINTS;
+    let cons_section_end = init_section_end + MasterExtTable::NUM_CONSISTENCY_CONSTRAINTS;
+    let tran_section_end = cons_section_end + MasterExtTable::NUM_TRANSITION_CONSTRAINTS;
+
+    let dot_product = |partial_row: Vec<_>, weights: &[_]| -> XFieldElement {
+        let pairs = partial_row.into_iter().zip_eq(weights.iter());
+        pairs.map(|(v, &w)| v * w).sum()
+    };
+
+    let initial_constraint_values =
+        MasterExtTable::evaluate_initial_constraints(current_row_main, current_row_aux, challenges);
+    let initial_inner_product = dot_product(
+        initial_constraint_values,
+        &quotient_weights[..init_section_end],
+    );
+
+    let consistency_constraint_values = MasterExtTable::evaluate_consistency_constraints(
+        current_row_main,
+        current_row_aux,
+        challenges,
+    );
+    let consistency_inner_product = dot_product(
+        consistency_constraint_values,
+        &quotient_weights[init_section_end..cons_section_end],
+    );
+
+    let transition_constraint_values = MasterExtTable::evaluate_transition_constraints(
+        current_row_main,
+        current_row_aux,
+        next_row_main,
+        next_row_aux,
+        challenges,
+    );
+    let transition_inner_product = dot_product(
+        transition_constraint_values,
+        &quotient_weights[cons_section_end..tran_section_end],
+    );
+
+    let terminal_constraint_values = MasterExtTable::evaluate_terminal_constraints(
+        current_row_main,
+        current_row_aux,
+        challenges,
+    );
+    let terminal_inner_product = dot_product(
+        terminal_constraint_values,
+        &quotient_weights[tran_section_end..],
+    );
+
+    [
+        initial_inner_product,
+        consistency_inner_product,
+        transition_inner_product,
+        terminal_inner_product,
+    ]
+}
+
+/// The inverses of the initial, consistency, transition, and terminal zerofiers, evaluated over
+/// the quotient domain.
+struct QuotientZerofierInverses {
+    initial: Array1<BFieldElement>,
+    consistency: Array1<BFieldElement>,
+    transition: Array1<BFieldElement>,
+    terminal: Array1<BFieldElement>,
+}
+
+impl QuotientZerofierInverses {
+    fn new(trace_domain: ArithmeticDomain, quotient_domain: ArithmeticDomain) -> Self {
+        Self {
+            initial: initial_quotient_zerofier_inverse(quotient_domain),
+            consistency: consistency_quotient_zerofier_inverse(trace_domain, quotient_domain),
+            transition: transition_quotient_zerofier_inverse(trace_domain, quotient_domain),
+            terminal: terminal_quotient_zerofier_inverse(trace_domain, quotient_domain),
+        }
+    }
+
+    /// Divides each of the given section inner products by the corresponding zerofier at the
+    /// given row of the quotient domain, and sums the results.
+    fn combine(
+        &self,
+        section_inner_products: [XFieldElement; 4],
+        row_index: usize,
+    ) -> XFieldElement {
+        let [initial, consistency, transition, terminal] = section_inner_products;
+        initial * self.initial[row_index]
+            + consistency * self.consistency[row_index]
+            + transition * self.transition[row_index]
+            + terminal * self.terminal[row_index]
+    }
 }
 
 /// Guaranteed to be a power of two.
@@ -1277,6 +1459,7 @@ mod tests {
     use ndarray::s;
     use ndarray::Array2;
     use num_traits::Zero;
+    use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use strum::EnumCount;
@@ -1438,7 +1621,7 @@ mod tests {
         assert_eq!(big_order as usize, initial_zerofier_inv.len());
         assert_eq!(1, initial_zerofier_poly.degree());
         assert!(initial_zerofier_poly
-            .evaluate(small_domain.domain_value(0))
+            .evaluate::<_, BFieldElement>(small_domain.domain_value(0))
             .is_zero());
 
         let consistency_zerofier_inv =
@@ -1449,7 +1632,9 @@ mod tests {
         assert_eq!(big_order as usize, consistency_zerofier_inv.len());
         assert_eq!(small_order as isize, consistency_zerofier_poly.degree());
         for val in small_domain.domain_values() {
-            assert!(consistency_zerofier_poly.evaluate(val).is_zero());
+            assert!(consistency_zerofier_poly
+                .evaluate::<_, BFieldElement>(val)
+                .is_zero());
         }
 
         let transition_zerofier_inv =
@@ -1463,7 +1648,9 @@ mod tests {
             .iter()
             .take(small_order as usize - 1)
         {
-            assert!(transition_zerofier_poly.evaluate(val).is_zero());
+            assert!(transition_zerofier_poly
+                .evaluate::<_, BFieldElement>(val)
+                .is_zero());
         }
 
         let terminal_zerofier_inv = terminal_quotient_zerofier_inverse(small_domain, big_domain);
@@ -1472,10 +1659,106 @@ mod tests {
         assert_eq!(big_order as usize, terminal_zerofier_inv.len());
         assert_eq!(1, terminal_zerofier_poly.degree());
         assert!(terminal_zerofier_poly
-            .evaluate(small_domain.domain_value(small_order as u32 - 1))
+            .evaluate::<_, BFieldElement>(small_domain.domain_value(small_order as u32 - 1))
             .is_zero());
     }
 
+    #[proptest(cases = 10)]
+    fn memoized_quotients_are_identical_to_non_memoized_quotients(
+        #[strategy(arb())] challenges: Challenges,
+        #[strategy(vec(arb(), MasterExtTable::NUM_CONSTRAINTS))] quotient_weights: Vec<
+            XFieldElement,
+        >,
+    ) {
+        let trace_domain = ArithmeticDomain::of_length(8).unwrap();
+        let quotient_domain = ArithmeticDomain::of_length(64)
+            .unwrap()
+            .with_offset(BFieldElement::generator());
+
+        // The first 8 rows are pairwise distinct, all other rows are identical padding rows.
+        let num_distinct_rows = trace_domain.length;
+        let base_entry = |(row, col)| match row < num_distinct_rows {
+            true => bfe!((row * NUM_BASE_COLUMNS + col + 1) as u64),
+            false => bfe!(0),
+        };
+        let ext_entry = |(row, col)| match row < num_distinct_rows {
+            true => xfe!((row * NUM_EXT_COLUMNS + col + 1) as u64),
+            false => xfe!(0),
+        };
+        let base_table =
+            Array2::from_shape_fn((quotient_domain.length, NUM_BASE_COLUMNS), base_entry);
+        let ext_table = Array2::from_shape_fn((quotient_domain.length, NUM_EXT_COLUMNS), ext_entry);
+
+        let quotient_codeword = all_quotients_combined(
+            base_table.view(),
+            ext_table.view(),
+            trace_domain,
+            quotient_domain,
+            &challenges,
+            &quotient_weights,
+        );
+        let memoized_quotients = all_quotients_combined_memoized(
+            base_table.view(),
+            ext_table.view(),
+            trace_domain,
+            quotient_domain,
+            &challenges,
+            &quotient_weights,
+        );
+        prop_assert_eq!(quotient_codeword, memoized_quotients.quotient_codeword);
+
+        // Row pairs (i, i+8) are distinct if either row is one of the first 8 rows. This is the
+        // case for 16 row pairs. The remaining 48 row pairs consist of padding rows only; all but
+        // the first of them are cache hits.
+        prop_assert_eq!(47, memoized_quotients.num_cache_hits);
+    }
+
+    #[proptest(cases = 10)]
+    fn named_constraint_evaluations_cover_all_constraints_with_unique_names(
+        #[strategy(vec(arb(), NUM_BASE_COLUMNS))]
+        #[map(Array1::from)]
+        current_base_row: Array1<BFieldElement>,
+        #[strategy(vec(arb(), NUM_EXT_COLUMNS))]
+        #[map(Array1::from)]
+        current_ext_row: Array1<XFieldElement>,
+        #[strategy(vec(arb(), NUM_BASE_COLUMNS))]
+        #[map(Array1::from)]
+        next_base_row: Array1<BFieldElement>,
+        #[strategy(vec(arb(), NUM_EXT_COLUMNS))]
+        #[map(Array1::from)]
+        next_ext_row: Array1<XFieldElement>,
+        #[strategy(arb())] challenges: Challenges,
+    ) {
+        let named_values = MasterExtTable::evaluate_all_constraints_named(
+            current_base_row.view(),
+            current_ext_row.view(),
+            next_base_row.view(),
+            next_ext_row.view(),
+            &challenges,
+        );
+        prop_assert_eq!(MasterExtTable::NUM_CONSTRAINTS, named_values.len());
+
+        let (names, values): (Vec<_>, Vec<_>) = named_values.into_iter().unzip();
+        prop_assert!(names.iter().all_unique());
+
+        let terminal_values = MasterExtTable::evaluate_terminal_constraints(
+            current_base_row.view(),
+            current_ext_row.view(),
+            &challenges,
+        );
+        let num_terminal_constraints = MasterExtTable::NUM_TERMINAL_CONSTRAINTS;
+        let first_terminal_index = MasterExtTable::NUM_CONSTRAINTS - num_terminal_constraints;
+        prop_assert_eq!(&terminal_values, &values[first_terminal_index..]);
+
+        let first_terminal_name = names[first_terminal_index];
+        prop_assert_eq!(
+            ConstraintType::Terminal,
+            first_terminal_name.constraint_type
+        );
+        prop_assert_eq!(0, first_terminal_name.index);
+        prop_assert_eq!("terminal constraint 0", first_terminal_name.to_string());
+    }
+
     macro_rules! constraints_without_degree_lowering {
         ($constraint_type: ident) => {{
             let circuit_builder = ConstraintCircuitBuilder::new();
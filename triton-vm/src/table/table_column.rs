@@ -2,10 +2,19 @@
 //! rather than their hard-to-remember index.
 
 use std::hash::Hash;
+use std::ops::Index;
+use std::ops::IndexMut;
 
+use ndarray::ArrayBase;
+use ndarray::Data;
+use ndarray::DataMut;
+use ndarray::Ix1;
 use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
+use twenty_first::math::traits::FiniteField;
+use twenty_first::prelude::XFieldElement;
 
 use crate::table::degree_lowering_table::DegreeLoweringBaseTableColumn;
 use crate::table::degree_lowering_table::DegreeLoweringExtTableColumn;
@@ -778,19 +787,133 @@ impl MasterExtTableColumn for DegreeLoweringExtTableColumn {
 
 // --------------------------------------------------------------------
 
+/// Declares the enums [`BaseColumn`] and [`ExtColumn`] from one list of tables, each given by
+/// the name of the variant and the tables' base and extension column types.
+macro_rules! typed_columns {
+    ($($variant:ident($base_column:ident, $ext_column:ident),)+) => {
+        typed_columns!(@column
+            /// Any column of the master base table. Allows indexing rows of the master base
+            /// table by column name, for example
+            /// `row[BaseColumn::from(ProcessorBaseTableColumn::IP)]`. The rows can be over
+            /// either field, since base rows are also evaluated over the extension field.
+            ///
+            /// The indices are computed by [`MasterBaseTableColumn`], which is also what the
+            /// constraint circuits use to refer to their inputs.
+            BaseColumn: MasterBaseTableColumn::{base_table_index, master_base_table_index}
+            for rows where S::Elem: FiniteField,
+            $($variant($base_column),)+
+        );
+        typed_columns!(@column
+            /// Any column of the master extension table. Allows indexing rows of the master
+            /// extension table by column name, for example
+            /// `row[ExtColumn::from(ProcessorExtTableColumn::InputTableEvalArg)]`.
+            ///
+            /// The indices are computed by [`MasterExtTableColumn`], which is also what the
+            /// constraint circuits use to refer to their inputs.
+            ExtColumn: MasterExtTableColumn::{ext_table_index, master_ext_table_index}
+            for rows where S: Data<Elem = XFieldElement>,
+            $($variant($ext_column),)+
+        );
+    };
+    (@column
+        $(#[$meta:meta])*
+        $column:ident: $trait:ident::{$table_index:ident, $master_table_index:ident}
+        for rows where $row_bound:ty: $row_bound_trait:path,
+        $($variant:ident($table_column:ident),)+
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        pub enum $column {
+            $( $variant($table_column), )+
+        }
+
+        impl $column {
+            /// All columns, in the order of the master table.
+            pub fn iter() -> impl Iterator<Item = Self> {
+                std::iter::empty()
+                    $( .chain($table_column::iter().map(Self::$variant)) )+
+            }
+        }
+
+        impl $trait for $column {
+            #[inline]
+            fn $table_index(&self) -> usize {
+                match self {
+                    $( Self::$variant(column) => column.$table_index(), )+
+                }
+            }
+
+            #[inline]
+            fn $master_table_index(&self) -> usize {
+                match self {
+                    $( Self::$variant(column) => column.$master_table_index(), )+
+                }
+            }
+        }
+
+        $(
+        impl From<$table_column> for $column {
+            fn from(column: $table_column) -> Self {
+                Self::$variant(column)
+            }
+        }
+        )+
+
+        impl<S: Data> Index<$column> for ArrayBase<S, Ix1>
+        where
+            $row_bound: $row_bound_trait,
+        {
+            type Output = S::Elem;
+
+            fn index(&self, column: $column) -> &Self::Output {
+                &self[column.$master_table_index()]
+            }
+        }
+
+        impl<S: DataMut> IndexMut<$column> for ArrayBase<S, Ix1>
+        where
+            $row_bound: $row_bound_trait,
+        {
+            fn index_mut(&mut self, column: $column) -> &mut Self::Output {
+                &mut self[column.$master_table_index()]
+            }
+        }
+    };
+}
+
+typed_columns!(
+    Program(ProgramBaseTableColumn, ProgramExtTableColumn),
+    Processor(ProcessorBaseTableColumn, ProcessorExtTableColumn),
+    OpStack(OpStackBaseTableColumn, OpStackExtTableColumn),
+    Ram(RamBaseTableColumn, RamExtTableColumn),
+    JumpStack(JumpStackBaseTableColumn, JumpStackExtTableColumn),
+    Hash(HashBaseTableColumn, HashExtTableColumn),
+    Cascade(CascadeBaseTableColumn, CascadeExtTableColumn),
+    Lookup(LookupBaseTableColumn, LookupExtTableColumn),
+    U32(U32BaseTableColumn, U32ExtTableColumn),
+    DegreeLowering(DegreeLoweringBaseTableColumn, DegreeLoweringExtTableColumn),
+);
+
+// --------------------------------------------------------------------
+
 #[cfg(test)]
 mod tests {
-    use strum::IntoEnumIterator;
+    use std::collections::HashSet;
+
+    use ndarray::Array1;
+    use twenty_first::prelude::*;
 
     use crate::table::cascade_table;
     use crate::table::hash_table;
     use crate::table::jump_stack_table;
     use crate::table::lookup_table;
+    use crate::table::master_table::EXT_DEGREE_LOWERING_TABLE_END;
     use crate::table::op_stack_table;
     use crate::table::processor_table;
     use crate::table::program_table;
     use crate::table::ram_table;
     use crate::table::u32_table;
+    use crate::table::NUM_BASE_COLUMNS;
 
     use super::*;
 
@@ -1038,4 +1161,40 @@ mod tests {
             expected_column_index += 1;
         }
     }
+
+    #[test]
+    fn every_base_column_maps_to_distinct_index_within_master_base_table() {
+        let indices = BaseColumn::iter()
+            .map(|column| column.master_base_table_index())
+            .collect::<HashSet<_>>();
+        assert_eq!(NUM_BASE_COLUMNS, indices.len());
+        assert!(indices.iter().all(|&index| index < NUM_BASE_COLUMNS));
+    }
+
+    #[test]
+    fn every_ext_column_maps_to_distinct_index_within_master_ext_table() {
+        let indices = ExtColumn::iter()
+            .map(|column| column.master_ext_table_index())
+            .collect::<HashSet<_>>();
+        assert_eq!(EXT_DEGREE_LOWERING_TABLE_END, indices.len());
+        assert!(indices
+            .iter()
+            .all(|&index| index < EXT_DEGREE_LOWERING_TABLE_END));
+    }
+
+    #[test]
+    fn rows_can_be_indexed_by_typed_columns() {
+        let mut row = Array1::from_iter((0..NUM_BASE_COLUMNS as u64).map(BFieldElement::new));
+        let column = BaseColumn::from(ProcessorBaseTableColumn::IP);
+        let index = column.master_base_table_index();
+        assert_eq!(bfe!(index as u64), row.view()[column]);
+
+        row[column] = bfe!(42);
+        assert_eq!(bfe!(42), row[index]);
+
+        let mut row = Array1::from_elem(EXT_DEGREE_LOWERING_TABLE_END, xfe!(0));
+        let column = ExtColumn::from(ProcessorExtTableColumn::InputTableEvalArg);
+        row[column] = xfe!(42);
+        assert_eq!(xfe!(42), row[column.master_ext_table_index()]);
+    }
 }
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt::Display;
@@ -12,10 +14,13 @@ use num_traits::ConstZero;
 use num_traits::One;
 use num_traits::Zero;
 use serde_derive::*;
+use strum::EnumCount;
+use strum::IntoEnumIterator;
 use twenty_first::math::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 use twenty_first::util_types::algebraic_hasher::Domain;
 
+use crate::air::memory_layout::MemoryRegion;
 use crate::error::InstructionError;
 use crate::error::InstructionError::*;
 use crate::instruction::AnInstruction::*;
@@ -36,8 +41,8 @@ type Result<T> = std::result::Result<T, InstructionError>;
 /// The number of helper variable registers
 pub const NUM_HELPER_VARIABLE_REGISTERS: usize = 6;
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
-pub struct VMState {
+#[derive(Debug, Clone, Serialize, Deserialize, Arbitrary)]
+pub struct VMState<Ram = HashMap<BFieldElement, BFieldElement>> {
     /// The **program memory** stores the instructions (and their arguments) of the program
     /// currently being executed by Triton VM. It is read-only.
     pub program: Vec<Instruction>,
@@ -55,7 +60,8 @@ pub struct VMState {
     pub secret_digests: VecDeque<Digest>,
 
     /// The read-write **random-access memory** allows Triton VM to store arbitrary data.
-    pub ram: HashMap<BFieldElement, BFieldElement>,
+    /// See also [`RamBackend`].
+    pub ram: Ram,
 
     ram_calls: Vec<RamTableCall>,
 
@@ -81,6 +87,78 @@ pub struct VMState {
 
     /// Indicates whether the terminating instruction `halt` has been executed.
     pub halting: bool,
+
+    /// See [`with_trace_history`](Self::with_trace_history).
+    #[serde(skip)]
+    #[arbitrary(default)]
+    trace_history: TraceHistory,
+}
+
+/// The addresses and instructions of the most recently executed instructions, oldest first.
+///
+/// Only a diagnostic aid: it is not serialized, and it is ignored when comparing
+/// [`VMState`]s. Two states that agree on everything but their history are the same state.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct TraceHistory {
+    /// The maximal number of entries. Zero if the history is disabled.
+    capacity: usize,
+    entries: VecDeque<(usize, Instruction)>,
+}
+
+impl<Ram: PartialEq> PartialEq for VMState<Ram> {
+    fn eq(&self, other: &Self) -> bool {
+        // Exhaustive destructuring makes sure new fields are not forgotten.
+        let Self {
+            program,
+            public_input,
+            public_output,
+            secret_individual_tokens,
+            secret_digests,
+            ram,
+            ram_calls,
+            op_stack,
+            jump_stack,
+            cycle_count,
+            instruction_pointer,
+            sponge,
+            halting,
+            trace_history: _,
+        } = self;
+
+        program == &other.program
+            && public_input == &other.public_input
+            && public_output == &other.public_output
+            && secret_individual_tokens == &other.secret_individual_tokens
+            && secret_digests == &other.secret_digests
+            && ram == &other.ram
+            && ram_calls == &other.ram_calls
+            && op_stack == &other.op_stack
+            && jump_stack == &other.jump_stack
+            && cycle_count == &other.cycle_count
+            && instruction_pointer == &other.instruction_pointer
+            && sponge == &other.sponge
+            && halting == &other.halting
+    }
+}
+
+impl<Ram: std::cmp::Eq> std::cmp::Eq for VMState<Ram> {}
+
+impl TraceHistory {
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let num_excess_entries = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..num_excess_entries);
+    }
+
+    fn record(&mut self, address: usize, instruction: Instruction) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((address, instruction));
+    }
 }
 
 /// A call from the main processor to one of the co-processors, including the trace for that
@@ -103,6 +181,38 @@ pub enum CoProcessorCall {
     RamCall(RamTableCall),
 }
 
+/// The storage backing Triton VM's [random-access memory](VMState::ram). By default, RAM is
+/// kept in a [`HashMap`]. Alternative backends, for example ones with bounded memory
+/// consumption or persistent ones, can be used through
+/// [`VMState::with_ram_backend`].
+///
+/// Reading from an address that was never written to must return `None`; the VM then
+/// treats the value as 0.
+pub trait RamBackend {
+    /// The value stored at the given address, if any.
+    fn read(&self, address: BFieldElement) -> Option<BFieldElement>;
+
+    /// Store the given value at the given address, overwriting any previous value.
+    fn write(&mut self, address: BFieldElement, value: BFieldElement);
+
+    /// All initialized addresses and their values, in no particular order.
+    fn iter(&self) -> impl Iterator<Item = (BFieldElement, BFieldElement)> + '_;
+}
+
+impl RamBackend for HashMap<BFieldElement, BFieldElement> {
+    fn read(&self, address: BFieldElement) -> Option<BFieldElement> {
+        self.get(&address).copied()
+    }
+
+    fn write(&mut self, address: BFieldElement, value: BFieldElement) {
+        self.insert(address, value);
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (BFieldElement, BFieldElement)> + '_ {
+        HashMap::iter(self).map(|(&address, &value)| (address, value))
+    }
+}
+
 impl VMState {
     /// Create initial `VMState` for a given `program`
     ///
@@ -130,7 +240,89 @@ impl VMState {
             instruction_pointer: 0,
             sponge: None,
             halting: false,
+            trace_history: TraceHistory::default(),
+        }
+    }
+}
+
+impl<Ram: RamBackend> VMState<Ram> {
+    /// Move this state to a different [`RamBackend`]. All initialized RAM cells are
+    /// written to the given backend, which should generally be empty.
+    pub fn with_ram_backend<NewRam: RamBackend>(self, mut ram: NewRam) -> VMState<NewRam> {
+        for (address, value) in self.ram.iter() {
+            ram.write(address, value);
         }
+
+        VMState {
+            program: self.program,
+            public_input: self.public_input,
+            public_output: self.public_output,
+            secret_individual_tokens: self.secret_individual_tokens,
+            secret_digests: self.secret_digests,
+            ram,
+            ram_calls: self.ram_calls,
+            op_stack: self.op_stack,
+            jump_stack: self.jump_stack,
+            cycle_count: self.cycle_count,
+            instruction_pointer: self.instruction_pointer,
+            sponge: self.sponge,
+            halting: self.halting,
+            trace_history: self.trace_history,
+        }
+    }
+
+    /// Record the addresses and instructions of the last `capacity` executed instructions,
+    /// including the one currently being executed. Helps understand how a crashed program
+    /// ended up in its final state; see [`recent_instructions`](Self::recent_instructions).
+    ///
+    /// A `capacity` of zero disables the history. Any previously recorded history is
+    /// truncated to the new capacity.
+    #[must_use]
+    pub fn with_trace_history(mut self, capacity: usize) -> Self {
+        self.trace_history.set_capacity(capacity);
+        self
+    }
+
+    /// The addresses and instructions of the most recently executed instructions, oldest
+    /// first. The last entry is the instruction executed last, or the one that caused a
+    /// crash. Empty unless enabled through [`with_trace_history`](Self::with_trace_history).
+    pub fn recent_instructions(&self) -> Vec<(usize, Instruction)> {
+        self.trace_history.entries.iter().copied().collect()
+    }
+
+    /// The [`XFieldElement`]s stored in the given region of RAM, each paired with its address.
+    /// The region is interpreted as a list of [`XFieldElement`]s as laid out by
+    /// [`xfes_to_bfes`][xfes_to_bfes], _i.e._, the first element starts at the region's first
+    /// address, and every element occupies [`EXTENSION_DEGREE`] consecutive addresses. Trailing
+    /// words of the region that don't make up an entire element are ignored.
+    ///
+    /// Elements with at least one uninitialized coefficient are omitted. The returned
+    /// elements are sorted by address.
+    ///
+    /// [xfes_to_bfes]: crate::air::memory_layout::xfes_to_bfes
+    pub fn dump_region_as_xfes(&self, region: MemoryRegion) -> Vec<(BFieldElement, XFieldElement)> {
+        // Regions can be huge. Only consider elements with at least one initialized word.
+        let extension_degree = EXTENSION_DEGREE as u64;
+        let start = region.start().value();
+        let num_xfes = region.size() / extension_degree;
+        let xfe_indices = self
+            .ram
+            .iter()
+            .filter(|&(address, _)| region.contains_address(address))
+            .map(|(address, _)| (address.value() - start) / extension_degree)
+            .filter(|&xfe_index| xfe_index < num_xfes)
+            .sorted()
+            .dedup();
+
+        let read_xfe = |xfe_index| {
+            let address = region.start() + bfe!(xfe_index * extension_degree);
+            let read_coefficient = |i: u64| self.ram.read(address + bfe!(i));
+            let coefficients: Option<Vec<_>> =
+                (0..extension_degree).map(read_coefficient).collect();
+            let xfe = XFieldElement::new(coefficients?.try_into().unwrap());
+            Some((address, xfe))
+        };
+        xfe_indices.filter_map(read_xfe).collect()
     }
 
     pub fn derive_helper_variables(&self) -> [BFieldElement; NUM_HELPER_VARIABLE_REGISTERS] {
@@ -140,7 +332,7 @@ impl VMState {
         };
 
         let decompose_arg = |a: u64| bfe_array![a % 2, (a >> 1) % 2, (a >> 2) % 2, (a >> 3) % 2];
-        let ram_read = |address| self.ram.get(&address).copied().unwrap_or_else(|| bfe!(0));
+        let ram_read = |address| self.ram.read(address).unwrap_or_else(|| bfe!(0));
 
         match current_instruction {
             Pop(_) | Divine(_) | Dup(_) | Swap(_) | ReadMem(_) | WriteMem(_) | ReadIo(_)
@@ -227,6 +419,8 @@ impl VMState {
         }
 
         let current_instruction = self.current_instruction()?;
+        let address = self.instruction_pointer;
+        self.trace_history.record(address, current_instruction);
         let op_stack_delta = current_instruction.op_stack_size_influence();
         if self.op_stack.would_be_too_shallow(op_stack_delta) {
             return Err(OpStackTooShallow);
@@ -464,11 +658,7 @@ impl VMState {
     }
 
     fn ram_read(&mut self, ram_pointer: BFieldElement) -> BFieldElement {
-        let ram_value = self
-            .ram
-            .get(&ram_pointer)
-            .copied()
-            .unwrap_or(BFieldElement::ZERO);
+        let ram_value = self.ram.read(ram_pointer).unwrap_or(BFieldElement::ZERO);
 
         let ram_table_call = RamTableCall {
             clk: self.cycle_count,
@@ -490,7 +680,7 @@ impl VMState {
         };
         self.ram_calls.push(ram_table_call);
 
-        self.ram.insert(ram_pointer, ram_value);
+        self.ram.write(ram_pointer, ram_value);
     }
 
     fn hash(&mut self) -> Result<Vec<CoProcessorCall>> {
@@ -1038,16 +1228,188 @@ impl VMState {
         Ok(digest.values())
     }
 
+    /// A snapshot of the most relevant parts of the current state. See [`StateReport`].
+    pub fn state_report(&self) -> StateReport {
+        let op_stack_registers = OpStackElement::iter()
+            .map(|st| self.op_stack[st])
+            .collect_vec()
+            .try_into()
+            .unwrap();
+
+        StateReport {
+            instruction_pointer: self.instruction_pointer,
+            current_instruction: self.current_instruction().ok(),
+            op_stack_registers,
+            op_stack_underflow_depth: self.op_stack.len() - OpStackElement::COUNT,
+            sponge_state: self.sponge.as_ref().map(|sponge| sponge.state),
+            cycle_count: self.cycle_count,
+            num_initialized_ram_cells: self.ram.iter().count(),
+        }
+    }
+
     /// Run Triton VM on this state to completion, or until an error occurs.
+    ///
+    /// Every instruction is executed through [`step_with_delta`](Self::step_with_delta), the
+    /// same path used when inspecting the execution step by step.
     pub fn run(&mut self) -> Result<()> {
+        while !self.halting {
+            self.step_with_delta()?;
+        }
+        Ok(())
+    }
+
+    /// Like [`step`](Self::step), but reports the changes the executed instruction made to
+    /// the state instead of the calls to the co-processors. See [`StateDelta`].
+    ///
+    /// Stepping a state that is already [halting](Self::halting) fails with
+    /// [`MachineHalted`].
+    pub fn step_with_delta(&mut self) -> Result<StateDelta> {
+        if self.halting {
+            return Err(MachineHalted);
+        }
+
+        let instruction_pointer = self.instruction_pointer;
+        let instruction = self.current_instruction()?;
+        let stack_before = self.op_stack.stack.clone();
+        let jump_stack_top_before = self.jump_stack.last().copied();
+        let jump_stack_len_before = self.jump_stack.len();
+
+        let ram_values_before = match instruction {
+            WriteMem(n) => (0..n.num_words())
+                .map(|i| self.ram.read(self.op_stack[ST0] + bfe!(i as u64)))
+                .collect(),
+            _ => vec![],
+        };
+        let num_tokens = match instruction {
+            Divine(n) => n.num_words(),
+            _ => 0,
+        };
+        let num_input_words = match instruction {
+            ReadIo(n) => n.num_words(),
+            _ => 0,
+        };
+        let num_digests = match instruction {
+            MerkleStep => 1,
+            _ => 0,
+        };
+        let secret_individual_tokens_consumed = self
+            .secret_individual_tokens
+            .iter()
+            .take(num_tokens)
+            .copied()
+            .collect();
+        let secret_digests_consumed = self
+            .secret_digests
+            .iter()
+            .take(num_digests)
+            .copied()
+            .collect();
+        let public_input_consumed = self
+            .public_input
+            .iter()
+            .take(num_input_words)
+            .copied()
+            .collect();
+
+        let co_processor_calls = self.step()?;
+
+        let stack_after = &self.op_stack.stack;
+        let num_unchanged = stack_before
+            .iter()
+            .zip(stack_after)
+            .take_while(|(before, after)| before == after)
+            .count();
+        let popped = stack_before[num_unchanged..]
+            .iter()
+            .rev()
+            .copied()
+            .collect();
+        let pushed = stack_after[num_unchanged..].to_vec();
+
+        let ram_writes = co_processor_calls
+            .into_iter()
+            .filter_map(|call| match call {
+                RamCall(call) if call.is_write => Some(call),
+                _ => None,
+            })
+            .zip(ram_values_before)
+            .map(|(call, old_value)| RamWrite {
+                address: call.ram_pointer,
+                old_value,
+                new_value: call.ram_value,
+            })
+            .collect();
+
+        let jump_stack_change = match self.jump_stack.len().cmp(&jump_stack_len_before) {
+            Ordering::Less => JumpStackChange::Popped(jump_stack_top_before.unwrap()),
+            Ordering::Equal => JumpStackChange::Unchanged,
+            Ordering::Greater => JumpStackChange::Pushed(*self.jump_stack.last().unwrap()),
+        };
+
+        Ok(StateDelta {
+            instruction_pointer,
+            instruction,
+            popped,
+            pushed,
+            ram_writes,
+            jump_stack_change,
+            secret_individual_tokens_consumed,
+            secret_digests_consumed,
+            public_input_consumed,
+        })
+    }
+
+    /// Like [`run`](Self::run), but fails with [`JumpStackOverflow`] as soon as the jump
+    /// stack holds more than `max_depth` elements. Since every [`call`](Instruction::Call)
+    /// pushes to the jump stack, this bounds the memory consumed by deeply nested or
+    /// recursive calls, which a budget of clock cycles alone does not directly cap.
+    pub fn run_with_max_jump_stack_depth(&mut self, max_depth: usize) -> Result<()> {
         while !self.halting {
             self.step()?;
+            if self.jump_stack.len() > max_depth {
+                return Err(JumpStackOverflow(max_depth));
+            }
         }
         Ok(())
     }
+
+    /// Like [`run`](Self::run), but additionally records an [`ExecutionProfile`]: the clock
+    /// cycles spent on each instruction and in each subroutine entered through instruction
+    /// [`call`](Instruction::Call).
+    ///
+    /// Only clock cycles spent in this invocation are recorded. Subroutines that are still
+    /// active when the VM halts are accounted for up to and including the `halt` instruction.
+    pub fn run_with_profile(&mut self) -> Result<ExecutionProfile> {
+        let mut profile = ExecutionProfile::default();
+        let mut active_calls = vec![];
+        while !self.halting {
+            let instruction = self.current_instruction()?;
+            let cycle_count = self.cycle_count;
+            let jump_stack_len = self.jump_stack.len();
+            self.step()?;
+
+            *profile
+                .cycles_per_opcode
+                .entry(instruction.opcode())
+                .or_default() += 1;
+            if let Call(address) = instruction {
+                active_calls.push((address.value(), cycle_count));
+            }
+            if self.jump_stack.len() < jump_stack_len {
+                if let Some(call) = active_calls.pop() {
+                    profile.record_call(call, &active_calls, self.cycle_count);
+                }
+            }
+        }
+
+        while let Some(call) = active_calls.pop() {
+            profile.record_call(call, &active_calls, self.cycle_count);
+        }
+        Ok(profile)
+    }
 }
 
-impl Display for VMState {
+impl<Ram: RamBackend> Display for VMState<Ram> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         use ProcessorBaseTableColumn as ProcCol;
 
@@ -1163,8 +1525,189 @@ impl Display for VMState {
     }
 }
 
+/// The clock cycles spent when [running a `VMState` with profiling][run].
+///
+/// Since every instruction takes exactly one clock cycle, the number of cycles spent on an
+/// instruction equals the number of times it was executed. Instructions are identified by
+/// their opcode, _i.e._, their arguments are disregarded.
+///
+/// The profile does not reference the executed [`Program`]. Labels of call targets can be
+/// recovered using [`cycles_per_label`](Self::cycles_per_label).
+///
+/// [run]: VMState::run_with_profile
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ExecutionProfile {
+    /// The number of clock cycles spent on the instructions with the given opcode.
+    pub cycles_per_opcode: HashMap<u32, usize>,
+
+    /// The number of clock cycles spent in subroutines starting at the given address, from
+    /// the [`call`](Instruction::Call) up to and including the corresponding `return`. Cycles
+    /// spent in nested subroutines are included. Recursive invocations of a subroutine are
+    /// counted once.
+    pub cycles_per_call_target: HashMap<u64, usize>,
+}
+
+impl ExecutionProfile {
+    /// The total number of clock cycles recorded in this profile.
+    pub fn total_cycles(&self) -> usize {
+        self.cycles_per_opcode.values().sum()
+    }
+
+    /// All executed instructions and the clock cycles spent on them, most expensive first.
+    /// Instructions with an argument are represented by their variant with a default
+    /// argument.
+    pub fn hot_instructions(&self) -> Vec<(Instruction, usize)> {
+        self.cycles_per_opcode
+            .iter()
+            .map(|(&opcode, &cycles)| (Instruction::try_from(opcode).unwrap(), cycles))
+            .sorted_by_key(|&(instruction, cycles)| (Reverse(cycles), instruction.opcode()))
+            .collect()
+    }
+
+    /// Like [`cycles_per_call_target`](Self::cycles_per_call_target), but keyed by the
+    /// given program's [labels](Program::label_for_address).
+    pub fn cycles_per_label(&self, program: &Program) -> HashMap<String, usize> {
+        self.cycles_per_call_target
+            .iter()
+            .map(|(&address, &cycles)| (program.label_for_address(address), cycles))
+            .collect()
+    }
+
+    fn record_call(
+        &mut self,
+        (address, start_cycle): (u64, u32),
+        enclosing_calls: &[(u64, u32)],
+        end_cycle: u32,
+    ) {
+        let is_recursive = enclosing_calls.iter().any(|&(a, _)| a == address);
+        if is_recursive {
+            return;
+        }
+        let num_cycles = usize::try_from(end_cycle - start_cycle).unwrap();
+        *self.cycles_per_call_target.entry(address).or_default() += num_cycles;
+    }
+}
+
+/// The changes a single instruction made to a [`VMState`], as produced by
+/// [`VMState::step_with_delta`].
+///
+/// The op-stack change is reported as the elements that were popped, followed by the
+/// elements that were pushed. Instructions that modify the stack in place, like
+/// [`swap`](Instruction::Swap), are reported as popping all elements down to the deepest
+/// modified one and pushing their new values.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StateDelta {
+    /// The address of the executed instruction.
+    pub instruction_pointer: usize,
+
+    pub instruction: Instruction,
+
+    /// The elements popped off the op stack, in the order they were popped, _i.e._, the
+    /// previous top of the stack first.
+    pub popped: Vec<BFieldElement>,
+
+    /// The elements pushed onto the op stack, in the order they were pushed, _i.e._, the new
+    /// top of the stack last.
+    pub pushed: Vec<BFieldElement>,
+
+    /// All writes to RAM, in the order they happened.
+    pub ram_writes: Vec<RamWrite>,
+
+    pub jump_stack_change: JumpStackChange,
+
+    /// The elements of [secret input](VMState::secret_individual_tokens) consumed by
+    /// instruction [`divine`](Instruction::Divine).
+    pub secret_individual_tokens_consumed: Vec<BFieldElement>,
+
+    /// The [secret digests](VMState::secret_digests) consumed by instruction
+    /// [`merkle_step`](Instruction::MerkleStep).
+    pub secret_digests_consumed: Vec<Digest>,
+
+    /// The elements of [public input](VMState::public_input) consumed by instruction
+    /// [`read_io`](Instruction::ReadIo).
+    pub public_input_consumed: Vec<BFieldElement>,
+}
+
+/// A single write to RAM, as recorded in a [`StateDelta`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RamWrite {
+    pub address: BFieldElement,
+
+    /// The value at the address before the write, or `None` if the address was
+    /// uninitialized.
+    pub old_value: Option<BFieldElement>,
+
+    pub new_value: BFieldElement,
+}
+
+/// The change a single instruction made to the [jump stack](VMState::jump_stack), as
+/// recorded in a [`StateDelta`]. No instruction both pushes and pops.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum JumpStackChange {
+    #[default]
+    Unchanged,
+
+    /// The given entry was pushed, for example by instruction [`call`](Instruction::Call).
+    Pushed((BFieldElement, BFieldElement)),
+
+    /// The given entry was popped, for example by instruction [`return`](Instruction::Return).
+    Popped((BFieldElement, BFieldElement)),
+}
+
+/// A snapshot of a [`VMState`], as produced by [`VMState::state_report`].
+///
+/// In contrast to [`VMState`]'s [`Display`] implementation, the report only contains the
+/// registers most relevant when debugging, and renders them compactly.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StateReport {
+    pub instruction_pointer: usize,
+
+    /// The instruction at the instruction pointer, or `None` if the instruction pointer is
+    /// out of bounds.
+    pub current_instruction: Option<Instruction>,
+
+    /// The op-stack registers, starting with `st0`.
+    pub op_stack_registers: [BFieldElement; OpStackElement::COUNT],
+
+    /// The number of elements in op-stack underflow memory.
+    pub op_stack_underflow_depth: usize,
+
+    /// The full state of the Sponge, or `None` if the Sponge has not been initialized.
+    pub sponge_state: Option<[BFieldElement; tip5::STATE_SIZE]>,
+
+    pub cycle_count: u32,
+
+    /// The number of RAM cells holding a value, be it through the program writing to them or
+    /// through initialization via [non-determinism](NonDeterminism).
+    pub num_initialized_ram_cells: usize,
+}
+
+impl Display for StateReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let instruction = self
+            .current_instruction
+            .map_or_else(|| "END-OF-FILE".to_string(), |i| i.to_string());
+        let op_stack = self.op_stack_registers.iter().join(", ");
+
+        writeln!(f, "ip: {} ({instruction})", self.instruction_pointer)?;
+        writeln!(f, "clk: {}", self.cycle_count)?;
+        writeln!(f, "stack: [{op_stack}]")?;
+        writeln!(f, "underflow depth: {}", self.op_stack_underflow_depth)?;
+        match self.sponge_state {
+            Some(state) => writeln!(f, "sponge: [{}]", state.iter().join(", "))?,
+            None => writeln!(f, "sponge: uninitialized")?,
+        }
+        write!(
+            f,
+            "initialized RAM cells: {}",
+            self.num_initialized_ram_cells
+        )
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::collections::BTreeMap;
     use std::ops::BitAnd;
     use std::ops::BitXor;
 
@@ -1182,6 +1725,7 @@ pub(crate) mod tests {
     use test_strategy::proptest;
     use twenty_first::math::other::random_elements;
 
+    use crate::error::VMError;
     use crate::example_programs::*;
     use crate::shared_tests::prove_and_verify;
     use crate::shared_tests::LeavedMerkleTreeTestData;
@@ -1202,6 +1746,283 @@ pub(crate) mod tests {
         program.trace_execution(stdin, secret_in).unwrap();
     }
 
+    /// A program that calls a subroutine recursively, as often as indicated by the public
+    /// input.
+    fn recursively_calling_program() -> Program {
+        triton_program! {
+            read_io 1 call rec halt
+            rec:
+                dup 0 push 0 eq skiz return
+                push -1 add call rec return
+        }
+    }
+
+    #[test]
+    fn deep_recursion_exceeds_maximum_jump_stack_depth() {
+        let program = recursively_calling_program();
+        let mut vm_state = VMState::new(&program, bfe_vec![100].into(), [].into());
+        let_assert!(Err(err) = vm_state.run_with_max_jump_stack_depth(50));
+        assert!(JumpStackOverflow(50) == err);
+        assert!(51 == vm_state.jump_stack.len());
+    }
+
+    #[test]
+    fn shallow_recursion_stays_within_maximum_jump_stack_depth() {
+        let program = recursively_calling_program();
+        let mut vm_state = VMState::new(&program, bfe_vec![10].into(), [].into());
+        vm_state.run_with_max_jump_stack_depth(11).unwrap();
+        assert!(vm_state.halting);
+    }
+
+    #[test]
+    fn state_delta_records_op_stack_pushes_and_pops() {
+        let program = triton_program!(push 1 push 2 add pop 1 push 4 swap 1 halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(0 == delta.instruction_pointer);
+        assert!(Push(bfe!(1)) == delta.instruction);
+        assert!(delta.popped.is_empty());
+        assert!(bfe_vec![1] == delta.pushed);
+
+        vm_state.step_with_delta().unwrap();
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(Add == delta.instruction);
+        assert!(bfe_vec![2, 1] == delta.popped);
+        assert!(bfe_vec![3] == delta.pushed);
+
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(bfe_vec![3] == delta.popped);
+        assert!(delta.pushed.is_empty());
+
+        vm_state.step_with_delta().unwrap();
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(bfe_vec![4, 0] == delta.popped);
+        assert!(bfe_vec![4, 0] == delta.pushed);
+        assert!(delta.ram_writes.is_empty());
+        assert!(JumpStackChange::Unchanged == delta.jump_stack_change);
+    }
+
+    #[test]
+    fn state_delta_records_old_and_new_ram_values() {
+        let program = triton_program!(push 8 push 7 push 10 write_mem 2 halt);
+        let ram = HashMap::from([(bfe!(10), bfe!(42))]);
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let mut vm_state = VMState::new(&program, [].into(), non_determinism);
+        for _ in 0..3 {
+            vm_state.step_with_delta().unwrap();
+        }
+
+        let delta = vm_state.step_with_delta().unwrap();
+        let expected_writes = vec![
+            RamWrite {
+                address: bfe!(10),
+                old_value: Some(bfe!(42)),
+                new_value: bfe!(7),
+            },
+            RamWrite {
+                address: bfe!(11),
+                old_value: None,
+                new_value: bfe!(8),
+            },
+        ];
+        assert!(expected_writes == delta.ram_writes);
+    }
+
+    #[test]
+    fn state_delta_records_jump_stack_changes() {
+        let program = triton_program!(call foo halt foo: return);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+
+        let delta = vm_state.step_with_delta().unwrap();
+        let expected_entry = (bfe!(2), bfe!(3));
+        assert!(JumpStackChange::Pushed(expected_entry) == delta.jump_stack_change);
+
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(Return == delta.instruction);
+        assert!(JumpStackChange::Popped(expected_entry) == delta.jump_stack_change);
+    }
+
+    #[test]
+    fn state_delta_records_consumed_non_determinism_and_input() {
+        let program = triton_program! {
+            read_io 1 divine 2 push 0 swap 5 merkle_step halt
+        };
+        let digest = Digest::new(bfe_array![11, 12, 13, 14, 15]);
+        let non_determinism = NonDeterminism::new(bfe_vec![5, 6, 7]).with_digests(vec![digest]);
+        let mut vm_state = VMState::new(&program, bfe_vec![3, 4].into(), non_determinism);
+
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(bfe_vec![3] == delta.public_input_consumed);
+        assert!(delta.secret_individual_tokens_consumed.is_empty());
+
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(bfe_vec![5, 6] == delta.secret_individual_tokens_consumed);
+        assert!(bfe_vec![5, 6] == delta.pushed);
+        assert!(delta.public_input_consumed.is_empty());
+
+        vm_state.step_with_delta().unwrap();
+        vm_state.step_with_delta().unwrap();
+        let delta = vm_state.step_with_delta().unwrap();
+        assert!(vec![digest] == delta.secret_digests_consumed);
+        assert!(delta.secret_individual_tokens_consumed.is_empty());
+    }
+
+    #[test]
+    fn stepping_with_delta_past_halt_fails() {
+        let program = triton_program!(halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        vm_state.step_with_delta().unwrap();
+        let_assert!(Err(err) = vm_state.step_with_delta());
+        assert!(MachineHalted == err);
+    }
+
+    #[test]
+    fn stepping_without_delta_until_halt_is_equivalent_to_running() {
+        let program = recursively_calling_program();
+        let mut stepped_state = VMState::new(&program, bfe_vec![10].into(), [].into());
+        let mut ran_state = stepped_state.clone();
+
+        while !stepped_state.halting {
+            stepped_state.step().unwrap();
+        }
+        ran_state.run().unwrap();
+        assert!(ran_state == stepped_state);
+    }
+
+    #[test]
+    fn trace_history_contains_instructions_leading_up_to_crash() {
+        let program = triton_program! {
+            push 1 push 2 push 3 pop 1 pop 1 push 7 push 8 split assert halt
+        };
+        let vm_state = VMState::new(&program, [].into(), [].into());
+        let mut vm_state = vm_state.with_trace_history(4);
+        let_assert!(Err(AssertionFailed) = vm_state.run());
+
+        let recent_instructions = vm_state.recent_instructions();
+        let expected = [
+            (10, Push(bfe!(7))),
+            (12, Push(bfe!(8))),
+            (14, Split),
+            (15, Assert),
+        ];
+        assert!(expected.to_vec() == recent_instructions);
+
+        let crash_report = VMError::new(AssertionFailed, vm_state).to_string();
+        assert!(crash_report.contains("recent instructions:"));
+        assert!(crash_report.contains("14: split"));
+    }
+
+    #[test]
+    fn dump_of_ram_region_contains_written_xfes_at_their_addresses() {
+        let region = MemoryRegion::new(100_u64, 4 * EXTENSION_DEGREE);
+        let xfes = [xfe!(1), xfe!([2, 3, 4]), xfe!(5)];
+        let ram: HashMap<_, _> = crate::air::memory_layout::xfes_to_bfes(&xfes)
+            .into_iter()
+            .enumerate()
+            .map(|(i, bfe)| (bfe!(100 + i as u64), bfe))
+            .collect();
+
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let vm_state = VMState::new(&triton_program!(halt), [].into(), non_determinism);
+        let dump = vm_state.dump_region_as_xfes(region);
+        let expected = vec![
+            (bfe!(100), xfes[0]),
+            (bfe!(103), xfes[1]),
+            (bfe!(106), xfes[2]),
+        ];
+        assert!(expected == dump);
+    }
+
+    #[test]
+    fn dump_of_ram_region_omits_partially_initialized_xfes_and_words_outside_region() {
+        let ram: HashMap<_, _> = (0..20).map(|i| (bfe!(i), bfe!(i))).collect();
+        let non_determinism = NonDeterminism::default().with_ram(ram);
+        let mut vm_state = VMState::new(&triton_program!(halt), [].into(), non_determinism);
+        vm_state.ram.remove(&bfe!(7));
+
+        // the region's last two words don't make up an entire element
+        let region = MemoryRegion::new(4_u64, 3 * EXTENSION_DEGREE + 2);
+        let dump = vm_state.dump_region_as_xfes(region);
+        let expected = vec![(bfe!(4), xfe!([4, 5, 6])), (bfe!(10), xfe!([10, 11, 12]))];
+        assert!(expected == dump);
+    }
+
+    #[test]
+    fn dump_of_uninitialized_ram_region_is_empty() {
+        let vm_state = VMState::new(&triton_program!(halt), [].into(), [].into());
+        let region = MemoryRegion::new(0_u64, crate::air::memory_layout::MEM_PAGE_SIZE);
+        assert!(vm_state.dump_region_as_xfes(region).is_empty());
+    }
+
+    #[test]
+    fn trace_history_is_ignored_by_equality_and_serialization() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let mut vm_state_with_history = vm_state.clone().with_trace_history(2);
+        vm_state.run().unwrap();
+        vm_state_with_history.run().unwrap();
+        assert!(vm_state.recent_instructions() != vm_state_with_history.recent_instructions());
+        assert!(vm_state == vm_state_with_history);
+
+        let serialized = serde_json::to_string(&vm_state).unwrap();
+        let serialized_with_history = serde_json::to_string(&vm_state_with_history).unwrap();
+        assert!(serialized == serialized_with_history);
+
+        let deserialized: VMState = serde_json::from_str(&serialized_with_history).unwrap();
+        assert!(deserialized.recent_instructions().is_empty());
+    }
+
+    #[test]
+    fn trace_history_is_disabled_by_default() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        vm_state.run().unwrap();
+        assert!(vm_state.recent_instructions().is_empty());
+        assert!(!VMError::new(JumpStackIsEmpty, vm_state)
+            .to_string()
+            .contains("recent instructions"));
+    }
+
+    #[test]
+    fn profile_accounts_for_every_clock_cycle() {
+        let program = triton_program! {
+            push 3 call loop halt
+            loop:
+                dup 0 push 0 eq skiz return
+                push -1 add call body recurse
+            body:
+                nop nop return
+        };
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let profile = vm_state.run_with_profile().unwrap();
+
+        assert!(vm_state.cycle_count as usize == profile.total_cycles());
+        let hot_instructions = profile.hot_instructions();
+        let_assert!(Some(&(hottest_instruction, cycles)) = hot_instructions.first());
+        assert!(Instruction::Push(bfe!(0)) == hottest_instruction);
+        assert!(8 == cycles);
+
+        let cycles_per_label = profile.cycles_per_label(&program);
+        let loop_cycles = cycles_per_label["loop"];
+        let body_cycles = cycles_per_label["body"];
+        assert!(3 * 4 == body_cycles);
+        assert!(vm_state.cycle_count as usize - 2 == loop_cycles);
+    }
+
+    #[test]
+    fn hot_instructions_are_sorted_by_clock_cycles() {
+        let program = triton_program!(push 1 push 2 push 3 pop 1 pop 1 add halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let profile = vm_state.run_with_profile().unwrap();
+
+        let hot_instructions = profile.hot_instructions();
+        let cycles = hot_instructions.iter().map(|&(_, c)| c).collect_vec();
+        assert!(vec![3, 2, 1, 1] == cycles);
+        assert!(Instruction::Push(bfe!(0)) == hot_instructions[0].0);
+        assert!(Instruction::Pop(NumberOfWords::N1) == hot_instructions[1].0);
+    }
+
     #[test]
     fn run_tvm_gcd() {
         let program = GREATEST_COMMON_DIVISOR.clone();
@@ -1215,6 +2036,85 @@ pub(crate) mod tests {
         assert!(bfe!(14) == stdout[0]);
     }
 
+    #[test]
+    fn state_report_reflects_mid_execution_state() {
+        let program = triton_program!(push 42 push 7 write_mem 1 sponge_init push 5 halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        for _ in 0..4 {
+            vm_state.step().unwrap();
+        }
+
+        let report = vm_state.state_report();
+        assert!(7 == report.instruction_pointer);
+        let_assert!(Some(Instruction::Push(five)) = report.current_instruction);
+        assert!(bfe!(5) == five);
+        assert!(bfe!(8) == report.op_stack_registers[0]);
+        assert!(vm_state.op_stack[ST1] == report.op_stack_registers[1]);
+        assert!(1 == report.op_stack_underflow_depth);
+        assert!(Some(Tip5::init().state) == report.sponge_state);
+        assert!(4 == report.cycle_count);
+        assert!(1 == report.num_initialized_ram_cells);
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("push 5"));
+        assert!(rendered.contains("clk: 4"));
+    }
+
+    #[test]
+    fn state_report_of_terminated_program_has_no_current_instruction() {
+        let program = triton_program!(halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        vm_state.run().unwrap();
+
+        let report = vm_state.state_report();
+        assert!(report.current_instruction.is_none());
+        assert!(report.sponge_state.is_none());
+        assert!(report.to_string().contains("END-OF-FILE"));
+    }
+
+    #[derive(Debug, Default, Clone, Eq, PartialEq)]
+    struct BTreeMapRam(BTreeMap<u64, BFieldElement>);
+
+    impl RamBackend for BTreeMapRam {
+        fn read(&self, address: BFieldElement) -> Option<BFieldElement> {
+            self.0.get(&address.value()).copied()
+        }
+
+        fn write(&mut self, address: BFieldElement, value: BFieldElement) {
+            self.0.insert(address.value(), value);
+        }
+
+        fn iter(&self) -> impl Iterator<Item = (BFieldElement, BFieldElement)> + '_ {
+            self.0
+                .iter()
+                .map(|(&address, &value)| (bfe!(address), value))
+        }
+    }
+
+    #[test]
+    fn running_program_with_custom_ram_backend_gives_identical_results() {
+        let program = triton_program!(
+            push 42 push 1000 write_mem 1 pop 1
+            push 1001 read_mem 1 pop 1 write_io 1
+            push 1000 read_mem 1 pop 1 write_io 1
+            push 1000 sponge_init sponge_absorb_mem pop 1
+            sponge_squeeze write_io 5 pop 5
+            halt
+        );
+        let non_determinism = NonDeterminism::default().with_ram([(bfe!(1001), bfe!(7))]);
+
+        let mut default_state = VMState::new(&program, [].into(), non_determinism);
+        let mut custom_state = default_state
+            .clone()
+            .with_ram_backend(BTreeMapRam::default());
+        default_state.run().unwrap();
+        custom_state.run().unwrap();
+
+        assert!(bfe_vec![7, 42] == default_state.public_output[..2]);
+        assert!(default_state.state_report() == custom_state.state_report());
+        assert!(default_state == custom_state.with_ram_backend(HashMap::new()));
+    }
+
     #[test]
     fn crash_triton_vm_and_print_vm_error() {
         let crashing_program = triton_program!(push 2 assert halt);
@@ -2453,6 +3353,35 @@ pub(crate) mod tests {
         assert!(18 == vm_state.op_stack[ST3].value());
     }
 
+    #[test]
+    fn op_stack_underflow_memory_does_not_collide_with_ram() {
+        // The op-stack pointer starts at 16. Move four elements into op-stack underflow memory,
+        // then write to RAM at the very same addresses the op-stack pointer just traversed.
+        let program = triton_program!(
+            push 100 push 101 push 102 push 103
+            push 200 push 16 write_mem 1 pop 1
+            push 201 push 17 write_mem 1 pop 1
+            push 202 push 18 write_mem 1 pop 1
+            push 203 push 19 write_mem 1 pop 1
+            halt
+        );
+
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let initial_op_stack_underflow = vm_state.op_stack.stack[..Digest::LEN].to_vec();
+        let_assert!(Ok(()) = vm_state.run());
+
+        let op_stack_underflow = &vm_state.op_stack.stack[..Digest::LEN];
+        assert!(initial_op_stack_underflow == op_stack_underflow);
+        assert!(bfe!(103) == vm_state.op_stack[ST0]);
+        assert!(bfe!(102) == vm_state.op_stack[ST1]);
+        assert!(bfe!(101) == vm_state.op_stack[ST2]);
+        assert!(bfe!(100) == vm_state.op_stack[ST3]);
+
+        for (address, value) in [(16, 200), (17, 201), (18, 202), (19, 203)] {
+            assert!(bfe!(value) == vm_state.ram[&bfe!(address)]);
+        }
+    }
+
     #[test]
     fn run_tvm_edgy_ram_writes() {
         let program = triton_program!(